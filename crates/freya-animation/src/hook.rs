@@ -1,9 +1,6 @@
 use std::{
     ops::Deref,
-    time::{
-        Duration,
-        Instant,
-    },
+    time::Duration,
 };
 
 use async_io::Timer;
@@ -266,7 +263,7 @@ impl<Animated: AnimatedValue> UseAnimation<Animated> {
             platform.send(UserEvent::RequestRedraw);
 
             let mut index = 0u128;
-            let mut prev_frame = Instant::now();
+            let mut prev_frame = animation_clock.now();
 
             if !peek_has_run_yet {
                 *has_run_yet.write() = true;
@@ -281,7 +278,9 @@ impl<Animated: AnimatedValue> UseAnimation<Animated> {
                 // Request another redraw to move the animation forward
                 platform.send(UserEvent::RequestRedraw);
 
-                let elapsed = animation_clock.correct_elapsed_duration(prev_frame.elapsed());
+                let elapsed = animation_clock.correct_elapsed_duration(
+                    animation_clock.now().saturating_duration_since(prev_frame),
+                );
 
                 index += elapsed.as_millis();
 
@@ -318,7 +317,7 @@ impl<Animated: AnimatedValue> UseAnimation<Animated> {
                     animated_value.write().prepare(direction);
                 }
 
-                prev_frame = Instant::now();
+                prev_frame = animation_clock.now();
             }
 
             is_running.set(false);
@@ -0,0 +1,146 @@
+use std::borrow::Cow;
+
+use freya_core::prelude::Color;
+use freya_edit::{
+    EditorLine,
+    TextEditor,
+};
+
+use crate::editor_data::CodeEditorData;
+
+/// Annotation painted in the lines of a [CodeEditor](crate::editor_ui::CodeEditor),
+/// see [CodeEditorData::set_decorations].
+#[derive(Clone, PartialEq, Debug)]
+pub enum Decoration {
+    /// Background of a whole line, also shown in the minimap.
+    LineBackground { line: usize, color: Color },
+    /// Text, such as an emoji or a symbol, shown in the gutter of a line.
+    GutterIcon {
+        line: usize,
+        icon: Cow<'static, str>,
+        color: Color,
+    },
+    /// Text shown after the end of a line, such as a blame annotation.
+    AfterText {
+        line: usize,
+        text: Cow<'static, str>,
+        color: Color,
+    },
+    /// Background of a range of text in UTF-16 code units.
+    Highlight { range: (usize, usize), color: Color },
+    /// Wavy underline of a range of text in UTF-16 code units.
+    Underline { range: (usize, usize), color: Color },
+}
+
+impl Decoration {
+    /// Line of the decorations that apply to a whole line.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::LineBackground { line, .. }
+            | Self::GutterIcon { line, .. }
+            | Self::AfterText { line, .. } => Some(*line),
+            Self::Highlight { .. } | Self::Underline { .. } => None,
+        }
+    }
+}
+
+impl CodeEditorData {
+    /// Replace the decorations of the given group, e.g. `blame` or `diff`, leaving the other groups untouched.
+    ///
+    /// Decorations are not moved when the text is edited, they must be set again instead.
+    pub fn set_decorations(
+        &mut self,
+        group: impl Into<Cow<'static, str>>,
+        decorations: Vec<Decoration>,
+    ) -> bool {
+        let group = group.into();
+        if self.decorations.get(&group) == Some(&decorations) {
+            return false;
+        }
+        self.decorations.insert(group, decorations);
+        true
+    }
+
+    /// Remove the decorations of the given group.
+    pub fn clear_decorations(&mut self, group: &str) -> bool {
+        self.decorations.remove(group).is_some()
+    }
+
+    /// Decorations of all the groups, sorted by group.
+    pub fn decorations(&self) -> impl Iterator<Item = &Decoration> {
+        self.decorations.values().flatten()
+    }
+
+    /// Decorations that apply to the whole given line.
+    pub(crate) fn line_decorations(&self, line: usize) -> impl Iterator<Item = &Decoration> {
+        self.decorations()
+            .filter(move |decoration| decoration.line() == Some(line))
+    }
+
+    /// Ranges of the highlight or underline decorations that are visible in the given line.
+    pub(crate) fn visible_decoration_ranges(
+        &self,
+        line: usize,
+        underlines: bool,
+    ) -> Vec<((usize, usize), Color)> {
+        self.decorations()
+            .filter_map(|decoration| match decoration {
+                Decoration::Highlight { range, color } if !underlines => Some((*range, *color)),
+                Decoration::Underline { range, color } if underlines => Some((*range, *color)),
+                _ => None,
+            })
+            .flat_map(|(range, color)| {
+                self.get_visible_ranges(&[range], EditorLine::Paragraph(line))
+                    .into_iter()
+                    .map(move |range| (range, color))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ropey::Rope;
+
+    use super::*;
+    use crate::languages::LanguageId;
+
+    #[test]
+    fn decorations_groups() {
+        let mut editor = CodeEditorData::new(Rope::from_str("a\nbc\n"), LanguageId::Unknown);
+        let blame = vec![Decoration::AfterText {
+            line: 1,
+            text: "Someone, 2 days ago".into(),
+            color: Color::GRAY,
+        }];
+
+        assert!(editor.set_decorations("blame", blame.clone()));
+        assert!(!editor.set_decorations("blame", blame));
+        assert!(editor.set_decorations(
+            "diff",
+            vec![
+                Decoration::LineBackground {
+                    line: 0,
+                    color: Color::GREEN,
+                },
+                Decoration::Underline {
+                    range: (0, 4),
+                    color: Color::RED,
+                },
+            ],
+        ));
+
+        assert_eq!(editor.decorations().count(), 3);
+        assert_eq!(editor.line_decorations(1).count(), 1);
+        // Ranges are split by line
+        assert_eq!(
+            editor.visible_decoration_ranges(1, true),
+            vec![((0, 2), Color::RED)]
+        );
+        assert!(editor.visible_decoration_ranges(1, false).is_empty());
+
+        assert!(editor.clear_decorations("blame"));
+        assert!(!editor.clear_decorations("blame"));
+        assert_eq!(editor.line_decorations(1).count(), 0);
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     borrow::Cow,
+    collections::BTreeMap,
     fmt::Display,
     ops::{
         Mul,
@@ -14,11 +15,19 @@ use freya_core::{
 };
 use freya_edit::*;
 use ropey::Rope;
-use tree_sitter::InputEdit;
+use rustc_hash::FxHashSet;
+use tree_sitter::{
+    InputEdit,
+    QueryError,
+};
 
 use crate::{
+    decorations::Decoration,
     editor_theme::SyntaxTheme,
-    languages::LanguageId,
+    languages::{
+        Grammar,
+        LanguageId,
+    },
     metrics::EditorMetrics,
     syntax::InputEditExt,
 };
@@ -27,11 +36,19 @@ pub struct CodeEditorData {
     pub(crate) history: EditorHistory,
     pub rope: Rope,
     pub(crate) selection: TextSelection,
+    pub(crate) secondary_selections: Vec<TextSelection>,
     pub(crate) last_saved_history_change: usize,
     pub(crate) metrics: EditorMetrics,
     pub(crate) dragging: TextDragging,
     pub(crate) scrolls: (i32, i32),
     pub(crate) pending_edit: Option<InputEdit>,
+    pub(crate) search: Option<SearchQuery>,
+    pub(crate) search_matches: Vec<(usize, usize)>,
+    /// Start lines of the folded regions.
+    pub(crate) folded_lines: FxHashSet<usize>,
+    pub(crate) decorations: BTreeMap<Cow<'static, str>, Vec<Decoration>>,
+    #[cfg(feature = "lsp")]
+    pub(crate) lsp: Option<crate::lsp::document::LspDocument>,
     pub language_id: LanguageId,
     theme: SyntaxTheme,
 }
@@ -41,12 +58,19 @@ impl CodeEditorData {
         Self {
             rope,
             selection: TextSelection::new_cursor(0),
+            secondary_selections: Vec::new(),
             history: EditorHistory::new(Duration::from_secs(1)),
             last_saved_history_change: 0,
             metrics: EditorMetrics::new(),
             dragging: TextDragging::default(),
             scrolls: (0, 0),
             pending_edit: None,
+            search: None,
+            search_matches: Vec::new(),
+            folded_lines: FxHashSet::default(),
+            decorations: BTreeMap::new(),
+            #[cfg(feature = "lsp")]
+            lsp: None,
             language_id,
             theme: SyntaxTheme::default(),
         }
@@ -63,7 +87,46 @@ impl CodeEditorData {
     pub fn parse(&mut self) {
         let edit = self.pending_edit.take();
         self.metrics
-            .run_parser(&self.rope, self.language_id, edit, &self.theme);
+            .run_parser(&self.rope, &self.language_id, edit, &self.theme);
+        self.update_folds();
+        self.reveal_cursor();
+        self.update_search_matches();
+        #[cfg(feature = "lsp")]
+        self.sync_lsp();
+    }
+
+    /// Keep the edit to incrementally re-parse the text, more than one edit before parsing forces a full re-parse.
+    fn push_edit(&mut self, edit: InputEdit) {
+        self.shift_folds(&edit);
+        if self.pending_edit.replace(edit).is_some() {
+            self.metrics.highlighter.invalidate_tree();
+        }
+    }
+
+    /// Highlight the matches of the given query, or stop highlighting them with `None`.
+    pub fn set_search(&mut self, search: Option<SearchQuery>) -> Result<(), SearchError> {
+        if let Some(search) = &search {
+            search.validate()?;
+        }
+        self.search = search;
+        self.update_search_matches();
+        Ok(())
+    }
+
+    pub fn search(&self) -> Option<&SearchQuery> {
+        self.search.as_ref()
+    }
+
+    /// Matches of the [search](Self::set_search) query, as of the last [parse](Self::parse).
+    pub fn search_matches(&self) -> &[(usize, usize)] {
+        &self.search_matches
+    }
+
+    fn update_search_matches(&mut self) {
+        self.search_matches = match &self.search {
+            Some(search) => self.find_all(search).unwrap_or_default(),
+            None => Vec::new(),
+        };
     }
 
     pub fn measure(&mut self, font_size: f32, font_family: &str) {
@@ -71,6 +134,60 @@ impl CodeEditorData {
             .measure_longest_line(font_size, font_family, &self.rope);
     }
 
+    /// Ranges in UTF-16 code units of the bracket at the cursor, or right before it, and of its matching bracket.
+    pub fn matching_brackets(&self) -> Option<[(usize, usize); 2]> {
+        let cursor = self.utf16_cu_to_char(self.cursor_pos());
+        let to_utf16_range = |bytes: Range<usize>| {
+            (
+                self.char_to_utf16_cu(self.rope.byte_to_char(bytes.start)),
+                self.char_to_utf16_cu(self.rope.byte_to_char(bytes.end)),
+            )
+        };
+        std::iter::once(cursor)
+            .chain(cursor.checked_sub(1))
+            .filter(|char_idx| *char_idx < self.rope.len_chars())
+            .find_map(|char_idx| {
+                let byte_idx = self.rope.char_to_byte(char_idx);
+                let matching = self.metrics.highlighter.matching_bracket(byte_idx)?;
+                Some([
+                    to_utf16_range(byte_idx..byte_idx + 1),
+                    to_utf16_range(matching),
+                ])
+            })
+    }
+
+    /// Indentation level of the given line, blank lines take the deepest level of their surrounding lines.
+    pub fn indent_level(&self, line: usize) -> usize {
+        let indentation = self.get_indentation() as usize;
+        let level_of = |line: usize| {
+            let mut columns = 0;
+            for ch in self.rope.get_line(line)?.chars() {
+                match ch {
+                    ' ' => columns += 1,
+                    '\t' => columns += indentation,
+                    '\n' | '\r' => return None,
+                    _ => return Some(columns / indentation),
+                }
+            }
+            None
+        };
+        level_of(line).unwrap_or_else(|| {
+            let previous = (0..line).rev().find_map(level_of).unwrap_or_default();
+            let next = (line + 1..self.rope.len_lines())
+                .find_map(level_of)
+                .unwrap_or_default();
+            previous.max(next)
+        })
+    }
+
+    /// Register a grammar to highlight the language of its name and the languages injected with it,
+    /// the text is highlighted with it the next time it is [parsed](Self::parse).
+    pub fn register_grammar(&mut self, grammar: Grammar) -> Result<(), QueryError> {
+        self.metrics
+            .highlighter
+            .register_grammar(grammar, &self.theme)
+    }
+
     pub fn set_theme(&mut self, theme: SyntaxTheme) {
         self.theme = theme;
     }
@@ -94,6 +211,25 @@ impl CodeEditorData {
                     scale_factor,
                 } = holder.as_ref().unwrap();
 
+                // Alt adds a new cursor, dragging afterwards selects a block
+                if self.dragging.alt {
+                    let char_position = paragraph.get_glyph_position_at_coordinate(
+                        location.mul(*scale_factor).to_i32().to_tuple(),
+                    );
+                    let pos = self
+                        .measure_selection(char_position.position as usize, editor_line)
+                        .pos();
+                    self.add_selection(TextSelection::new_cursor(pos));
+                    self.dragging.clicked = true;
+                    self.dragging.block_anchor = Some(pos);
+                    return true;
+                }
+
+                if !self.secondary_selections.is_empty() {
+                    self.clear_secondary_selections();
+                    processed = true;
+                }
+
                 let current_selection = self.selection().clone();
 
                 if self.dragging.shift || self.dragging.clicked {
@@ -152,6 +288,18 @@ impl CodeEditorData {
                         .get_glyph_position_at_coordinate(dist_position.to_i32().to_tuple());
                     let to = dist_char.position as usize;
 
+                    if let Some(block_anchor) = self.dragging.block_anchor {
+                        let pos = self.measure_selection(to, editor_line).pos();
+                        if pos != block_anchor {
+                            let current_selections =
+                                (self.selection.clone(), self.secondary_selections.clone());
+                            self.set_block_selection(block_anchor, pos);
+                            processed = current_selections.0 != self.selection
+                                || current_selections.1 != self.secondary_selections;
+                        }
+                        return processed;
+                    }
+
                     if self.get_selection().is_none() {
                         self.selection_mut().set_as_range();
                         processed = true;
@@ -170,6 +318,7 @@ impl CodeEditorData {
             }
             EditableEvent::Release => {
                 self.dragging.clicked = false;
+                self.dragging.block_anchor = None;
             }
             EditableEvent::KeyDown { key, modifiers } => {
                 match key {
@@ -177,6 +326,9 @@ impl CodeEditorData {
                     Key::Named(NamedKey::Shift) => {
                         self.dragging.shift = true;
                     }
+                    Key::Named(NamedKey::Alt) => {
+                        self.dragging.alt = true;
+                    }
                     // Handle editing
                     _ => {
                         let event = self.process_key(key, &modifiers, true, true, true);
@@ -186,6 +338,7 @@ impl CodeEditorData {
                             self.dragging = TextDragging::default();
                         }
                         if !event.is_empty() {
+                            self.reveal_cursor();
                             processed = true;
                         }
                     }
@@ -195,6 +348,9 @@ impl CodeEditorData {
                 if *key == Key::Named(NamedKey::Shift) {
                     self.dragging.shift = false;
                 }
+                if *key == Key::Named(NamedKey::Alt) {
+                    self.dragging.alt = false;
+                }
             }
         };
         processed
@@ -240,7 +396,7 @@ impl TextEditor for CodeEditorData {
         let new_end_line_byte = self.rope.line_to_byte(new_end_line);
         let new_end_col = new_end_byte - new_end_line_byte;
 
-        self.pending_edit = Some(InputEdit::new_edit(
+        self.push_edit(InputEdit::new_edit(
             start_byte,
             start_byte,
             new_end_byte,
@@ -283,7 +439,7 @@ impl TextEditor for CodeEditorData {
         let new_end_line_byte = self.rope.line_to_byte(new_end_line);
         let new_end_col = new_end_byte - new_end_line_byte;
 
-        self.pending_edit = Some(InputEdit::new_edit(
+        self.push_edit(InputEdit::new_edit(
             start_byte,
             start_byte,
             new_end_byte,
@@ -325,7 +481,7 @@ impl TextEditor for CodeEditorData {
         let removed_text_len = len_before_remove - len_after_remove;
 
         // After removal, new_end == start (the removed range collapses to a point).
-        self.pending_edit = Some(InputEdit::new_edit(
+        self.push_edit(InputEdit::new_edit(
             start_byte,
             old_end_byte,
             start_byte,
@@ -455,7 +611,52 @@ impl TextEditor for CodeEditorData {
         &mut self.selection
     }
 
+    fn secondary_selections(&self) -> &[TextSelection] {
+        &self.secondary_selections
+    }
+
+    fn secondary_selections_mut(&mut self) -> Option<&mut Vec<TextSelection>> {
+        Some(&mut self.secondary_selections)
+    }
+
     fn get_indentation(&self) -> u8 {
         4
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matching_brackets() {
+        let mut editor = CodeEditorData::new(
+            Rope::from_str("fn a(b: [u8; 2]) {\n    \"(\";\n}"),
+            LanguageId::Rust,
+        );
+        editor.parse();
+
+        // Bracket at the cursor
+        editor.move_cursor_to(4);
+        assert_eq!(editor.matching_brackets(), Some([(4, 5), (15, 16)]));
+        // Bracket right before the cursor
+        editor.move_cursor_to(18);
+        assert_eq!(editor.matching_brackets(), Some([(17, 18), (28, 29)]));
+        // Brackets in strings are not matched
+        editor.move_cursor_to(24);
+        assert_eq!(editor.matching_brackets(), None);
+    }
+
+    #[test]
+    fn indent_level() {
+        let editor = CodeEditorData::new(
+            Rope::from_str("a {\n    b {\n\n        c\n    }\n}\n"),
+            LanguageId::Unknown,
+        );
+
+        let levels = (0..6)
+            .map(|line| editor.indent_level(line))
+            .collect::<Vec<_>>();
+        assert_eq!(levels, vec![0, 1, 2, 2, 1, 0]);
+    }
+}
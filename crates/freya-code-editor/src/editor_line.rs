@@ -8,11 +8,13 @@ use freya_edit::{
 };
 use torin::{
     gaps::Gaps,
+    position::Position,
     prelude::Alignment,
     size::Size,
 };
 
 use crate::{
+    decorations::Decoration,
     editor_data::CodeEditorData,
     editor_theme::EditorTheme,
     syntax::TextNode,
@@ -27,6 +29,7 @@ pub struct EditorLineUI {
     pub(crate) read_only: bool,
     pub(crate) gutter: bool,
     pub(crate) show_whitespace: bool,
+    pub(crate) indent_guides: bool,
     pub(crate) font_family: Cow<'static, str>,
     pub(crate) theme: Readable<EditorTheme>,
 }
@@ -37,13 +40,14 @@ impl Component for EditorLineUI {
     }
     fn render(&self) -> impl IntoElement {
         let EditorLineUI {
-            mut editor,
+            editor,
             font_size,
             line_height,
             line_index,
             read_only,
             gutter,
             show_whitespace,
+            indent_guides,
             font_family,
             theme,
         } = self.clone();
@@ -55,9 +59,44 @@ impl Component for EditorLineUI {
 
         let longest_width = editor_data.metrics.longest_width;
         let line = editor_data.metrics.syntax_blocks.get_line(line_index);
-        let highlights = editor_data.get_visible_selection(EditorLine::Paragraph(line_index));
+        let highlights = editor_data.get_visible_selections(EditorLine::Paragraph(line_index));
+        let colored_highlights = editor_data
+            .visible_decoration_ranges(line_index, false)
+            .into_iter()
+            .chain(
+                editor_data
+                    .get_visible_ranges(
+                        editor_data.search_matches(),
+                        EditorLine::Paragraph(line_index),
+                    )
+                    .into_iter()
+                    .map(|highlight| (highlight, theme.search_match)),
+            )
+            .chain(
+                editor_data
+                    .get_visible_ranges(
+                        &editor_data
+                            .matching_brackets()
+                            .map(Vec::from)
+                            .unwrap_or_default(),
+                        EditorLine::Paragraph(line_index),
+                    )
+                    .into_iter()
+                    .map(|highlight| (highlight, theme.bracket_match)),
+            )
+            .collect::<Vec<_>>();
+        let underlines = editor_data.visible_decoration_ranges(line_index, true);
+        #[cfg(feature = "lsp")]
+        let underlines = [
+            underlines,
+            crate::lsp::ui::diagnostics_underlines(&editor_data, line_index, &theme),
+        ]
+        .concat();
         let gutter_width = font_size * 5.0;
+        let x_offset = if gutter { gutter_width } else { 0. };
+        let char_width = editor_data.metrics.char_width;
         let is_line_selected = editor_data.cursor_row() == line_index;
+        let is_folded = editor_data.is_folded(line_index);
 
         let on_mouse_down = {
             let mut editor = editor.clone();
@@ -78,8 +117,16 @@ impl Component for EditorLineUI {
         };
 
         let on_mouse_move = {
+            let mut editor = editor.clone();
             let font_family = font_family.clone();
             move |e: Event<MouseEventData>| {
+                #[cfg(feature = "lsp")]
+                crate::lsp::ui::request_paragraph_hover(
+                    editor.clone(),
+                    &holder.read(),
+                    e.element_location,
+                    line_index,
+                );
                 editor.write_if(|mut editor_editor| {
                     editor_editor.process(
                         font_size,
@@ -94,10 +141,13 @@ impl Component for EditorLineUI {
             }
         };
 
-        let cursor_index = if read_only {
-            None
+        let (cursor_index, secondary_cursor_indexes) = if read_only {
+            (None, Vec::new())
         } else {
-            is_line_selected.then(|| editor_data.cursor_col())
+            (
+                is_line_selected.then(|| editor_data.cursor_col()),
+                editor_data.get_visible_secondary_cursors(EditorLine::Paragraph(line_index)),
+            )
         };
         let gutter_color = if is_line_selected {
             theme.gutter_selected
@@ -109,12 +159,112 @@ impl Component for EditorLineUI {
             Some((s, e)) if s != e => true,
             _ => false,
         };
-        let line_background = if is_line_selected && !visible_selection {
+        let decorated_background = editor_data
+            .line_decorations(line_index)
+            .filter_map(|decoration| match decoration {
+                Decoration::LineBackground { color, .. } => Some(*color),
+                _ => None,
+            })
+            .last();
+        let line_background = if let Some(color) = decorated_background {
+            color
+        } else if is_line_selected && !visible_selection {
             theme.line_selected_background
         } else {
             Color::TRANSPARENT
         };
 
+        let gutter_icons = editor_data
+            .line_decorations(line_index)
+            .filter_map(|decoration| match decoration {
+                Decoration::GutterIcon { icon, color, .. } => Some(
+                    label()
+                        .position(Position::new_absolute().left(4.))
+                        .color(*color)
+                        .text(icon.clone())
+                        .into(),
+                ),
+                _ => None,
+            })
+            .collect::<Vec<Element>>();
+
+        let after_texts = editor_data
+            .line_decorations(line_index)
+            .filter_map(|decoration| match decoration {
+                Decoration::AfterText { text, color, .. } => {
+                    Some(label().color(*color).text(text.clone()).into())
+                }
+                _ => None,
+            })
+            .collect::<Vec<Element>>();
+
+        let indent_guides = if indent_guides {
+            let indent_width = editor_data.get_indentation() as f32 * char_width;
+            (0..editor_data.indent_level(line_index))
+                .map(|level| {
+                    rect()
+                        .position(
+                            Position::new_absolute().left(x_offset + level as f32 * indent_width),
+                        )
+                        .width(Size::px(1.))
+                        .height(Size::fill())
+                        .background(theme.indent_guide)
+                        .into()
+                })
+                .collect::<Vec<Element>>()
+        } else {
+            Vec::new()
+        };
+
+        let fold_toggle = editor_data.fold_region(line_index).map(|_| {
+            let mut editor = editor.clone();
+            label()
+                .position(Position::new_absolute().right(font_size * 0.5))
+                .color(theme.gutter_unselected)
+                .text(if is_folded { "▸" } else { "▾" })
+                .on_mouse_down(move |e: Event<MouseEventData>| {
+                    e.stop_propagation();
+                    editor.write_if(|mut editor| editor.toggle_fold(line_index));
+                })
+        });
+
+        // Placeholder of the hidden lines after the end of the folded line
+        let fold_placeholder = is_folded.then(|| {
+            let mut editor = editor.clone();
+            let line_len = editor_data.rope.line(line_index).len_chars();
+            rect()
+                .position(
+                    Position::new_absolute().left(x_offset + (line_len + 1) as f32 * char_width),
+                )
+                .padding(Gaps::new(0., 4., 0., 4.))
+                .corner_radius(4.)
+                .background(theme.line_selected_background)
+                .on_mouse_down(move |e: Event<MouseEventData>| {
+                    e.stop_propagation();
+                    editor.write_if(|mut editor| editor.unfold(line_index));
+                })
+                .child(label().color(theme.gutter_unselected).text("⋯"))
+        });
+
+        let after_text = (!after_texts.is_empty()).then(|| {
+            // Leave room for the fold placeholder
+            let line_len = editor_data.rope.line(line_index).len_chars();
+            let columns = line_len + if is_folded { 5 } else { 2 };
+            rect()
+                .position(Position::new_absolute().left(x_offset + columns as f32 * char_width))
+                .horizontal()
+                .height(Size::fill())
+                .cross_align(Alignment::Center)
+                .spacing(char_width * 2.)
+                .children(after_texts)
+        });
+
+        #[cfg(feature = "lsp")]
+        let popups =
+            crate::lsp::ui::line_popups(&editor_data, line_index, line_height, x_offset, &theme);
+        #[cfg(not(feature = "lsp"))]
+        let popups = Vec::new();
+
         rect()
             .horizontal()
             .height(Size::px(line_height))
@@ -131,7 +281,9 @@ impl Component for EditorLineUI {
                             label()
                                 .color(gutter_color)
                                 .text(format!("{} ", line_index + 1)),
-                        ),
+                        )
+                        .children(gutter_icons)
+                        .maybe_child(fold_toggle),
                 )
             })
             .child(
@@ -142,10 +294,13 @@ impl Component for EditorLineUI {
                     .cursor_color(theme.cursor)
                     .cursor_style(CursorStyle::Block)
                     .cursor_index(cursor_index)
+                    .secondary_cursor_indexes(secondary_cursor_indexes)
                     .cursor_mode(CursorMode::Expanded)
                     .vertical_align(VerticalAlign::Center)
-                    .highlights(highlights.map(|h| vec![h]))
+                    .highlights(highlights)
                     .highlight_color(theme.highlight)
+                    .colored_highlights(colored_highlights)
+                    .underlines(underlines)
                     .width(Size::px(longest_width))
                     .min_width(Size::fill())
                     .height(Size::fill())
@@ -168,5 +323,9 @@ impl Component for EditorLineUI {
                         Span::new(Cow::Owned(text.to_string())).color(span.0)
                     })),
             )
+            .children(indent_guides)
+            .maybe_child(fold_placeholder)
+            .maybe_child(after_text)
+            .children(popups)
     }
 }
@@ -8,6 +8,16 @@ pub struct EditorTheme {
     pub line_selected_background: Color,
     pub cursor: Color,
     pub highlight: Color,
+    pub search_match: Color,
+    pub bracket_match: Color,
+    pub indent_guide: Color,
+    pub minimap_viewport: Color,
+    pub diagnostic_error: Color,
+    pub diagnostic_warning: Color,
+    pub diagnostic_info: Color,
+    pub popup_background: Color,
+    pub popup_selected_background: Color,
+    pub popup_border: Color,
     pub text: Color,
     pub whitespace: Color,
 }
@@ -69,6 +79,16 @@ pub const DEFAULT_EDITOR_THEME: EditorTheme = EditorTheme {
     line_selected_background: Color::from_rgb(55, 55, 55),
     cursor: Color::WHITE,
     highlight: Color::from_rgb(80, 80, 80),
+    search_match: Color::from_af32rgb(0.4, 250, 189, 47),
+    bracket_match: Color::from_af32rgb(0.5, 131, 165, 152),
+    indent_guide: Color::from_af32rgb(0.15, 235, 219, 178),
+    minimap_viewport: Color::from_af32rgb(0.1, 235, 235, 235),
+    diagnostic_error: Color::from_rgb(251, 73, 52),
+    diagnostic_warning: Color::from_rgb(250, 189, 47),
+    diagnostic_info: Color::from_rgb(131, 165, 152),
+    popup_background: Color::from_rgb(40, 40, 40),
+    popup_selected_background: Color::from_rgb(80, 73, 69),
+    popup_border: Color::from_rgb(80, 80, 80),
     text: Color::WHITE,
     whitespace: Color::from_af32rgb(0.2, 223, 191, 142),
 };
@@ -7,6 +7,10 @@ use freya_components::scrollviews::{
 };
 use freya_core::prelude::*;
 use freya_edit::EditableEvent;
+use torin::{
+    content::Content,
+    size::Size,
+};
 
 use crate::{
     editor_data::CodeEditorData,
@@ -15,6 +19,7 @@ use crate::{
         DEFAULT_EDITOR_THEME,
         EditorTheme,
     },
+    minimap::Minimap,
 };
 
 #[derive(PartialEq, Clone)]
@@ -25,6 +30,8 @@ pub struct CodeEditor {
     read_only: bool,
     gutter: bool,
     show_whitespace: bool,
+    indent_guides: bool,
+    minimap: bool,
     font_family: Cow<'static, str>,
     a11y_id: AccessibilityId,
     theme: Readable<EditorTheme>,
@@ -42,6 +49,8 @@ impl CodeEditor {
             read_only: false,
             gutter: true,
             show_whitespace: true,
+            indent_guides: true,
+            minimap: false,
             font_family: Cow::Borrowed("Jetbrains Mono"),
             a11y_id,
             theme: DEFAULT_EDITOR_THEME.into(),
@@ -77,6 +86,18 @@ impl CodeEditor {
         self
     }
 
+    /// Sets whether vertical guides are rendered for every indentation level.
+    pub fn indent_guides(mut self, indent_guides: bool) -> Self {
+        self.indent_guides = indent_guides;
+        self
+    }
+
+    /// Sets whether a scaled-down overview of the text is rendered next to the editor.
+    pub fn minimap(mut self, minimap: bool) -> Self {
+        self.minimap = minimap;
+        self
+    }
+
     /// Sets the font family used in the editor. Defaults to `"Jetbrains Mono"`.
     pub fn font_family(mut self, font_family: impl Into<Cow<'static, str>>) -> Self {
         self.font_family = font_family.into();
@@ -99,6 +120,8 @@ impl Component for CodeEditor {
             read_only,
             gutter,
             show_whitespace,
+            indent_guides,
+            minimap,
             font_family,
             a11y_id,
             theme,
@@ -141,8 +164,23 @@ impl Component for CodeEditor {
             )
         });
 
+        #[cfg(feature = "lsp")]
+        {
+            let lsp = editor_data
+                .lsp()
+                .map(|lsp| (lsp.client().clone(), lsp.uri().to_string()));
+            let editor = editor.clone();
+            let mut watcher: Option<TaskHandle> = None;
+            use_side_effect_with_deps(&lsp, move |_| {
+                if let Some(watcher) = watcher.take() {
+                    watcher.cancel();
+                }
+                watcher = crate::lsp::ui::watch_diagnostics(editor.clone());
+            });
+        }
+
         let line_height = (font_size * line_height).floor();
-        let lines_len = editor_data.metrics.syntax_blocks.len();
+        let lines_len = editor_data.visible_lines_len();
 
         let on_mouse_down = move |_| {
             focus.request_focus();
@@ -172,6 +210,14 @@ impl Component for CodeEditor {
                     e.prevent_default();
                 }
 
+                #[cfg(feature = "lsp")]
+                if crate::lsp::ui::process_key_down(editor.clone(), &e, font_size, &font_family) {
+                    e.prevent_default();
+                    return;
+                }
+                #[cfg(feature = "lsp")]
+                let lsp_version = editor.peek().lsp.as_ref().map(|lsp| lsp.version);
+
                 const LINES_JUMP_ALT: usize = 5;
                 const LINES_JUMP_CONTROL: usize = 3;
 
@@ -212,7 +258,15 @@ impl Component for CodeEditor {
                                 })
                                 .collect::<Vec<EditableEvent>>()
                         }
-                        _ if e.code == Code::Escape
+                        // Alt is tracked to add cursors and select blocks with the mouse
+                        Key::Named(NamedKey::Alt) => {
+                            vec![EditableEvent::KeyDown {
+                                key: &e.key,
+                                modifiers: e.modifiers,
+                            }]
+                        }
+                        // Escape is only used to go back to a single cursor
+                        _ if (e.code == Code::Escape && editor.secondary_selections.is_empty())
                             || e.modifiers.contains(Modifiers::ALT)
                             || (e.modifiers.contains(Modifiers::CONTROL)
                                 && e.code == Code::KeyS) =>
@@ -235,6 +289,9 @@ impl Component for CodeEditor {
 
                     changed
                 });
+
+                #[cfg(feature = "lsp")]
+                crate::lsp::ui::update_completion(editor.clone(), &e.key, lsp_version);
             }
         };
 
@@ -248,35 +305,51 @@ impl Component for CodeEditor {
             }
         };
 
-        rect().expanded().background(theme.read().background).child(
-            rect()
-                .a11y_auto_focus(true)
-                .a11y_focusable(true)
-                .a11y_id(focus.a11y_id())
-                .maybe(!read_only, |el| {
-                    el.on_key_down(on_key_down).on_key_up(on_key_up)
-                })
-                .on_global_pointer_press(on_global_pointer_press)
-                .on_mouse_down(on_mouse_down)
-                .child(
-                    VirtualScrollView::new(move |line_index, _| {
-                        EditorLineUI {
-                            editor: editor.clone(),
-                            font_size,
-                            line_height,
-                            line_index,
-                            read_only,
-                            gutter,
-                            show_whitespace,
-                            font_family: font_family.clone(),
-                            theme: theme.clone(),
-                        }
-                        .into()
+        let minimap = minimap.then(|| Minimap {
+            editor: editor.clone(),
+            line_height,
+            theme: theme.clone(),
+        });
+
+        rect()
+            .expanded()
+            .horizontal()
+            .content(Content::flex())
+            .background(theme.read().background)
+            .child(
+                rect()
+                    .width(Size::flex(1.))
+                    .height(Size::fill())
+                    .a11y_auto_focus(true)
+                    .a11y_focusable(true)
+                    .a11y_id(focus.a11y_id())
+                    .maybe(!read_only, |el| {
+                        el.on_key_down(on_key_down).on_key_up(on_key_up)
                     })
-                    .scroll_controller(scroll_controller)
-                    .length(lines_len)
-                    .item_size(line_height),
-                ),
-        )
+                    .on_global_pointer_press(on_global_pointer_press)
+                    .on_mouse_down(on_mouse_down)
+                    .child(
+                        VirtualScrollView::new(move |index, _| {
+                            let line_index = editor.peek().visible_line(index);
+                            EditorLineUI {
+                                editor: editor.clone(),
+                                font_size,
+                                line_height,
+                                line_index,
+                                read_only,
+                                gutter,
+                                show_whitespace,
+                                indent_guides,
+                                font_family: font_family.clone(),
+                                theme: theme.clone(),
+                            }
+                            .into()
+                        })
+                        .scroll_controller(scroll_controller)
+                        .length(lines_len)
+                        .item_size(line_height),
+                    ),
+            )
+            .maybe_child(minimap)
     }
 }
@@ -0,0 +1,230 @@
+use freya_edit::TextEditor;
+use tree_sitter::InputEdit;
+
+use crate::editor_data::CodeEditorData;
+
+/// Lines of a multi-line syntax node, such as a function, which can be folded.
+/// Folding hides every line but the first.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FoldRegion {
+    pub start_line: usize,
+    /// Last line of the region, inclusive.
+    pub end_line: usize,
+}
+
+impl FoldRegion {
+    /// Whether the given line is hidden when this region is folded.
+    pub fn hides(&self, line: usize) -> bool {
+        self.start_line < line && line <= self.end_line
+    }
+}
+
+impl CodeEditorData {
+    /// Fold regions of the syntax tree, sorted by their start line.
+    pub fn fold_regions(&self) -> &[FoldRegion] {
+        &self.metrics.fold_regions
+    }
+
+    /// Fold region that starts in the given line, if any.
+    pub fn fold_region(&self, line: usize) -> Option<FoldRegion> {
+        let regions = &self.metrics.fold_regions;
+        regions
+            .binary_search_by_key(&line, |region| region.start_line)
+            .ok()
+            .map(|i| regions[i])
+    }
+
+    pub fn is_folded(&self, line: usize) -> bool {
+        self.folded_lines.contains(&line)
+    }
+
+    /// Fold the region that starts in the given line.
+    pub fn fold(&mut self, line: usize) -> bool {
+        if self.fold_region(line).is_none() || !self.folded_lines.insert(line) {
+            return false;
+        }
+        self.update_visible_lines();
+        true
+    }
+
+    /// Unfold the region that starts in the given line.
+    pub fn unfold(&mut self, line: usize) -> bool {
+        if !self.folded_lines.remove(&line) {
+            return false;
+        }
+        self.update_visible_lines();
+        true
+    }
+
+    pub fn toggle_fold(&mut self, line: usize) -> bool {
+        if self.is_folded(line) {
+            self.unfold(line)
+        } else {
+            self.fold(line)
+        }
+    }
+
+    pub fn unfold_all(&mut self) -> bool {
+        if self.folded_lines.is_empty() {
+            return false;
+        }
+        self.folded_lines.clear();
+        self.update_visible_lines();
+        true
+    }
+
+    /// Amount of lines that are not hidden by a fold.
+    pub fn visible_lines_len(&self) -> usize {
+        self.metrics.visible_lines.len()
+    }
+
+    /// Line shown in the given index of the lines not hidden by a fold.
+    pub fn visible_line(&self, index: usize) -> usize {
+        self.metrics
+            .visible_lines
+            .get(index)
+            .copied()
+            .unwrap_or(index)
+    }
+
+    /// Unfold the regions that hide the line of the cursor.
+    pub(crate) fn reveal_cursor(&mut self) -> bool {
+        let row = self.cursor_row();
+        let hiding = self
+            .metrics
+            .fold_regions
+            .iter()
+            .filter(|region| self.folded_lines.contains(&region.start_line) && region.hides(row))
+            .map(|region| region.start_line)
+            .collect::<Vec<_>>();
+        if hiding.is_empty() {
+            return false;
+        }
+        for line in hiding {
+            self.folded_lines.remove(&line);
+        }
+        self.update_visible_lines();
+        true
+    }
+
+    /// Move the folds after the given edit, folds that start in the removed lines are dropped.
+    pub(crate) fn shift_folds(&mut self, edit: &InputEdit) {
+        let start = edit.start_position.row;
+        let old_end = edit.old_end_position.row;
+        let new_end = edit.new_end_position.row;
+        self.folded_lines = self
+            .folded_lines
+            .iter()
+            .filter_map(|&line| {
+                if line <= start {
+                    Some(line)
+                } else if line > old_end {
+                    Some(line - old_end + new_end)
+                } else {
+                    None
+                }
+            })
+            .collect();
+    }
+
+    /// Drop the folds whose region is gone after parsing the text.
+    pub(crate) fn update_folds(&mut self) {
+        let regions = &self.metrics.fold_regions;
+        self.folded_lines.retain(|line| {
+            regions
+                .binary_search_by_key(line, |region| region.start_line)
+                .is_ok()
+        });
+        self.update_visible_lines();
+    }
+
+    fn update_visible_lines(&mut self) {
+        let lines_len = self.metrics.syntax_blocks.len();
+        let mut folded_regions = self
+            .metrics
+            .fold_regions
+            .iter()
+            .filter(|region| self.folded_lines.contains(&region.start_line))
+            .peekable();
+
+        let mut visible_lines = Vec::with_capacity(lines_len);
+        let mut line = 0;
+        while line < lines_len {
+            visible_lines.push(line);
+            // Skip the regions nested in a folded region
+            while folded_regions
+                .peek()
+                .is_some_and(|region| region.start_line < line)
+            {
+                folded_regions.next();
+            }
+            line = match folded_regions.next_if(|region| region.start_line == line) {
+                Some(region) => region.end_line + 1,
+                None => line + 1,
+            };
+        }
+        self.metrics.visible_lines = visible_lines;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ropey::Rope;
+
+    use super::*;
+    use crate::languages::LanguageId;
+
+    #[test]
+    fn fold_regions() {
+        let mut editor = CodeEditorData::new(
+            Rope::from_str("fn a() {\n    if b {\n        c();\n    }\n}\n\nfn d() {}\n"),
+            LanguageId::Rust,
+        );
+        editor.parse();
+
+        assert_eq!(
+            editor.fold_regions(),
+            &[
+                FoldRegion {
+                    start_line: 0,
+                    end_line: 4
+                },
+                FoldRegion {
+                    start_line: 1,
+                    end_line: 3
+                }
+            ]
+        );
+        assert_eq!(editor.visible_lines_len(), 8);
+
+        // Nested folds are hidden along with their parent
+        assert!(editor.fold(1));
+        assert_eq!(editor.visible_lines_len(), 6);
+        assert!(editor.fold(0));
+        assert!(!editor.fold(6));
+        assert_eq!(editor.visible_lines_len(), 4);
+        assert_eq!(editor.visible_line(1), 5);
+
+        // Moving the cursor into a folded region unfolds it
+        editor.move_cursor_to(editor.char_to_utf16_cu(editor.line_to_char(2)));
+        assert!(editor.reveal_cursor());
+        assert!(!editor.is_folded(0) && !editor.is_folded(1));
+        assert_eq!(editor.visible_lines_len(), 8);
+    }
+
+    #[test]
+    fn shift_folds() {
+        let mut editor = CodeEditorData::new(
+            Rope::from_str("\nfn a() {\n    b();\n}\n"),
+            LanguageId::Rust,
+        );
+        editor.parse();
+        assert!(editor.fold(1));
+
+        // Lines inserted before the fold move it
+        editor.insert("\n\n", 0);
+        editor.parse();
+        assert!(editor.is_folded(3));
+        assert_eq!(editor.visible_lines_len(), 5);
+    }
+}
@@ -1,6 +1,20 @@
-use std::fmt::Display;
+use std::{
+    borrow::Cow,
+    fmt::Display,
+};
 
-#[derive(Default, Clone, Debug, PartialEq, Copy)]
+use tree_sitter::Language;
+
+/// Language of the code in a [CodeEditorData](crate::editor_data::CodeEditorData).
+///
+/// Only [LanguageId::Rust], [LanguageId::Markdown], [LanguageId::Toml] and [LanguageId::Json] have a built-in [Grammar],
+/// [LanguageId::Python], [LanguageId::JavaScript], [LanguageId::TypeScript] and [LanguageId::Custom] languages are not
+/// highlighted until a [Grammar] with their [grammar name](LanguageId::grammar_name) is registered with
+/// [CodeEditorData::register_grammar](crate::editor_data::CodeEditorData::register_grammar).
+///
+/// **Breaking change**: [LanguageId] is no longer [Copy] since [LanguageId::Custom] owns its name,
+/// clone it where it used to be copied.
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum LanguageId {
     Rust,
     Python,
@@ -9,6 +23,9 @@ pub enum LanguageId {
     Markdown,
     Toml,
     Json,
+    /// Language highlighted with the [Grammar] of the given name,
+    /// see [CodeEditorData::register_grammar](crate::editor_data::CodeEditorData::register_grammar).
+    Custom(Cow<'static, str>),
     #[default]
     Unknown,
 }
@@ -23,12 +40,36 @@ impl Display for LanguageId {
             Self::Markdown => f.write_str("Markdown"),
             Self::Toml => f.write_str("TOML"),
             Self::Json => f.write_str("JSON"),
+            Self::Custom(name) => f.write_str(name),
             Self::Unknown => f.write_str("Unknown"),
         }
     }
 }
 
 impl LanguageId {
+    /// Identifier of the language in the Language Server Protocol.
+    pub fn lsp_id(&self) -> &str {
+        match self {
+            Self::Rust => "rust",
+            Self::Python => "python",
+            Self::JavaScript => "javascript",
+            Self::TypeScript => "typescript",
+            Self::Markdown => "markdown",
+            Self::Toml => "toml",
+            Self::Json => "json",
+            Self::Custom(name) => name,
+            Self::Unknown => "plaintext",
+        }
+    }
+
+    /// Name of the [Grammar] this language is highlighted with.
+    pub fn grammar_name(&self) -> Option<&str> {
+        match self {
+            Self::Unknown => None,
+            _ => Some(self.lsp_id()),
+        }
+    }
+
     pub fn parse(id: &str) -> Self {
         match id {
             "rs" => LanguageId::Rust,
@@ -42,3 +83,118 @@ impl LanguageId {
         }
     }
 }
+
+/// A tree-sitter grammar along with the queries to highlight a language.
+///
+/// Rust, Markdown, JSON and TOML grammars are built in, registering a [Grammar]
+/// with one of their names replaces them. Every other language, including [LanguageId::Python],
+/// [LanguageId::JavaScript] and [LanguageId::TypeScript], needs its [Grammar] registered with
+/// [CodeEditorData::register_grammar](crate::editor_data::CodeEditorData::register_grammar),
+/// e.g. with the `tree-sitter-python` crate:
+///
+/// ```rust,ignore
+/// editor.register_grammar(
+///     Grammar::new(
+///         "python",
+///         tree_sitter_python::LANGUAGE,
+///         tree_sitter_python::HIGHLIGHTS_QUERY,
+///     )
+///     .with_aliases(["py"]),
+/// )?;
+/// ```
+#[derive(Clone)]
+pub struct Grammar {
+    pub(crate) name: Cow<'static, str>,
+    pub(crate) aliases: Vec<Cow<'static, str>>,
+    pub(crate) language: Language,
+    pub(crate) highlights_query: Cow<'static, str>,
+    pub(crate) injections_query: Option<Cow<'static, str>>,
+}
+
+impl Grammar {
+    /// Create a [Grammar] with the given name, e.g. `python`, which is what
+    /// [LanguageId::grammar_name] and language injections refer to it by.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        language: impl Into<Language>,
+        highlights_query: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            aliases: Vec::new(),
+            language: language.into(),
+            highlights_query: highlights_query.into(),
+            injections_query: None,
+        }
+    }
+
+    /// Other names language injections can refer to this grammar by, e.g. `py`.
+    pub fn with_aliases(
+        mut self,
+        aliases: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+    ) -> Self {
+        self.aliases = aliases.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Query of the nodes to highlight with another grammar, such as code blocks in Markdown.
+    ///
+    /// The nodes are captured with `@injection.content` and their language is
+    /// either captured with `@injection.language` or set with `(#set! injection.language "name")`.
+    /// Matches with `(#set! injection.combined)` are parsed together as a single document.
+    pub fn with_injections_query(mut self, injections_query: impl Into<Cow<'static, str>>) -> Self {
+        self.injections_query = Some(injections_query.into());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn aliases(&self) -> &[Cow<'static, str>] {
+        &self.aliases
+    }
+
+    /// Names this grammar is referred to by.
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(&self.name)
+            .chain(&self.aliases)
+            .map(|name| name.as_ref())
+    }
+
+    /// Built-in grammar of the given name or alias.
+    pub(crate) fn builtin(name: &str) -> Option<Self> {
+        let grammar = match name {
+            "rust" | "rs" => Self::new(
+                "rust",
+                tree_sitter_rust::LANGUAGE,
+                tree_sitter_rust::HIGHLIGHTS_QUERY,
+            )
+            .with_aliases(["rs"]),
+            "json" => Self::new(
+                "json",
+                tree_sitter_json::LANGUAGE,
+                tree_sitter_json::HIGHLIGHTS_QUERY,
+            ),
+            "toml" => Self::new(
+                "toml",
+                tree_sitter_toml_ng::LANGUAGE,
+                tree_sitter_toml_ng::HIGHLIGHTS_QUERY,
+            ),
+            "markdown" | "md" => Self::new(
+                "markdown",
+                tree_sitter_md::LANGUAGE,
+                tree_sitter_md::HIGHLIGHT_QUERY_BLOCK,
+            )
+            .with_aliases(["md"])
+            .with_injections_query(tree_sitter_md::INJECTION_QUERY_BLOCK),
+            "markdown_inline" => Self::new(
+                "markdown_inline",
+                tree_sitter_md::INLINE_LANGUAGE,
+                tree_sitter_md::HIGHLIGHT_QUERY_INLINE,
+            ),
+            _ => return None,
+        };
+        Some(grammar)
+    }
+}
@@ -1,20 +1,48 @@
 pub mod constants;
+pub mod decorations;
 pub mod editor_data;
 pub mod editor_line;
 pub mod editor_theme;
 pub mod editor_ui;
+pub mod folding;
 pub mod languages;
+#[cfg(feature = "lsp")]
+pub mod lsp;
 pub mod metrics;
+pub mod minimap;
 pub mod syntax;
 
 pub mod prelude {
     pub use ropey::Rope;
+    pub use tree_sitter::QueryError;
 
+    #[cfg(feature = "lsp")]
+    pub use crate::lsp::{
+        client::{
+            LspClient,
+            LspError,
+        },
+        document::{
+            LspCompletion,
+            LspDocument,
+            LspHover,
+        },
+        protocol::{
+            CompletionItem,
+            Diagnostic,
+            DiagnosticSeverity,
+            Hover,
+            Location,
+            LspPosition,
+            LspRange,
+        },
+    };
     pub use crate::{
         constants::{
             BASE_FONT_SIZE,
             MAX_FONT_SIZE,
         },
+        decorations::Decoration,
         editor_data::CodeEditorData,
         editor_line::EditorLineUI,
         editor_theme::{
@@ -24,8 +52,13 @@ pub mod prelude {
             SyntaxTheme,
         },
         editor_ui::CodeEditor,
-        languages::LanguageId,
+        folding::FoldRegion,
+        languages::{
+            Grammar,
+            LanguageId,
+        },
         metrics::EditorMetrics,
+        minimap::Minimap,
         syntax::{
             InputEditExt,
             RopeChunkIter,
@@ -0,0 +1,659 @@
+use std::{
+    io::{
+        BufRead,
+        BufReader,
+        Read,
+        Write,
+    },
+    process::{
+        Child,
+        Command,
+        Stdio,
+    },
+    sync::{
+        Arc,
+        Mutex,
+        atomic::{
+            AtomicI64,
+            Ordering,
+        },
+    },
+    thread,
+};
+
+use futures_channel::{
+    mpsc::{
+        UnboundedReceiver,
+        UnboundedSender,
+        unbounded,
+    },
+    oneshot,
+};
+use rustc_hash::FxHashMap;
+use serde::de::DeserializeOwned;
+use serde_json::{
+    Value,
+    json,
+};
+
+use crate::lsp::protocol::{
+    CompletionItem,
+    CompletionResponse,
+    DefinitionResponse,
+    Diagnostic,
+    Hover,
+    HoverResponse,
+    Location,
+    LspPosition,
+    PublishDiagnosticsParams,
+};
+
+/// Error type for Language Server operations
+#[derive(Debug, thiserror::Error)]
+pub enum LspError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid message: {0}")]
+    InvalidMessage(#[from] serde_json::Error),
+
+    #[error("Server error {code}: {message}")]
+    Server { code: i64, message: String },
+
+    #[error("Language server disconnected")]
+    Disconnected,
+}
+
+type PendingRequests = FxHashMap<i64, oneshot::Sender<Result<Value, LspError>>>;
+
+/// State shared with the thread reading the server messages.
+struct LspShared {
+    writer: Mutex<Box<dyn Write + Send>>,
+    /// Requests waiting for a response, `None` once the server disconnected.
+    pending: Mutex<Option<PendingRequests>>,
+    diagnostics: Mutex<FxHashMap<String, Vec<Diagnostic>>>,
+    diagnostics_listeners: Mutex<Vec<UnboundedSender<String>>>,
+}
+
+impl LspShared {
+    fn send(&self, message: &Value) -> Result<(), LspError> {
+        let mut writer = self.writer.lock().unwrap();
+        write_message(&mut *writer, message)
+    }
+
+    fn handle_message(&self, message: Value) {
+        // Ids can be numbers or strings, ours are always numbers
+        let id = message.get("id").filter(|id| !id.is_null());
+        match (id, message.get("method").and_then(Value::as_str)) {
+            // Response to one of our requests
+            (Some(id), None) => {
+                let sender = id.as_i64().and_then(|id| {
+                    self.pending
+                        .lock()
+                        .unwrap()
+                        .as_mut()
+                        .and_then(|pending| pending.remove(&id))
+                });
+                if let Some(sender) = sender {
+                    let result = match message.get("error") {
+                        Some(error) => Err(LspError::Server {
+                            code: error
+                                .get("code")
+                                .and_then(Value::as_i64)
+                                .unwrap_or_default(),
+                            message: error
+                                .get("message")
+                                .and_then(Value::as_str)
+                                .unwrap_or_default()
+                                .to_string(),
+                        }),
+                        None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+                    };
+                    sender.send(result).ok();
+                }
+            }
+            // Requests from the server are not supported, but they still get a response so the server doesn't wait forever
+            (Some(id), Some(method)) => {
+                let result = match method {
+                    // One (empty) setting per requested item
+                    "workspace/configuration" => Value::Array(vec![
+                        Value::Null;
+                        message
+                            .pointer("/params/items")
+                            .and_then(Value::as_array)
+                            .map_or(0, Vec::len)
+                    ]),
+                    _ => Value::Null,
+                };
+                self.send(&json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+                    .ok();
+            }
+            (None, Some("textDocument/publishDiagnostics")) => {
+                let Some(Ok(params)) = message
+                    .get("params")
+                    .cloned()
+                    .map(serde_json::from_value::<PublishDiagnosticsParams>)
+                else {
+                    return;
+                };
+                self.diagnostics
+                    .lock()
+                    .unwrap()
+                    .insert(params.uri.clone(), params.diagnostics);
+                self.diagnostics_listeners
+                    .lock()
+                    .unwrap()
+                    .retain(|listener| listener.unbounded_send(params.uri.clone()).is_ok());
+            }
+            _ => {}
+        }
+    }
+
+    fn disconnect(&self) {
+        // Dropping the senders makes the pending requests fail
+        self.pending.lock().unwrap().take();
+        self.diagnostics_listeners.lock().unwrap().clear();
+    }
+}
+
+struct LspClientInner {
+    shared: Arc<LspShared>,
+    next_id: AtomicI64,
+    child: Mutex<Option<Child>>,
+}
+
+impl Drop for LspClientInner {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.child.lock().unwrap().take() {
+            child.kill().ok();
+            child.wait().ok();
+        }
+    }
+}
+
+/// Client for a Language Server speaking JSON-RPC, usually over the stdio of a spawned process.
+///
+/// Cloning it is cheap, all the clones talk to the same server. The server process is killed once all the clones are dropped.
+#[derive(Clone)]
+pub struct LspClient {
+    inner: Arc<LspClientInner>,
+}
+
+impl PartialEq for LspClient {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl LspClient {
+    /// Spawn the given Language Server command and talk to it through its stdin and stdout.
+    pub fn spawn(mut command: Command) -> Result<Self, LspError> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take().ok_or(LspError::Disconnected)?;
+        let stdout = child.stdout.take().ok_or(LspError::Disconnected)?;
+
+        let client = Self::new(stdout, stdin);
+        *client.inner.child.lock().unwrap() = Some(child);
+        Ok(client)
+    }
+
+    /// Talk to a Language Server through the given reader and writer.
+    pub fn new(reader: impl Read + Send + 'static, writer: impl Write + Send + 'static) -> Self {
+        let shared = Arc::new(LspShared {
+            writer: Mutex::new(Box::new(writer)),
+            pending: Mutex::new(Some(PendingRequests::default())),
+            diagnostics: Mutex::default(),
+            diagnostics_listeners: Mutex::default(),
+        });
+
+        thread::spawn({
+            let shared = shared.clone();
+            move || {
+                let mut reader = BufReader::new(reader);
+                while let Ok(Some(message)) = read_message(&mut reader) {
+                    shared.handle_message(message);
+                }
+                shared.disconnect();
+            }
+        });
+
+        Self {
+            inner: Arc::new(LspClientInner {
+                shared,
+                next_id: AtomicI64::new(0),
+                child: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Send a request and wait for its result.
+    pub async fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<T, LspError> {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = oneshot::channel();
+        self.inner
+            .shared
+            .pending
+            .lock()
+            .unwrap()
+            .as_mut()
+            .ok_or(LspError::Disconnected)?
+            .insert(id, sender);
+
+        self.inner.shared.send(&json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        }))?;
+
+        let result = receiver.await.map_err(|_| LspError::Disconnected)??;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Send a notification, these have no response.
+    pub fn notify(&self, method: &str, params: Value) -> Result<(), LspError> {
+        self.inner.shared.send(&json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        }))
+    }
+
+    /// Start the session, this must be done before anything else.
+    pub async fn initialize(&self, root_uri: Option<&str>) -> Result<Value, LspError> {
+        let result = self
+            .request(
+                "initialize",
+                json!({
+                    "processId": std::process::id(),
+                    "rootUri": root_uri,
+                    "capabilities": {
+                        "general": { "positionEncodings": ["utf-16"] },
+                        "textDocument": {
+                            "synchronization": {},
+                            "publishDiagnostics": {},
+                            "completion": { "completionItem": {} },
+                            "hover": { "contentFormat": ["plaintext", "markdown"] },
+                            "definition": { "linkSupport": true },
+                        },
+                    },
+                }),
+            )
+            .await?;
+        self.notify("initialized", json!({}))?;
+        Ok(result)
+    }
+
+    /// End the session and ask the server to exit.
+    pub async fn shutdown(&self) -> Result<(), LspError> {
+        self.request::<Value>("shutdown", Value::Null).await?;
+        self.notify("exit", Value::Null)
+    }
+
+    pub fn did_open(
+        &self,
+        uri: &str,
+        language_id: &str,
+        version: i32,
+        text: &str,
+    ) -> Result<(), LspError> {
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": {
+                    "uri": uri,
+                    "languageId": language_id,
+                    "version": version,
+                    "text": text,
+                },
+            }),
+        )
+    }
+
+    /// Send the whole new text of a document.
+    pub fn did_change(&self, uri: &str, version: i32, text: &str) -> Result<(), LspError> {
+        self.notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": uri, "version": version },
+                "contentChanges": [{ "text": text }],
+            }),
+        )
+    }
+
+    pub fn did_close(&self, uri: &str) -> Result<(), LspError> {
+        self.inner.shared.diagnostics.lock().unwrap().remove(uri);
+        self.notify(
+            "textDocument/didClose",
+            json!({ "textDocument": { "uri": uri } }),
+        )
+    }
+
+    pub async fn completion(
+        &self,
+        uri: &str,
+        position: LspPosition,
+    ) -> Result<Vec<CompletionItem>, LspError> {
+        let response: Option<CompletionResponse> = self
+            .request("textDocument/completion", position_params(uri, position))
+            .await?;
+        Ok(response.map(Vec::from).unwrap_or_default())
+    }
+
+    pub async fn hover(&self, uri: &str, position: LspPosition) -> Result<Option<Hover>, LspError> {
+        let response: Option<HoverResponse> = self
+            .request("textDocument/hover", position_params(uri, position))
+            .await?;
+        Ok(response.map(Hover::from))
+    }
+
+    pub async fn definition(
+        &self,
+        uri: &str,
+        position: LspPosition,
+    ) -> Result<Vec<Location>, LspError> {
+        let response: Option<DefinitionResponse> = self
+            .request("textDocument/definition", position_params(uri, position))
+            .await?;
+        Ok(response.map(Vec::from).unwrap_or_default())
+    }
+
+    /// Latest diagnostics published by the server for the given document.
+    pub fn diagnostics(&self, uri: &str) -> Vec<Diagnostic> {
+        self.inner
+            .shared
+            .diagnostics
+            .lock()
+            .unwrap()
+            .get(uri)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Receive the URI of every document whose diagnostics are published.
+    /// The stream ends when the server disconnects.
+    pub fn diagnostics_updates(&self) -> UnboundedReceiver<String> {
+        let (sender, receiver) = unbounded();
+        self.inner
+            .shared
+            .diagnostics_listeners
+            .lock()
+            .unwrap()
+            .push(sender);
+        receiver
+    }
+}
+
+fn position_params(uri: &str, position: LspPosition) -> Value {
+    json!({
+        "textDocument": { "uri": uri },
+        "position": position,
+    })
+}
+
+/// Write a message with its `Content-Length` header.
+pub(crate) fn write_message(writer: &mut impl Write, message: &Value) -> Result<(), LspError> {
+    let content = serde_json::to_vec(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n", content.len())?;
+    writer.write_all(&content)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read the next message, `None` once the stream ends.
+pub(crate) fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>, LspError> {
+    let mut content_length = None;
+    let mut header = String::new();
+    loop {
+        header.clear();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let Some(content_length) = content_length else {
+        return Err(LspError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Missing Content-Length header",
+        )));
+    };
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{
+        BufReader,
+        pipe,
+    };
+
+    use futures_lite::{
+        StreamExt,
+        future::block_on,
+    };
+    use serde_json::{
+        Value,
+        json,
+    };
+
+    use super::*;
+    use crate::lsp::protocol::{
+        DiagnosticSeverity,
+        LspRange,
+    };
+
+    /// Start a tiny Language Server that reports every `bad` word as an error.
+    fn mock_server() -> LspClient {
+        let (client_reader, mut server_writer) = pipe().unwrap();
+        let (server_reader, client_writer) = pipe().unwrap();
+
+        thread::spawn(move || {
+            let mut server_reader = BufReader::new(server_reader);
+            while let Ok(Some(message)) = read_message(&mut server_reader) {
+                let method = message["method"].as_str().unwrap_or_default();
+                let params = &message["params"];
+                let result = match method {
+                    "initialize" => json!({ "capabilities": { "textDocumentSync": 1 } }),
+                    "textDocument/didOpen" | "textDocument/didChange" => {
+                        let text = params["textDocument"]["text"]
+                            .as_str()
+                            .or(params["contentChanges"][0]["text"].as_str())
+                            .unwrap();
+                        let diagnostics = text
+                            .lines()
+                            .enumerate()
+                            .flat_map(|(line, text)| {
+                                text.match_indices("bad").map(move |(column, _)| {
+                                    let column = text[..column].encode_utf16().count();
+                                    json!({
+                                        "range": {
+                                            "start": { "line": line, "character": column },
+                                            "end": { "line": line, "character": column + 3 },
+                                        },
+                                        "severity": 1,
+                                        "message": "bad word",
+                                    })
+                                })
+                            })
+                            .collect::<Vec<_>>();
+                        write_message(
+                            &mut server_writer,
+                            &json!({
+                                "jsonrpc": "2.0",
+                                "method": "textDocument/publishDiagnostics",
+                                "params": {
+                                    "uri": params["textDocument"]["uri"],
+                                    "diagnostics": diagnostics,
+                                },
+                            }),
+                        )
+                        .unwrap();
+                        continue;
+                    }
+                    "textDocument/completion" => json!({
+                        "isIncomplete": false,
+                        "items": [{ "label": "println", "detail": "macro" }, { "label": "print" }],
+                    }),
+                    "textDocument/hover" => json!({
+                        "contents": { "kind": "markdown", "value": format!("hover {}", params["position"]) },
+                    }),
+                    "textDocument/definition" => json!([{
+                        "targetUri": params["textDocument"]["uri"],
+                        "targetRange": { "start": { "line": 0, "character": 0 }, "end": { "line": 1, "character": 0 } },
+                        "targetSelectionRange": { "start": { "line": 0, "character": 3 }, "end": { "line": 0, "character": 7 } },
+                    }]),
+                    "fail" => {
+                        write_message(
+                            &mut server_writer,
+                            &json!({
+                                "jsonrpc": "2.0",
+                                "id": message["id"],
+                                "error": { "code": -32601, "message": "Method not found" },
+                            }),
+                        )
+                        .unwrap();
+                        continue;
+                    }
+                    "exit" => break,
+                    _ => Value::Null,
+                };
+                if message.get("id").is_some() {
+                    write_message(
+                        &mut server_writer,
+                        &json!({ "jsonrpc": "2.0", "id": message["id"], "result": result }),
+                    )
+                    .unwrap();
+                }
+            }
+        });
+
+        LspClient::new(client_reader, client_writer)
+    }
+
+    #[test]
+    fn message_framing() {
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &json!({ "a": "ü" })).unwrap();
+        write_message(&mut buffer, &json!([1, 2])).unwrap();
+        assert!(buffer.starts_with(b"Content-Length: 10\r\n\r\n"));
+
+        let mut reader = BufReader::new(buffer.as_slice());
+        assert_eq!(
+            read_message(&mut reader).unwrap(),
+            Some(json!({ "a": "ü" }))
+        );
+        assert_eq!(read_message(&mut reader).unwrap(), Some(json!([1, 2])));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn server_requests() {
+        let (client_reader, mut server_writer) = pipe().unwrap();
+        let (server_reader, client_writer) = pipe().unwrap();
+        let _client = LspClient::new(client_reader, client_writer);
+        let mut server_reader = BufReader::new(server_reader);
+
+        // String ids are echoed as they are
+        write_message(
+            &mut server_writer,
+            &json!({
+                "jsonrpc": "2.0",
+                "id": "config",
+                "method": "workspace/configuration",
+                "params": { "items": [{ "section": "a" }, { "section": "b" }] },
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            read_message(&mut server_reader).unwrap(),
+            Some(json!({ "jsonrpc": "2.0", "id": "config", "result": [null, null] }))
+        );
+
+        write_message(
+            &mut server_writer,
+            &json!({ "jsonrpc": "2.0", "id": 3, "method": "window/workDoneProgress/create" }),
+        )
+        .unwrap();
+        assert_eq!(
+            read_message(&mut server_reader).unwrap(),
+            Some(json!({ "jsonrpc": "2.0", "id": 3, "result": null }))
+        );
+    }
+
+    #[test]
+    fn requests_and_diagnostics() {
+        let client = mock_server();
+        let mut updates = client.diagnostics_updates();
+
+        block_on(async {
+            client.initialize(None).await.unwrap();
+
+            client
+                .did_open("file:///a.rs", "rust", 0, "good\nok bad")
+                .unwrap();
+            assert_eq!(updates.next().await.as_deref(), Some("file:///a.rs"));
+            let diagnostics = client.diagnostics("file:///a.rs");
+            assert_eq!(diagnostics.len(), 1);
+            assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::Error));
+            assert_eq!(
+                diagnostics[0].range,
+                LspRange {
+                    start: LspPosition::new(1, 3),
+                    end: LspPosition::new(1, 6),
+                }
+            );
+
+            let items = client
+                .completion("file:///a.rs", LspPosition::new(0, 0))
+                .await
+                .unwrap();
+            assert_eq!(items[0].label, "println");
+            assert_eq!(items[0].detail.as_deref(), Some("macro"));
+
+            let hover = client
+                .hover("file:///a.rs", LspPosition::new(1, 4))
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(hover.contents, r#"hover {"character":4,"line":1}"#);
+
+            let locations = client
+                .definition("file:///a.rs", LspPosition::new(1, 4))
+                .await
+                .unwrap();
+            assert_eq!(locations[0].range.start, LspPosition::new(0, 3));
+
+            assert!(matches!(
+                client.request::<Value>("fail", Value::Null).await,
+                Err(LspError::Server { code: -32601, .. })
+            ));
+
+            client.shutdown().await.unwrap();
+            // The server exits so the pending and new requests fail
+            assert_eq!(updates.next().await, None);
+            assert!(matches!(
+                client.request::<Value>("shutdown", Value::Null).await,
+                Err(LspError::Disconnected)
+            ));
+        });
+    }
+}
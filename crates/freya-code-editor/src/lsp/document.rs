@@ -0,0 +1,316 @@
+use freya_edit::{
+    TextEditor,
+    TextSelection,
+};
+
+use crate::{
+    editor_data::CodeEditorData,
+    lsp::{
+        client::{
+            LspClient,
+            LspError,
+        },
+        protocol::{
+            CompletionItem,
+            Diagnostic,
+            LspPosition,
+            LspRange,
+        },
+    },
+};
+
+/// State of a [CodeEditorData] synced with a Language Server, see [CodeEditorData::set_lsp].
+pub struct LspDocument {
+    pub(crate) client: LspClient,
+    pub(crate) uri: String,
+    pub(crate) version: i32,
+    pub(crate) diagnostics: Vec<Diagnostic>,
+    pub(crate) completion: Option<LspCompletion>,
+    pub(crate) hover: Option<LspHover>,
+    /// Position the pointer is waiting on to request a hover.
+    pub(crate) hover_request: Option<usize>,
+}
+
+impl LspDocument {
+    pub fn client(&self) -> &LspClient {
+        &self.client
+    }
+
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn completion(&self) -> Option<&LspCompletion> {
+        self.completion.as_ref()
+    }
+
+    pub fn hover(&self) -> Option<&LspHover> {
+        self.hover.as_ref()
+    }
+}
+
+/// Completion items offered for the word being typed.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LspCompletion {
+    pub(crate) items: Vec<CompletionItem>,
+    pub(crate) selected: usize,
+    /// Start of the word replaced by the accepted item, in UTF-16 code units.
+    pub(crate) from: usize,
+}
+
+impl LspCompletion {
+    pub fn items(&self) -> &[CompletionItem] {
+        &self.items
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn from(&self) -> usize {
+        self.from
+    }
+}
+
+/// Information about the text under the pointer.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LspHover {
+    /// Hovered position in UTF-16 code units.
+    pub(crate) position: usize,
+    /// Messages of the diagnostics at the hovered position followed by the server hover contents.
+    pub(crate) messages: Vec<String>,
+}
+
+impl LspHover {
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn messages(&self) -> &[String] {
+        &self.messages
+    }
+}
+
+impl CodeEditorData {
+    /// Sync this editor with the given Language Server as the document of the given URI.
+    /// The server must have been [initialized](LspClient::initialize) already.
+    pub fn set_lsp(&mut self, client: LspClient, uri: impl Into<String>) -> Result<(), LspError> {
+        let uri = uri.into();
+        client.did_open(&uri, self.language_id.lsp_id(), 0, &self.rope.to_string())?;
+        self.lsp = Some(LspDocument {
+            diagnostics: client.diagnostics(&uri),
+            client,
+            uri,
+            version: 0,
+            completion: None,
+            hover: None,
+            hover_request: None,
+        });
+        Ok(())
+    }
+
+    pub fn lsp(&self) -> Option<&LspDocument> {
+        self.lsp.as_ref()
+    }
+
+    /// Convert a position in UTF-16 code units to a Language Server position.
+    pub fn to_lsp_position(&self, pos: usize) -> LspPosition {
+        let line = self.char_to_line(self.utf16_cu_to_char(pos));
+        let line_start = self.char_to_utf16_cu(self.line_to_char(line));
+        LspPosition::new(line as u32, (pos - line_start) as u32)
+    }
+
+    /// Convert a Language Server position to a position in UTF-16 code units,
+    /// positions past the end of a line or of the text are clamped.
+    pub fn from_lsp_position(&self, position: LspPosition) -> usize {
+        let line = position.line as usize;
+        if line >= self.len_lines() {
+            return self.len_utf16_cu();
+        }
+        let line_start = self.char_to_utf16_cu(self.line_to_char(line));
+        let line_len = self
+            .line(line)
+            .map(|line| {
+                line.text
+                    .trim_end_matches(['\n', '\r'])
+                    .encode_utf16()
+                    .count()
+            })
+            .unwrap_or_default();
+        line_start + (position.character as usize).min(line_len)
+    }
+
+    /// Convert a Language Server range to a range in UTF-16 code units.
+    pub fn from_lsp_range(&self, range: LspRange) -> (usize, usize) {
+        (
+            self.from_lsp_position(range.start),
+            self.from_lsp_position(range.end),
+        )
+    }
+
+    /// Send the current text to the Language Server.
+    pub(crate) fn sync_lsp(&mut self) {
+        let text = self.rope.to_string();
+        let cursor = self.cursor_pos();
+        let Some(lsp) = &mut self.lsp else {
+            return;
+        };
+        lsp.version += 1;
+        lsp.client.did_change(&lsp.uri, lsp.version, &text).ok();
+        lsp.hover = None;
+        if lsp
+            .completion
+            .as_ref()
+            .is_some_and(|completion| cursor < completion.from)
+        {
+            lsp.completion = None;
+        }
+    }
+
+    /// Pull the latest diagnostics of the document from the Language Server.
+    pub fn update_diagnostics(&mut self) -> bool {
+        let Some(lsp) = &mut self.lsp else {
+            return false;
+        };
+        let diagnostics = lsp.client.diagnostics(&lsp.uri);
+        if lsp.diagnostics == diagnostics {
+            return false;
+        }
+        lsp.diagnostics = diagnostics;
+        true
+    }
+
+    /// Start of the word that ends in the cursor, in UTF-16 code units.
+    fn word_start(&self) -> usize {
+        let cursor_char = self.utf16_cu_to_char(self.cursor_pos());
+        let word_len = self
+            .rope
+            .chars_at(cursor_char)
+            .reversed()
+            .take_while(|ch| ch.is_alphanumeric() || *ch == '_')
+            .count();
+        self.char_to_utf16_cu(cursor_char - word_len)
+    }
+
+    /// Show the given completion items, filtered by the word being typed.
+    pub fn set_completion(&mut self, mut items: Vec<CompletionItem>) -> bool {
+        let from = self.word_start();
+        let word = self
+            .rope
+            .slice(self.utf16_cu_to_char(from)..self.utf16_cu_to_char(self.cursor_pos()))
+            .to_string()
+            .to_lowercase();
+        let Some(lsp) = &mut self.lsp else {
+            return false;
+        };
+
+        items.retain(|item| item.label.to_lowercase().starts_with(&word));
+        let completion = (!items.is_empty()).then_some(LspCompletion {
+            items,
+            selected: 0,
+            from,
+        });
+        if lsp.completion == completion {
+            return false;
+        }
+        lsp.completion = completion;
+        true
+    }
+
+    /// Move the selected completion item up or down, wrapping around.
+    pub fn move_completion_selection(&mut self, down: bool) -> bool {
+        let Some(completion) = self.lsp.as_mut().and_then(|lsp| lsp.completion.as_mut()) else {
+            return false;
+        };
+        let len = completion.items.len();
+        completion.selected = if down {
+            (completion.selected + 1) % len
+        } else {
+            (completion.selected + len - 1) % len
+        };
+        true
+    }
+
+    /// Replace the word being typed with the selected completion item.
+    pub fn accept_completion(&mut self) -> bool {
+        let Some(completion) = self.lsp.as_mut().and_then(|lsp| lsp.completion.take()) else {
+            return false;
+        };
+        let item = &completion.items[completion.selected];
+        let cursor = self.cursor_pos();
+
+        self.clear_secondary_selections();
+        self.history.start_transaction();
+        self.remove(completion.from..cursor);
+        let inserted_text_len = self.insert(item.text(), completion.from);
+        self.history.commit_transaction();
+        *self.selection_mut() = TextSelection::new_cursor(completion.from + inserted_text_len);
+        true
+    }
+
+    pub fn close_completion(&mut self) -> bool {
+        self.lsp
+            .as_mut()
+            .and_then(|lsp| lsp.completion.take())
+            .is_some()
+    }
+
+    /// Show the diagnostics at the given position along with the given hover contents.
+    pub fn set_hover(&mut self, position: usize, contents: Option<String>) -> bool {
+        let diagnostics_messages = self
+            .lsp
+            .iter()
+            .flat_map(|lsp| lsp.diagnostics.iter())
+            .filter(|diagnostic| {
+                let (from, to) = self.from_lsp_range(diagnostic.range);
+                (from..=to).contains(&position)
+            })
+            .map(|diagnostic| diagnostic.message.clone())
+            .collect::<Vec<_>>();
+        let Some(lsp) = &mut self.lsp else {
+            return false;
+        };
+
+        let messages = diagnostics_messages
+            .into_iter()
+            .chain(contents.filter(|contents| !contents.trim().is_empty()))
+            .collect::<Vec<_>>();
+        let hover = (!messages.is_empty()).then_some(LspHover { position, messages });
+        if lsp.hover == hover {
+            return false;
+        }
+        lsp.hover = hover;
+        true
+    }
+
+    pub fn close_hover(&mut self) -> bool {
+        self.lsp.as_mut().and_then(|lsp| lsp.hover.take()).is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ropey::Rope;
+
+    use super::*;
+    use crate::languages::LanguageId;
+
+    #[test]
+    fn lsp_positions() {
+        let editor =
+            CodeEditorData::new(Rope::from_str("fn 😀()\nlet a = 1;\n"), LanguageId::Unknown);
+
+        // The emoji takes two UTF-16 code units
+        assert_eq!(editor.to_lsp_position(5), LspPosition::new(0, 5));
+        assert_eq!(editor.to_lsp_position(11), LspPosition::new(1, 3));
+        assert_eq!(editor.from_lsp_position(LspPosition::new(1, 3)), 11);
+
+        // Positions out of the text are clamped
+        assert_eq!(editor.from_lsp_position(LspPosition::new(0, 50)), 7);
+        assert_eq!(editor.from_lsp_position(LspPosition::new(9, 0)), 19);
+    }
+}
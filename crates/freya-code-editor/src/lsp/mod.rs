@@ -0,0 +1,11 @@
+//! Optional Language Server Protocol support, enabled with the `lsp` feature.
+//!
+//! Spawn a server with [LspClient::spawn], [initialize](LspClient::initialize) it and sync a
+//! [CodeEditorData](crate::editor_data::CodeEditorData) with it through [set_lsp](crate::editor_data::CodeEditorData::set_lsp).
+//! The [CodeEditor](crate::editor_ui::CodeEditor) then shows its diagnostics, completions (`Ctrl+Space`) and hovers,
+//! and jumps to definitions with `F12`.
+
+pub mod client;
+pub mod document;
+pub mod protocol;
+pub(crate) mod ui;
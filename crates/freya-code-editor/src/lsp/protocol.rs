@@ -0,0 +1,211 @@
+//! Subset of the Language Server Protocol types used by the editor.
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// Position in a text document, `character` is counted in UTF-16 code units.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct LspPosition {
+    pub line: u32,
+    pub character: u32,
+}
+
+impl LspPosition {
+    pub fn new(line: u32, character: u32) -> Self {
+        Self { line, character }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct LspRange {
+    pub start: LspPosition,
+    pub end: LspPosition,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+impl TryFrom<u8> for DiagnosticSeverity {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, String> {
+        match value {
+            1 => Ok(Self::Error),
+            2 => Ok(Self::Warning),
+            3 => Ok(Self::Information),
+            4 => Ok(Self::Hint),
+            _ => Err(format!("Unknown diagnostic severity {value}")),
+        }
+    }
+}
+
+impl From<DiagnosticSeverity> for u8 {
+    fn from(value: DiagnosticSeverity) -> Self {
+        match value {
+            DiagnosticSeverity::Error => 1,
+            DiagnosticSeverity::Warning => 2,
+            DiagnosticSeverity::Information => 3,
+            DiagnosticSeverity::Hint => 4,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub range: LspRange,
+    #[serde(default)]
+    pub severity: Option<DiagnosticSeverity>,
+    #[serde(default)]
+    pub source: Option<String>,
+    pub message: String,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PublishDiagnosticsParams {
+    pub uri: String,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionItem {
+    pub label: String,
+    #[serde(default)]
+    pub detail: Option<String>,
+    #[serde(default)]
+    pub insert_text: Option<String>,
+}
+
+impl CompletionItem {
+    /// Text inserted when the item is accepted.
+    pub fn text(&self) -> &str {
+        self.insert_text.as_deref().unwrap_or(&self.label)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum CompletionResponse {
+    List { items: Vec<CompletionItem> },
+    Items(Vec<CompletionItem>),
+}
+
+impl From<CompletionResponse> for Vec<CompletionItem> {
+    fn from(value: CompletionResponse) -> Self {
+        match value {
+            CompletionResponse::List { items } | CompletionResponse::Items(items) => items,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Hover {
+    /// Hover contents as plain text or markdown.
+    pub contents: String,
+    pub range: Option<LspRange>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MarkedString {
+    String(String),
+    Content { value: String },
+}
+
+impl From<MarkedString> for String {
+    fn from(value: MarkedString) -> Self {
+        match value {
+            MarkedString::String(value) | MarkedString::Content { value } => value,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HoverContents {
+    Many(Vec<MarkedString>),
+    One(MarkedString),
+}
+
+#[derive(Deserialize)]
+pub(crate) struct HoverResponse {
+    contents: HoverContents,
+    #[serde(default)]
+    range: Option<LspRange>,
+}
+
+impl From<HoverResponse> for Hover {
+    fn from(value: HoverResponse) -> Self {
+        let contents = match value.contents {
+            HoverContents::Many(contents) => contents
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+            HoverContents::One(contents) => contents.into(),
+        };
+        Self {
+            contents,
+            range: value.range,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Location {
+    pub uri: String,
+    pub range: LspRange,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum LocationOrLink {
+    Location(Location),
+    #[serde(rename_all = "camelCase")]
+    Link {
+        target_uri: String,
+        target_selection_range: LspRange,
+    },
+}
+
+impl From<LocationOrLink> for Location {
+    fn from(value: LocationOrLink) -> Self {
+        match value {
+            LocationOrLink::Location(location) => location,
+            LocationOrLink::Link {
+                target_uri,
+                target_selection_range,
+            } => Location {
+                uri: target_uri,
+                range: target_selection_range,
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum DefinitionResponse {
+    Many(Vec<LocationOrLink>),
+    One(LocationOrLink),
+}
+
+impl From<DefinitionResponse> for Vec<Location> {
+    fn from(value: DefinitionResponse) -> Self {
+        match value {
+            DefinitionResponse::Many(locations) => {
+                locations.into_iter().map(Location::from).collect()
+            }
+            DefinitionResponse::One(location) => vec![location.into()],
+        }
+    }
+}
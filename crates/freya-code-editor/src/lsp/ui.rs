@@ -0,0 +1,357 @@
+use std::{
+    ops::Mul,
+    time::Duration,
+};
+
+use async_io::Timer;
+use freya_core::{
+    elements::paragraph::ParagraphHolderInner,
+    prelude::*,
+};
+use freya_edit::{
+    EditorLine,
+    TextEditor,
+};
+use futures_lite::StreamExt;
+use torin::{
+    gaps::Gaps,
+    position::Position,
+    prelude::CursorPoint,
+    size::Size,
+};
+
+use crate::{
+    editor_data::CodeEditorData,
+    editor_theme::EditorTheme,
+    lsp::protocol::DiagnosticSeverity,
+};
+
+/// Time the pointer must rest on a position before its hover is requested.
+const HOVER_DELAY: Duration = Duration::from_millis(400);
+
+/// Max amount of completion items shown at once.
+const MAX_COMPLETION_ITEMS: usize = 10;
+
+/// Update the diagnostics of the editor every time the Language Server publishes them.
+pub(crate) fn watch_diagnostics(mut editor: Writable<CodeEditorData>) -> Option<TaskHandle> {
+    let mut updates = editor.peek().lsp.as_ref()?.client.diagnostics_updates();
+    Some(spawn(async move {
+        while let Some(uri) = updates.next().await {
+            editor.write_if(|mut editor| {
+                editor.lsp.as_ref().is_some_and(|lsp| lsp.uri == uri) && editor.update_diagnostics()
+            });
+        }
+    }))
+}
+
+/// Request the completion items for the cursor position.
+pub(crate) fn request_completion(mut editor: Writable<CodeEditorData>) {
+    let request = {
+        let editor = editor.peek();
+        editor.lsp.as_ref().map(|lsp| {
+            (
+                lsp.client.clone(),
+                lsp.uri.clone(),
+                editor.to_lsp_position(editor.cursor_pos()),
+            )
+        })
+    };
+    let Some((client, uri, position)) = request else {
+        return;
+    };
+    spawn(async move {
+        if let Ok(items) = client.completion(&uri, position).await {
+            editor.write_if(|mut editor| {
+                // Outdated if the cursor moved meanwhile
+                editor.to_lsp_position(editor.cursor_pos()) == position
+                    && editor.set_completion(items)
+            });
+        }
+    });
+}
+
+/// Request the hover of the given position once the pointer rests on it.
+pub(crate) fn request_hover(mut editor: Writable<CodeEditorData>, position: usize) {
+    let request = {
+        let editor = editor.peek();
+        editor
+            .lsp
+            .as_ref()
+            .filter(|lsp| lsp.hover_request != Some(position))
+            .map(|lsp| {
+                (
+                    lsp.client.clone(),
+                    lsp.uri.clone(),
+                    editor.to_lsp_position(position),
+                )
+            })
+    };
+    let Some((client, uri, lsp_position)) = request else {
+        return;
+    };
+    editor.write_if(|mut editor| {
+        if let Some(lsp) = &mut editor.lsp {
+            lsp.hover_request = Some(position);
+        }
+        editor.close_hover()
+    });
+
+    spawn(async move {
+        Timer::after(HOVER_DELAY).await;
+        let is_current = |editor: &CodeEditorData| {
+            editor
+                .lsp
+                .as_ref()
+                .is_some_and(|lsp| lsp.hover_request == Some(position))
+        };
+        if !is_current(&editor.peek()) {
+            return;
+        }
+        let contents = client
+            .hover(&uri, lsp_position)
+            .await
+            .ok()
+            .flatten()
+            .map(|hover| hover.contents);
+        editor.write_if(|mut editor| is_current(&editor) && editor.set_hover(position, contents));
+    });
+}
+
+/// Move the cursor to the definition of the symbol at the cursor, if it is in the same document.
+pub(crate) fn go_to_definition(mut editor: Writable<CodeEditorData>) {
+    let request = {
+        let editor = editor.peek();
+        editor.lsp.as_ref().map(|lsp| {
+            (
+                lsp.client.clone(),
+                lsp.uri.clone(),
+                editor.to_lsp_position(editor.cursor_pos()),
+            )
+        })
+    };
+    let Some((client, uri, position)) = request else {
+        return;
+    };
+    spawn(async move {
+        let Ok(locations) = client.definition(&uri, position).await else {
+            return;
+        };
+        if let Some(location) = locations.into_iter().find(|location| location.uri == uri) {
+            editor.write_if(|mut editor| {
+                let (from, _) = editor.from_lsp_range(location.range);
+                editor.clear_secondary_selections();
+                editor.clear_selection();
+                editor.move_cursor_to(from);
+                true
+            });
+        }
+    });
+}
+
+/// Diagnostics underlines of the given line.
+pub(crate) fn diagnostics_underlines(
+    editor: &CodeEditorData,
+    line_index: usize,
+    theme: &EditorTheme,
+) -> Vec<((usize, usize), Color)> {
+    let Some(lsp) = &editor.lsp else {
+        return Vec::new();
+    };
+    lsp.diagnostics
+        .iter()
+        .flat_map(|diagnostic| {
+            let color = match diagnostic.severity {
+                Some(DiagnosticSeverity::Warning) => theme.diagnostic_warning,
+                Some(DiagnosticSeverity::Information | DiagnosticSeverity::Hint) => {
+                    theme.diagnostic_info
+                }
+                Some(DiagnosticSeverity::Error) | None => theme.diagnostic_error,
+            };
+            let range = editor.from_lsp_range(diagnostic.range);
+            editor
+                .get_visible_ranges(&[range], EditorLine::Paragraph(line_index))
+                .into_iter()
+                .map(move |range| (range, color))
+        })
+        .collect()
+}
+
+/// Completion popup and hover tooltip anchored to the given line, if any.
+pub(crate) fn line_popups(
+    editor: &CodeEditorData,
+    line_index: usize,
+    line_height: f32,
+    x_offset: f32,
+    theme: &EditorTheme,
+) -> Vec<Element> {
+    let Some(lsp) = &editor.lsp else {
+        return Vec::new();
+    };
+    let char_width = editor.metrics.char_width;
+    let column_of = |pos: usize| {
+        let row = editor.char_to_line(editor.utf16_cu_to_char(pos));
+        let column = pos - editor.char_to_utf16_cu(editor.line_to_char(row));
+        (row, x_offset + column as f32 * char_width)
+    };
+    let popup = |left: f32| {
+        rect()
+            .position(Position::new_absolute().top(line_height).left(left))
+            .layer(Layer::Overlay)
+            .background(theme.popup_background)
+            .border(Border::new().fill(theme.popup_border).width(1.))
+            .corner_radius(4.)
+            .padding(4.)
+            .color(theme.text)
+    };
+
+    let mut popups = Vec::new();
+
+    if let Some(completion) = &lsp.completion {
+        let (row, left) = column_of(completion.from);
+        if row == line_index {
+            // Keep the selected item visible
+            let first = completion.selected.saturating_sub(MAX_COMPLETION_ITEMS - 1);
+            popups.push(
+                popup(left)
+                    .children(
+                        completion
+                            .items
+                            .iter()
+                            .enumerate()
+                            .skip(first)
+                            .take(MAX_COMPLETION_ITEMS)
+                            .map(|(i, item)| {
+                                let background = if i == completion.selected {
+                                    theme.popup_selected_background
+                                } else {
+                                    Color::TRANSPARENT
+                                };
+                                rect()
+                                    .horizontal()
+                                    .width(Size::fill_minimum())
+                                    .padding(Gaps::new(2., 6., 2., 6.))
+                                    .corner_radius(3.)
+                                    .background(background)
+                                    .child(label().text(item.label.clone()))
+                                    .maybe_child(item.detail.clone().map(|detail| {
+                                        label()
+                                            .margin(Gaps::new(0., 0., 0., 12.))
+                                            .color(theme.gutter_unselected)
+                                            .text(detail)
+                                    }))
+                                    .into()
+                            }),
+                    )
+                    .into(),
+            );
+        }
+    }
+
+    if let Some(hover) = &lsp.hover {
+        let (row, left) = column_of(hover.position);
+        if row == line_index {
+            popups.push(
+                popup(left)
+                    .max_width(Size::px(600.))
+                    .children(
+                        hover
+                            .messages
+                            .iter()
+                            .map(|message| paragraph().span(message.clone()).into()),
+                    )
+                    .into(),
+            );
+        }
+    }
+
+    popups
+}
+
+/// Request the hover of the text under the pointer in the paragraph of the given line.
+pub(crate) fn request_paragraph_hover(
+    editor: Writable<CodeEditorData>,
+    holder: &ParagraphHolder,
+    location: CursorPoint,
+    line_index: usize,
+) {
+    if editor.peek().dragging.clicked {
+        return;
+    }
+    let holder = holder.0.borrow();
+    let Some(ParagraphHolderInner {
+        paragraph,
+        scale_factor,
+    }) = holder.as_ref()
+    else {
+        return;
+    };
+    let char_position =
+        paragraph.get_glyph_position_at_coordinate(location.mul(*scale_factor).to_i32().to_tuple());
+    let position = editor
+        .peek()
+        .measure_selection(
+            char_position.position as usize,
+            EditorLine::Paragraph(line_index),
+        )
+        .pos();
+    request_hover(editor, position);
+}
+
+/// Handle the keys of the completion popup, hover tooltip, completion request (`Ctrl+Space`)
+/// and go to definition (`F12`). Returns whether the key was consumed.
+pub(crate) fn process_key_down(
+    mut editor: Writable<CodeEditorData>,
+    e: &KeyboardEventData,
+    font_size: f32,
+    font_family: &str,
+) -> bool {
+    let (has_completion, has_hover) = {
+        let editor = editor.peek();
+        let Some(lsp) = &editor.lsp else {
+            return false;
+        };
+        (lsp.completion.is_some(), lsp.hover.is_some())
+    };
+
+    match &e.key {
+        Key::Named(NamedKey::ArrowUp | NamedKey::ArrowDown) if has_completion => {
+            let down = e.key == Key::Named(NamedKey::ArrowDown);
+            editor.write_if(|mut editor| editor.move_completion_selection(down));
+        }
+        Key::Named(NamedKey::Enter | NamedKey::Tab) if has_completion => {
+            editor.write_if(|mut editor| {
+                editor.accept_completion();
+                editor.parse();
+                editor.measure(font_size, font_family);
+                true
+            });
+        }
+        Key::Named(NamedKey::Escape) if has_completion || has_hover => {
+            editor.write_if(|mut editor| editor.close_completion() | editor.close_hover());
+        }
+        Key::Character(ch) if ch == " " && e.modifiers.contains(Modifiers::CONTROL) => {
+            request_completion(editor);
+        }
+        Key::Named(NamedKey::F12) => {
+            go_to_definition(editor);
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Keep the completion up to date after the given key edited the text,
+/// or open it after typing a `.`.
+pub(crate) fn update_completion(
+    editor: Writable<CodeEditorData>,
+    key: &Key,
+    previous_version: Option<i32>,
+) {
+    let should_request = editor.peek().lsp.as_ref().is_some_and(|lsp| {
+        Some(lsp.version) != previous_version
+            && (lsp.completion.is_some() || *key == Key::Character(".".into()))
+    });
+    if should_request {
+        request_completion(editor);
+    }
+}
@@ -5,6 +5,7 @@ use tree_sitter::InputEdit;
 
 use crate::{
     editor_theme::SyntaxTheme,
+    folding::FoldRegion,
     languages::LanguageId,
     syntax::*,
 };
@@ -12,7 +13,12 @@ use crate::{
 pub struct EditorMetrics {
     pub(crate) syntax_blocks: SyntaxBlocks,
     pub(crate) longest_width: f32,
+    /// Width of a character of the monospaced font.
+    pub(crate) char_width: f32,
     pub(crate) highlighter: SyntaxHighlighter,
+    pub(crate) fold_regions: Vec<FoldRegion>,
+    /// Lines that are not hidden by a fold.
+    pub(crate) visible_lines: Vec<usize>,
 }
 
 impl Default for EditorMetrics {
@@ -26,7 +32,10 @@ impl EditorMetrics {
         Self {
             syntax_blocks: SyntaxBlocks::default(),
             longest_width: 0.0,
+            char_width: 0.0,
             highlighter: SyntaxHighlighter::new(),
+            fold_regions: Vec::new(),
+            visible_lines: Vec::new(),
         }
     }
 
@@ -52,17 +61,19 @@ impl EditorMetrics {
         let max_chars = rope.lines().map(|line| line.len_chars()).max().unwrap_or(0);
 
         self.longest_width = max_chars as f32 * char_width;
+        self.char_width = char_width;
     }
 
     pub fn run_parser(
         &mut self,
         rope: &Rope,
-        language_id: LanguageId,
+        language_id: &LanguageId,
         edit: Option<InputEdit>,
         theme: &SyntaxTheme,
     ) {
         self.highlighter.set_language(language_id, theme);
         self.highlighter
             .parse(rope, &mut self.syntax_blocks, edit, theme);
+        self.fold_regions = self.highlighter.fold_regions();
     }
 }
@@ -0,0 +1,190 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+};
+
+use freya_components::canvas::{
+    RenderCallback,
+    canvas,
+};
+use freya_core::prelude::*;
+use freya_engine::prelude::{
+    Paint,
+    PaintStyle,
+    SkRect,
+};
+use torin::size::Size;
+
+use crate::{
+    decorations::Decoration,
+    editor_data::CodeEditorData,
+    editor_theme::EditorTheme,
+    syntax::TextNode,
+};
+
+/// Width of the minimap.
+pub(crate) const MINIMAP_WIDTH: f32 = 100.;
+
+/// Height of a line in the minimap.
+const MINIMAP_LINE_HEIGHT: f32 = 2.;
+
+/// Width of a character in the minimap.
+const MINIMAP_CHAR_WIDTH: f32 = 1.;
+
+/// Position of the minimap as of the last time it was rendered.
+#[derive(Clone, Copy, Default)]
+struct MinimapLayout {
+    /// Index of the first visible line shown in the minimap.
+    first_line: usize,
+    height: f32,
+}
+
+/// Scaled-down overview of the text of a [CodeEditor](crate::editor_ui::CodeEditor),
+/// pressing it scrolls the editor to the pressed line.
+#[derive(Clone, PartialEq)]
+pub struct Minimap {
+    pub(crate) editor: Writable<CodeEditorData>,
+    pub(crate) line_height: f32,
+    pub(crate) theme: Readable<EditorTheme>,
+}
+
+impl Component for Minimap {
+    fn render(&self) -> impl IntoElement {
+        let Minimap {
+            editor,
+            line_height,
+            theme,
+        } = self.clone();
+
+        let layout = use_hook(|| Rc::new(Cell::new(MinimapLayout::default())));
+        let mut dragging = use_state(|| false);
+
+        let scroll_to = {
+            let mut editor = editor.clone();
+            let layout = layout.clone();
+            move |y: f32| {
+                let MinimapLayout { first_line, height } = layout.get();
+                let line = first_line as f32 + y / MINIMAP_LINE_HEIGHT;
+                editor.write_if(|mut editor| {
+                    // Center the pressed line in the editor, without scrolling past the last line.
+                    // The editor is as tall as the minimap
+                    let text_height = editor.visible_lines_len() as f32 * line_height;
+                    let min_scroll = -(text_height - height).max(0.);
+                    let scroll = (height / 2. - line * line_height).clamp(min_scroll, 0.) as i32;
+                    let changed = editor.scrolls.1 != scroll;
+                    editor.scrolls.1 = scroll;
+                    changed
+                });
+            }
+        };
+
+        let on_mouse_down = {
+            let mut scroll_to = scroll_to.clone();
+            move |e: Event<MouseEventData>| {
+                dragging.set(true);
+                scroll_to(e.element_location.y as f32);
+            }
+        };
+
+        let on_mouse_move = {
+            let mut scroll_to = scroll_to;
+            move |e: Event<MouseEventData>| {
+                if dragging() {
+                    scroll_to(e.element_location.y as f32);
+                }
+            }
+        };
+
+        let on_global_pointer_press = move |_: Event<PointerEventData>| {
+            dragging.set_if_modified(false);
+        };
+
+        canvas(RenderCallback::new(move |context| {
+            let editor = editor.peek();
+            let theme = theme.peek();
+            let area = context.layout_node.visible_area();
+            let (width, height) = (area.width(), area.height());
+
+            let lines_len = editor.visible_lines_len();
+            let capacity = (height / MINIMAP_LINE_HEIGHT).ceil() as usize;
+            // The editor is as tall as the minimap
+            let viewport_lines = height / line_height;
+            let first_visible_line = -editor.scrolls.1 as f32 / line_height;
+
+            // Scroll the minimap along with the editor when the text doesn't fit
+            let max_first_line = lines_len.saturating_sub(capacity);
+            let max_first_visible_line = (lines_len as f32 - viewport_lines).max(1.);
+            let first_line = ((first_visible_line / max_first_visible_line).clamp(0., 1.)
+                * max_first_line as f32)
+                .round() as usize;
+            layout.set(MinimapLayout { first_line, height });
+
+            let mut paint = Paint::default();
+            paint.set_style(PaintStyle::Fill);
+
+            for (row, index) in (first_line..lines_len.min(first_line + capacity)).enumerate() {
+                let line = editor.visible_line(index);
+                let y = row as f32 * MINIMAP_LINE_HEIGHT;
+
+                for decoration in editor.line_decorations(line) {
+                    if let Decoration::LineBackground { color, .. } = decoration {
+                        paint.set_color(*color);
+                        context
+                            .canvas
+                            .draw_rect(SkRect::new(0., y, width, y + MINIMAP_LINE_HEIGHT), &paint);
+                    }
+                }
+
+                let mut column = 0;
+                for (color, text_node) in editor.metrics.syntax_blocks.get_line(line) {
+                    let range = match text_node {
+                        TextNode::Range(range) => range.clone(),
+                        TextNode::LineOfChars { len, .. } => {
+                            column += len;
+                            continue;
+                        }
+                    };
+                    paint.set_color(*color);
+
+                    // Draw the words of the span, skipping the whitespace between them
+                    let mut word_start = None;
+                    for ch in editor.rope.slice(range).chars().chain([' ']) {
+                        if !ch.is_whitespace() {
+                            word_start.get_or_insert(column);
+                        } else if let Some(start) = word_start.take() {
+                            context.canvas.draw_rect(
+                                SkRect::new(
+                                    start as f32 * MINIMAP_CHAR_WIDTH,
+                                    y,
+                                    column as f32 * MINIMAP_CHAR_WIDTH,
+                                    y + MINIMAP_LINE_HEIGHT * 0.75,
+                                ),
+                                &paint,
+                            );
+                        }
+                        column += 1;
+                    }
+                    // Undo the trailing whitespace
+                    column -= 1;
+
+                    if column as f32 * MINIMAP_CHAR_WIDTH > width {
+                        break;
+                    }
+                }
+            }
+
+            // Area of the text visible in the editor
+            let y = (first_visible_line - first_line as f32) * MINIMAP_LINE_HEIGHT;
+            paint.set_color(theme.minimap_viewport);
+            context.canvas.draw_rect(
+                SkRect::new(0., y, width, y + viewport_lines * MINIMAP_LINE_HEIGHT),
+                &paint,
+            );
+        }))
+        .width(Size::px(MINIMAP_WIDTH))
+        .height(Size::fill())
+        .on_mouse_down(on_mouse_down)
+        .on_mouse_move(on_mouse_move)
+        .on_global_pointer_press(on_global_pointer_press)
+    }
+}
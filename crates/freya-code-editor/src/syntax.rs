@@ -1,4 +1,7 @@
-use std::ops::Range;
+use std::{
+    ops::Range,
+    rc::Rc,
+};
 
 use freya_core::prelude::Color;
 use ropey::Rope;
@@ -7,19 +10,28 @@ use smallvec::SmallVec;
 use tree_sitter::{
     InputEdit,
     Language,
+    Node,
     Parser,
     Point,
     Query,
     QueryCursor,
+    QueryError,
     StreamingIterator,
     Tree,
 };
 
 use crate::{
     editor_theme::SyntaxTheme,
-    languages::LanguageId,
+    folding::FoldRegion,
+    languages::{
+        Grammar,
+        LanguageId,
+    },
 };
 
+/// Max depth of languages injected in other injected languages.
+const MAX_INJECTION_DEPTH: usize = 3;
+
 fn capture_color(name: &str, theme: &SyntaxTheme) -> Color {
     match name {
         "attribute" => theme.attribute,
@@ -112,15 +124,42 @@ impl SyntaxBlocks {
 struct LangConfig {
     language: Language,
     query: Query,
+    injections_query: Option<Query>,
     capture_colors: Vec<Color>,
 }
 
+impl LangConfig {
+    fn new(grammar: &Grammar, theme: &SyntaxTheme) -> Result<Self, QueryError> {
+        let query = Query::new(&grammar.language, &grammar.highlights_query)?;
+        let injections_query = grammar
+            .injections_query
+            .as_ref()
+            .map(|injections_query| Query::new(&grammar.language, injections_query))
+            .transpose()?;
+        let capture_colors: Vec<Color> = query
+            .capture_names()
+            .iter()
+            .map(|name| resolve_capture_color(name, theme))
+            .collect();
+
+        Ok(Self {
+            language: grammar.language.clone(),
+            query,
+            injections_query,
+            capture_colors,
+        })
+    }
+}
+
 pub struct SyntaxHighlighter {
     parser: Parser,
+    injections_parser: Parser,
     tree: Option<Tree>,
-    config: Option<LangConfig>,
+    config: Option<Rc<LangConfig>>,
     cursor: QueryCursor,
     language_id: LanguageId,
+    /// Registered and already used built-in grammars, by name and alias.
+    grammars: FxHashMap<String, Rc<LangConfig>>,
 }
 
 impl Default for SyntaxHighlighter {
@@ -133,26 +172,66 @@ impl SyntaxHighlighter {
     pub fn new() -> Self {
         Self {
             parser: Parser::new(),
+            injections_parser: Parser::new(),
             tree: None,
             config: None,
             cursor: QueryCursor::new(),
             language_id: LanguageId::Unknown,
+            grammars: FxHashMap::default(),
         }
     }
 
-    pub fn set_language(&mut self, language_id: LanguageId, theme: &SyntaxTheme) {
-        if self.language_id == language_id {
+    pub fn set_language(&mut self, language_id: &LanguageId, theme: &SyntaxTheme) {
+        if self.language_id == *language_id {
             return;
         }
-        self.language_id = language_id;
+        self.language_id = language_id.clone();
+        self.load_language(theme);
+    }
+
+    fn load_language(&mut self, theme: &SyntaxTheme) {
         self.tree = None;
 
-        self.config = language_id.lang_config(theme);
+        let name = self.language_id.grammar_name().map(str::to_string);
+        self.config = name.and_then(|name| self.grammar(&name, theme));
         if let Some(cfg) = &self.config {
             let _ = self.parser.set_language(&cfg.language);
         }
     }
 
+    /// Register a grammar to highlight the language of its name and the languages injected with it,
+    /// replacing any other grammar with the same names.
+    pub fn register_grammar(
+        &mut self,
+        grammar: Grammar,
+        theme: &SyntaxTheme,
+    ) -> Result<(), QueryError> {
+        let config = Rc::new(LangConfig::new(&grammar, theme)?);
+        for name in grammar.names() {
+            self.grammars.insert(name.to_string(), config.clone());
+        }
+
+        if self
+            .language_id
+            .grammar_name()
+            .is_some_and(|name| grammar.names().any(|grammar_name| grammar_name == name))
+        {
+            self.load_language(theme);
+        }
+
+        Ok(())
+    }
+
+    /// Registered grammar of the given name or alias, falling back to the built-in grammars.
+    fn grammar(&mut self, name: &str, theme: &SyntaxTheme) -> Option<Rc<LangConfig>> {
+        if let Some(config) = self.grammars.get(name) {
+            return Some(config.clone());
+        }
+        let config = Rc::new(LangConfig::new(&Grammar::builtin(name)?, theme).ok()?);
+        self.grammars.insert(name.to_string(), config.clone());
+        Some(config)
+    }
+
     /// Discard the cached parse tree, forcing a full re-parse next time.
     pub fn invalidate_tree(&mut self) {
         self.tree = None;
@@ -174,24 +253,13 @@ impl SyntaxHighlighter {
             tree.edit(&input_edit);
         }
 
-        let new_tree = {
-            let len = rope.len_bytes();
-            self.parser.parse_with_options(
-                &mut |byte_offset: usize, _position: Point| {
-                    if byte_offset >= len {
-                        return &[] as &[u8];
-                    }
-                    let (chunk, chunk_start, _, _) = rope.chunk_at_byte(byte_offset);
-                    &chunk.as_bytes()[byte_offset - chunk_start..]
-                },
-                self.tree.as_ref(),
-                None,
-            )
-        };
+        let new_tree = parse_rope(&mut self.parser, rope, self.tree.as_ref());
 
         if let Some(new_tree) = new_tree {
-            if let Some(cfg) = &self.config {
-                build_syntax_blocks(&new_tree, cfg, &mut self.cursor, rope, syntax_blocks, theme);
+            if let Some(cfg) = self.config.clone() {
+                let mut spans = self.highlight(cfg, new_tree.clone(), rope, theme);
+                spans.sort_by_key(|s| s.start_byte);
+                build_lines_from_spans(rope, &spans, syntax_blocks, theme);
             } else {
                 build_plain_blocks(rope, syntax_blocks, theme);
             }
@@ -202,6 +270,88 @@ impl SyntaxHighlighter {
     }
 }
 
+/// Pairs of brackets highlighted when the cursor is on one of them.
+const BRACKETS: [(&str, &str); 3] = [("(", ")"), ("[", "]"), ("{", "}")];
+
+impl SyntaxHighlighter {
+    /// Fold regions of the syntax tree, with at most one region, the outermost one, per start line.
+    pub fn fold_regions(&self) -> Vec<FoldRegion> {
+        let mut regions: Vec<FoldRegion> = Vec::new();
+        let Some(tree) = &self.tree else {
+            return regions;
+        };
+
+        // Nodes are visited in order, parents before their children
+        let mut cursor = tree.walk();
+        if !cursor.goto_first_child() {
+            return regions;
+        }
+        loop {
+            let node = cursor.node();
+            let start_line = node.start_position().row;
+            let end_position = node.end_position();
+            // Nodes that include the line break end at the start of the next line
+            let end_line = if end_position.column == 0 {
+                end_position.row.saturating_sub(1)
+            } else {
+                end_position.row
+            };
+
+            // Children of single line nodes are single line as well
+            if end_line > start_line {
+                if regions
+                    .last()
+                    .is_none_or(|region| region.start_line < start_line)
+                {
+                    regions.push(FoldRegion {
+                        start_line,
+                        end_line,
+                    });
+                }
+                if cursor.goto_first_child() {
+                    continue;
+                }
+            }
+
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() || cursor.depth() == 0 {
+                    return regions;
+                }
+            }
+        }
+    }
+
+    /// Byte range of the bracket that matches the bracket at the given byte, if any.
+    pub fn matching_bracket(&self, byte_idx: usize) -> Option<Range<usize>> {
+        let node = self
+            .tree
+            .as_ref()?
+            .root_node()
+            .descendant_for_byte_range(byte_idx, byte_idx + 1)?;
+        if node.is_named() || node.start_byte() != byte_idx {
+            return None;
+        }
+        let kind = node.kind();
+        let (open, close) = BRACKETS
+            .into_iter()
+            .find(|(open, close)| kind == *open || kind == *close)?;
+
+        // Matching brackets are siblings, the brackets nested between them are in other nodes
+        let mut sibling = node;
+        loop {
+            sibling = if kind == open {
+                sibling.next_sibling()?
+            } else {
+                sibling.prev_sibling()?
+            };
+            let sibling_kind = sibling.kind();
+            if !sibling.is_named() && (sibling_kind == open || sibling_kind == close) {
+                return (sibling_kind != kind).then(|| sibling.byte_range());
+            }
+        }
+    }
+}
+
 pub trait InputEditExt {
     fn new_edit(
         start_byte: usize,
@@ -233,42 +383,179 @@ impl InputEditExt for InputEdit {
     }
 }
 
+fn parse_rope(parser: &mut Parser, rope: &Rope, old_tree: Option<&Tree>) -> Option<Tree> {
+    let len = rope.len_bytes();
+    parser.parse_with_options(
+        &mut |byte_offset: usize, _position: Point| {
+            if byte_offset >= len {
+                return &[] as &[u8];
+            }
+            let (chunk, chunk_start, _, _) = rope.chunk_at_byte(byte_offset);
+            &chunk.as_bytes()[byte_offset - chunk_start..]
+        },
+        old_tree,
+        None,
+    )
+}
+
 struct Span {
     start_byte: usize,
     end_byte: usize,
     color: Color,
+    /// Spans of injected languages have a greater depth than the language they are injected in.
+    depth: usize,
 }
 
-fn build_syntax_blocks(
-    tree: &Tree,
-    cfg: &LangConfig,
+/// A parsed language, either the editor language or one injected in it.
+struct Layer {
+    config: Rc<LangConfig>,
+    tree: Tree,
+    byte_range: Range<usize>,
+    depth: usize,
+}
+
+struct Injection {
+    language: String,
+    ranges: Vec<tree_sitter::Range>,
+    combined: bool,
+}
+
+impl SyntaxHighlighter {
+    /// Highlight spans of the given tree and of the languages injected in it.
+    fn highlight(
+        &mut self,
+        config: Rc<LangConfig>,
+        tree: Tree,
+        rope: &Rope,
+        theme: &SyntaxTheme,
+    ) -> Vec<Span> {
+        let mut spans: Vec<Span> = Vec::new();
+        let mut layers = vec![Layer {
+            config,
+            tree,
+            byte_range: 0..usize::MAX,
+            depth: 0,
+        }];
+
+        while let Some(layer) = layers.pop() {
+            let root = layer.tree.root_node();
+            self.cursor.set_byte_range(layer.byte_range);
+
+            let mut captures =
+                self.cursor
+                    .captures(&layer.config.query, root, RopeTextProvider { rope });
+            while let Some((match_result, capture_idx)) = {
+                captures.advance();
+                captures.get()
+            } {
+                let capture = &match_result.captures[*capture_idx];
+                let node = capture.node;
+                let color = layer.config.capture_colors[capture.index as usize];
+                spans.push(Span {
+                    start_byte: node.start_byte(),
+                    end_byte: node.end_byte(),
+                    color,
+                    depth: layer.depth,
+                });
+            }
+
+            let Some(injections_query) = &layer.config.injections_query else {
+                continue;
+            };
+            if layer.depth >= MAX_INJECTION_DEPTH {
+                continue;
+            }
+
+            for injection in find_injections(injections_query, &mut self.cursor, root, rope) {
+                let Some(config) = self.grammar(&injection.language, theme) else {
+                    continue;
+                };
+                if self
+                    .injections_parser
+                    .set_language(&config.language)
+                    .is_err()
+                    || self
+                        .injections_parser
+                        .set_included_ranges(&injection.ranges)
+                        .is_err()
+                {
+                    continue;
+                }
+                let Some(tree) = parse_rope(&mut self.injections_parser, rope, None) else {
+                    continue;
+                };
+                layers.push(Layer {
+                    config,
+                    tree,
+                    byte_range: injection.ranges[0].start_byte
+                        ..injection.ranges[injection.ranges.len() - 1].end_byte,
+                    depth: layer.depth + 1,
+                });
+            }
+        }
+
+        spans
+    }
+}
+
+/// Find the nodes to highlight with other languages.
+fn find_injections(
+    query: &Query,
     cursor: &mut QueryCursor,
+    root: Node,
     rope: &Rope,
-    syntax_blocks: &mut SyntaxBlocks,
-    theme: &SyntaxTheme,
-) {
-    let root = tree.root_node();
-    cursor.set_byte_range(0..usize::MAX);
-
-    let mut spans: Vec<Span> = Vec::new();
-    let mut captures = cursor.captures(&cfg.query, root, RopeTextProvider { rope });
-
-    while let Some((match_result, capture_idx)) = {
-        captures.advance();
-        captures.get()
-    } {
-        let capture = &match_result.captures[*capture_idx];
-        let node = capture.node;
-        let color = cfg.capture_colors[capture.index as usize];
-        spans.push(Span {
-            start_byte: node.start_byte(),
-            end_byte: node.end_byte(),
-            color,
-        });
+) -> Vec<Injection> {
+    let content_index = query.capture_index_for_name("injection.content");
+    let language_index = query.capture_index_for_name("injection.language");
+
+    let mut injections: Vec<Injection> = Vec::new();
+    let mut matches = cursor.matches(query, root, RopeTextProvider { rope });
+    while let Some(query_match) = matches.next() {
+        let properties = query.property_settings(query_match.pattern_index);
+        let mut language = properties
+            .iter()
+            .find(|property| &*property.key == "injection.language")
+            .and_then(|property| property.value.as_deref())
+            .map(str::to_string);
+        let combined = properties
+            .iter()
+            .any(|property| &*property.key == "injection.combined");
+
+        let mut ranges = Vec::new();
+        for capture in query_match.captures {
+            if Some(capture.index) == language_index {
+                language = Some(rope.byte_slice(capture.node.byte_range()).to_string());
+            } else if Some(capture.index) == content_index {
+                ranges.push(capture.node.range());
+            }
+        }
+
+        let Some(language) = language.map(|language| language.trim().to_lowercase()) else {
+            continue;
+        };
+        if ranges.is_empty() {
+            continue;
+        }
+
+        if let Some(injection) = injections
+            .iter_mut()
+            .find(|injection| combined && injection.combined && injection.language == language)
+        {
+            injection.ranges.extend(ranges);
+        } else {
+            injections.push(Injection {
+                language,
+                ranges,
+                combined,
+            });
+        }
     }
 
-    spans.sort_by_key(|s| s.start_byte);
-    build_lines_from_spans(rope, &spans, syntax_blocks, theme);
+    for injection in &mut injections {
+        injection.ranges.sort_by_key(|range| range.start_byte);
+    }
+
+    injections
 }
 
 fn build_lines_from_spans(
@@ -311,13 +598,15 @@ fn build_lines_from_spans(
         let mut byte_colors: SmallVec<[Color; 256]> =
             smallvec::smallvec![theme.text; content_bytes];
 
-        let mut si = span_idx;
-        while si < spans.len() && spans[si].start_byte < content_end_byte {
-            let span = &spans[si];
-            si += 1;
-            if span.end_byte <= line_start_byte {
-                continue;
-            }
+        let mut line_highlights = spans[span_idx..]
+            .iter()
+            .take_while(|span| span.start_byte < content_end_byte)
+            .filter(|span| span.end_byte > line_start_byte)
+            .collect::<SmallVec<[&Span; 16]>>();
+        // Injected languages are painted over the language they are injected in
+        line_highlights.sort_by_key(|span| span.depth);
+
+        for span in line_highlights {
             let s = span.start_byte.max(line_start_byte) - line_start_byte;
             let e = span.end_byte.min(content_end_byte) - line_start_byte;
             if s < e {
@@ -427,39 +716,71 @@ impl<'a> Iterator for RopeChunkIter<'a> {
     }
 }
 
-impl LanguageId {
-    fn lang_config(&self, theme: &SyntaxTheme) -> Option<LangConfig> {
-        let (language, highlights_query) = match self {
-            LanguageId::Rust => (
-                tree_sitter_rust::LANGUAGE.into(),
-                tree_sitter_rust::HIGHLIGHTS_QUERY,
-            ),
-            LanguageId::Json => (
-                tree_sitter_json::LANGUAGE.into(),
-                tree_sitter_json::HIGHLIGHTS_QUERY,
-            ),
-            LanguageId::Toml => (
-                tree_sitter_toml_ng::LANGUAGE.into(),
-                tree_sitter_toml_ng::HIGHLIGHTS_QUERY,
-            ),
-            LanguageId::Markdown => (
-                tree_sitter_md::LANGUAGE.into(),
-                tree_sitter_md::HIGHLIGHT_QUERY_BLOCK,
-            ),
-            _ => return None,
-        };
+#[cfg(test)]
+mod test {
+    use super::*;
 
-        let query = Query::new(&language, highlights_query).ok()?;
-        let capture_colors: Vec<Color> = query
-            .capture_names()
-            .iter()
-            .map(|name| resolve_capture_color(name, theme))
-            .collect();
+    fn highlight(language_id: LanguageId, text: &str, grammars: Vec<Grammar>) -> SyntaxBlocks {
+        let theme = SyntaxTheme::default();
+        let mut highlighter = SyntaxHighlighter::new();
+        for grammar in grammars {
+            highlighter.register_grammar(grammar, &theme).unwrap();
+        }
+        highlighter.set_language(&language_id, &theme);
+        let mut syntax_blocks = SyntaxBlocks::default();
+        highlighter.parse(&Rope::from_str(text), &mut syntax_blocks, None, &theme);
+        syntax_blocks
+    }
 
-        Some(LangConfig {
-            language,
-            query,
-            capture_colors,
-        })
+    fn colors(line: &[(Color, TextNode)]) -> Vec<Color> {
+        line.iter().map(|(color, _)| *color).collect()
+    }
+
+    #[test]
+    fn custom_grammar() {
+        let theme = SyntaxTheme::default();
+        let grammar = Grammar::new(
+            "jsonc",
+            tree_sitter_json::LANGUAGE,
+            tree_sitter_json::HIGHLIGHTS_QUERY,
+        );
+
+        let syntax_blocks = highlight(LanguageId::Custom("jsonc".into()), "[1]", vec![grammar]);
+        assert!(colors(syntax_blocks.get_line(0)).contains(&theme.number));
+
+        let syntax_blocks = highlight(LanguageId::Custom("jsonc".into()), "[1]", Vec::new());
+        assert_eq!(colors(syntax_blocks.get_line(0)), vec![theme.text]);
+
+        let invalid = Grammar::new("invalid", tree_sitter_json::LANGUAGE, "(unknown_node)");
+        assert!(
+            SyntaxHighlighter::new()
+                .register_grammar(invalid, &theme)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn injections() {
+        let theme = SyntaxTheme::default();
+
+        // Fenced code in Markdown
+        let syntax_blocks = highlight(
+            LanguageId::Markdown,
+            "# Title\n```rust\nfn main() {}\n```\n",
+            Vec::new(),
+        );
+        assert_eq!(colors(syntax_blocks.get_line(2))[0], theme.keyword);
+
+        // JSON strings in Rust
+        let rust = Grammar::new(
+            "rust",
+            tree_sitter_rust::LANGUAGE,
+            tree_sitter_rust::HIGHLIGHTS_QUERY,
+        )
+        .with_injections_query(
+            r#"((string_content) @injection.content (#set! injection.language "json"))"#,
+        );
+        let syntax_blocks = highlight(LanguageId::Rust, r#"let a = "[1]";"#, vec![rust]);
+        assert!(colors(syntax_blocks.get_line(0)).contains(&theme.number));
     }
 }
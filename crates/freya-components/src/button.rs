@@ -285,6 +285,9 @@ impl Component for Button {
             .a11y_id(focus.a11y_id())
             .a11y_focusable(self.enabled && self.focusable)
             .a11y_role(AccessibilityRole::Button)
+            .maybe(!self.enabled, |el| {
+                el.a11y_builder(|builder| builder.set_disabled())
+            })
             .background(background.mul_if(!self.enabled, 0.9))
             .border(border)
             .padding(theme_layout.padding)
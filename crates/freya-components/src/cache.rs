@@ -222,6 +222,14 @@ impl AssetCacher {
     pub fn size(&self) -> usize {
         self.registry.read().len()
     }
+
+    /// Check if any asset is currently being fetched.
+    pub fn is_loading(&self) -> bool {
+        self.registry
+            .peek()
+            .values()
+            .any(|asset_state| matches!(asset_state.asset, Asset::Loading))
+    }
 }
 
 /// Start listening to an asset given a [AssetConfiguration].
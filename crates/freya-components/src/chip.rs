@@ -195,6 +195,9 @@ impl Component for Chip {
             .a11y_id(focus.a11y_id())
             .a11y_focusable(self.enabled)
             .a11y_role(AccessibilityRole::Button)
+            .maybe(!self.enabled, |el| {
+                el.a11y_builder(|builder| builder.set_disabled())
+            })
             .maybe(self.enabled, |rect| rect.on_press(on_press))
             .on_pointer_enter(on_pointer_enter)
             .on_pointer_leave(on_pointer_leave)
@@ -12,8 +12,14 @@ use torin::{
 #[derive(Clone)]
 struct DraggableCanvasRegistry(State<Vec<usize>>);
 
+/// Zoom limits of a [DraggableCanvas].
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 4.;
+
 /// A canvas container that allows draggable elements within it.
 ///
+/// It can be panned with the mouse wheel or by dragging with a finger, and zoomed with pinch gestures.
+///
 /// # Example
 ///
 /// ```rust
@@ -71,6 +77,7 @@ impl Component for DraggableCanvas {
         use_provide_context(|| DraggableCanvasRegistry(State::create(Vec::new())));
         let focus = use_focus();
         let mut offset = use_state(CursorPoint::zero);
+        let mut zoom = use_state(|| 1.);
         let mut dragging_position = use_state::<Option<CursorPoint>>(|| None);
 
         let on_mouse_move = move |e: Event<MouseEventData>| {
@@ -99,6 +106,19 @@ impl Component for DraggableCanvas {
             current_offset.y += e.delta_y;
         };
 
+        // Touches don't emit mouse moves, so follow the pan gesture instead
+        let on_pan = move |e: Event<GestureEventData>| {
+            if dragging_position.read().is_some() {
+                let mut current_offset = offset.write();
+                current_offset.x += e.translation.x;
+                current_offset.y += e.translation.y;
+            }
+        };
+
+        let on_pinch = move |e: Event<GestureEventData>| {
+            zoom.set((zoom() * e.scale as f32).clamp(MIN_ZOOM, MAX_ZOOM));
+        };
+
         let (offset_x, offset_y) = offset().to_tuple();
 
         rect()
@@ -108,6 +128,9 @@ impl Component for DraggableCanvas {
             .on_pointer_down(on_pointer_down)
             .on_global_pointer_press(on_global_pointer_press)
             .on_wheel(on_wheel)
+            .on_pan(on_pan)
+            .on_pinch(on_pinch)
+            .scale(zoom())
             .offset_x(offset_x as f32)
             .offset_y(offset_y as f32)
             .a11y_id(focus.a11y_id())
@@ -203,6 +203,7 @@ pub struct ImageViewer {
     accessibility: AccessibilityData,
     effect: EffectData,
     corner_radius: Option<CornerRadius>,
+    zoomable: bool,
 
     children: Vec<Element>,
 
@@ -218,6 +219,7 @@ impl ImageViewer {
             accessibility: AccessibilityData::default(),
             effect: EffectData::default(),
             corner_radius: None,
+            zoomable: false,
             children: Vec::new(),
             key: DiffKey::None,
         }
@@ -268,14 +270,25 @@ impl ImageViewer {
         self.corner_radius = Some(corner_radius.into());
         self
     }
+
+    /// Zoom the image in and out with pinch gestures, e.g on touchscreens or trackpads. Disabled by default.
+    pub fn zoomable(mut self, zoomable: bool) -> Self {
+        self.zoomable = zoomable;
+        self
+    }
 }
 
+/// Zoom limits of a zoomable [ImageViewer].
+const MIN_ZOOM: f32 = 1.;
+const MAX_ZOOM: f32 = 8.;
+
 impl Component for ImageViewer {
     fn render(&self) -> impl IntoElement {
         let asset_config = AssetConfiguration::new(&self.source, AssetAge::default());
         let asset = use_asset(&asset_config);
         let mut asset_cacher = use_hook(AssetCacher::get);
         let mut assets_tasks = use_state::<Vec<TaskHandle>>(Vec::new);
+        let mut zoom = use_state(|| 1.);
 
         use_side_effect_with_deps(
             &(self.source.clone(), asset_config),
@@ -336,6 +349,12 @@ impl Component for ImageViewer {
                     .map(self.corner_radius, |img, corner_radius| {
                         img.corner_radius(corner_radius)
                     })
+                    .maybe(self.zoomable, |img| {
+                        img.scale(zoom())
+                            .on_pinch(move |e: Event<GestureEventData>| {
+                                zoom.set((zoom() * e.scale as f32).clamp(MIN_ZOOM, MAX_ZOOM));
+                            })
+                    })
                     .into_element()
             }
             Asset::Pending | Asset::Loading => rect()
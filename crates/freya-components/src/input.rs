@@ -557,6 +557,9 @@ impl Component for Input {
             .a11y_auto_focus(self.auto_focus)
             .a11y_alt(a11y_text)
             .a11y_role(a11_role)
+            .maybe(!self.enabled, |el| {
+                el.a11y_builder(|builder| builder.set_disabled())
+            })
             .maybe(self.enabled, |el| {
                 el.on_key_up(on_key_up)
                     .on_key_down(on_key_down)
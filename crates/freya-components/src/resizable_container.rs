@@ -497,12 +497,10 @@ impl Component for ResizableHandle {
             Cursor::set(cursor);
         };
 
-        let on_capture_global_pointer_move = {
+        let on_pointer_over = {
             let mut registry = registry;
             move |e: Event<PointerEventData>| {
                 if *clicking.read() {
-                    e.prevent_default();
-
                     if !*allow_resizing.read() {
                         return;
                     }
@@ -534,10 +532,12 @@ impl Component for ResizableHandle {
         let on_pointer_down = move |e: Event<PointerEventData>| {
             e.stop_propagation();
             e.prevent_default();
+            // Keep resizing even if the pointer leaves the handle or the window
+            e.capture_pointer();
             clicking.set(true);
         };
 
-        let on_global_pointer_press = move |_: Event<PointerEventData>| {
+        let on_pointer_press = move |_: Event<PointerEventData>| {
             if *clicking.read() {
                 if *status.peek() != HandleStatus::Hovering {
                     Cursor::set(CursorIcon::default());
@@ -567,9 +567,9 @@ impl Component for ResizableHandle {
                 allow_resizing.set(true);
             })
             .on_pointer_down(on_pointer_down)
-            .on_global_pointer_press(on_global_pointer_press)
+            .on_pointer_press(on_pointer_press)
             .on_pointer_enter(on_pointer_enter)
-            .on_capture_global_pointer_move(on_capture_global_pointer_move)
+            .on_pointer_over(on_pointer_over)
             .on_pointer_leave(on_pointer_leave)
     }
 }
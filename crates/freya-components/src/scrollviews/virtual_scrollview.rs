@@ -1,5 +1,7 @@
 use std::{
+    cell::Cell,
     ops::Range,
+    rc::Rc,
     time::Duration,
 };
 
@@ -76,6 +78,7 @@ pub struct VirtualScrollView<D, B: Fn(usize, &D) -> Element> {
     scroll_with_arrows: bool,
     scroll_controller: Option<ScrollController>,
     invert_scroll_wheel: bool,
+    on_end_reached: Option<EventHandler<()>>,
     key: DiffKey,
 }
 
@@ -103,6 +106,7 @@ impl<D: PartialEq, B: Fn(usize, &D) -> Element> PartialEq for VirtualScrollView<
             && self.scroll_with_arrows == other.scroll_with_arrows
             && self.scroll_controller == other.scroll_controller
             && self.invert_scroll_wheel == other.invert_scroll_wheel
+            && self.on_end_reached == other.on_end_reached
     }
 }
 
@@ -123,6 +127,7 @@ impl<B: Fn(usize, &()) -> Element> VirtualScrollView<(), B> {
             scroll_with_arrows: true,
             scroll_controller: None,
             invert_scroll_wheel: false,
+            on_end_reached: None,
             key: DiffKey::None,
         }
     }
@@ -143,6 +148,7 @@ impl<B: Fn(usize, &()) -> Element> VirtualScrollView<(), B> {
             scroll_with_arrows: true,
             scroll_controller: Some(scroll_controller),
             invert_scroll_wheel: false,
+            on_end_reached: None,
             key: DiffKey::None,
         }
     }
@@ -165,6 +171,7 @@ impl<D, B: Fn(usize, &D) -> Element> VirtualScrollView<D, B> {
             scroll_with_arrows: true,
             scroll_controller: None,
             invert_scroll_wheel: false,
+            on_end_reached: None,
             key: DiffKey::None,
         }
     }
@@ -190,6 +197,7 @@ impl<D, B: Fn(usize, &D) -> Element> VirtualScrollView<D, B> {
             scroll_with_arrows: true,
             scroll_controller: Some(scroll_controller),
             invert_scroll_wheel: false,
+            on_end_reached: None,
             key: DiffKey::None,
         }
     }
@@ -232,6 +240,15 @@ impl<D, B: Fn(usize, &D) -> Element> VirtualScrollView<D, B> {
         self
     }
 
+    /// Called when the rendered items get within a viewport of the end, e.g. to load more items.
+    ///
+    /// It is called again once more items are added and the end is still near,
+    /// but never while there are no items, so the first items must be loaded separately.
+    pub fn on_end_reached(mut self, on_end_reached: impl Into<EventHandler<()>>) -> Self {
+        self.on_end_reached = Some(on_end_reached.into());
+        self
+    }
+
     pub fn max_width(mut self, max_width: impl Into<Size>) -> Self {
         self.layout.maximum_width = max_width.into();
         self
@@ -440,6 +457,19 @@ impl<D: PartialEq + 'static, B: Fn(usize, &D) -> Element + 'static> Component
             self.length as f32,
         );
 
+        // There is no end to reach until there are items
+        let end_reached = self.length > 0 && render_range.end + render_range.len() >= self.length;
+        // Only notify once per length until the end is no longer near
+        let end_reached_length = use_hook(|| Rc::new(Cell::new(None)));
+        let previous_end_reached_length =
+            end_reached_length.replace(end_reached.then_some(self.length));
+        if end_reached
+            && previous_end_reached_length != Some(self.length)
+            && let Some(on_end_reached) = self.on_end_reached.clone()
+        {
+            spawn(async move { on_end_reached.call(()) });
+        }
+
         let children = render_range
             .map(|i| (self.builder)(i, &self.builder_data))
             .collect::<Vec<Element>>();
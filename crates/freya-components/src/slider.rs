@@ -160,17 +160,18 @@ impl Component for Slider {
                 focus.request_focus();
                 clicking.set(true);
                 e.stop_propagation();
+                // Keep sliding even if the pointer leaves the slider or the window
+                e.capture_pointer();
                 let coordinates = e.element_location();
                 on_moved.call(calc_percentage(coordinates.x, coordinates.y));
             }
         };
 
-        let on_global_pointer_press = move |_: Event<PointerEventData>| {
+        let on_pointer_press = move |_: Event<PointerEventData>| {
             clicking.set(false);
         };
 
-        let on_global_pointer_move = move |e: Event<PointerEventData>| {
-            e.stop_propagation();
+        let on_pointer_over = move |e: Event<PointerEventData>| {
             if *clicking.peek() {
                 let coordinates = e.global_location();
                 on_moved.call(calc_percentage(
@@ -257,12 +258,15 @@ impl Component for Slider {
             .a11y_id(focus.a11y_id())
             .a11y_focusable(self.enabled)
             .a11y_role(AccessibilityRole::Slider)
+            .maybe(!self.enabled, |el| {
+                el.a11y_builder(|builder| builder.set_disabled())
+            })
             .on_sized(move |e: Event<SizedEventData>| size.set(e.area))
             .maybe(self.enabled, |rect| {
                 rect.on_key_down(on_key_down)
                     .on_pointer_down(on_pointer_down)
-                    .on_global_pointer_move(on_global_pointer_move)
-                    .on_global_pointer_press(on_global_pointer_press)
+                    .on_pointer_over(on_pointer_over)
+                    .on_pointer_press(on_pointer_press)
             })
             .on_pointer_enter(on_pointer_enter)
             .on_pointer_leave(on_pointer_leave)
@@ -165,6 +165,9 @@ impl Component for Switch {
             .a11y_id(focus.a11y_id())
             .a11y_focusable(self.enabled)
             .a11y_role(AccessibilityRole::Switch)
+            .maybe(!self.enabled, |el| {
+                el.a11y_builder(|builder| builder.set_disabled())
+            })
             .a11y_builder(|builder| builder.set_toggled(Toggled::from(toggled)))
             .width(Size::px(48.))
             .height(Size::px(25.))
@@ -0,0 +1,53 @@
+use freya::prelude::{
+    Button,
+    Switch,
+};
+use freya_core::prelude::*;
+use freya_testing::prelude::*;
+
+fn form_app() -> impl IntoElement {
+    let mut saved = use_state(|| false);
+
+    rect()
+        .child(label().text("Settings"))
+        .child(Button::new().child("Cancel").enabled(false))
+        .child(
+            Button::new()
+                .on_press(move |_| saved.set(true))
+                .child("Save"),
+        )
+        .child(Switch::new().toggled(saved()))
+}
+
+#[test]
+pub fn accessibility_queries() {
+    let mut test = launch_test(form_app);
+    test.sync_and_update();
+
+    assert_eq!(test.find_all_by_role(AccessibilityRole::Button).len(), 2);
+    assert!(test.find_by_text("Settings").is_some());
+
+    let cancel = test
+        .find_by_role_and_name(AccessibilityRole::Button, "Cancel")
+        .unwrap();
+    assert!(cancel.is_disabled());
+    assert!(!cancel.is_focusable());
+
+    let save = test.find_by_name("Save").unwrap();
+    assert_eq!(save.role(), AccessibilityRole::Button);
+    assert!(!save.is_disabled());
+    assert!(!save.is_focused());
+    assert!(test.find_focused().is_none());
+
+    // Disabled elements are skipped
+    assert_eq!(test.tab_order_snapshot(), ["Button \"Save\"", "Switch"]);
+
+    test.press_key(Key::Named(NamedKey::Tab));
+    test.sync_and_update();
+
+    assert!(save.is_focused());
+    assert_eq!(
+        test.find_focused().unwrap().accessibility_id(),
+        save.accessibility_id()
+    );
+}
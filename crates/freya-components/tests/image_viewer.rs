@@ -127,3 +127,42 @@ pub fn image_viewer_load_and_render() {
         "Image element should be rendered after loading"
     );
 }
+
+#[test]
+pub fn image_viewer_pinch_zoom() {
+    fn image_viewer_app() -> impl IntoElement {
+        ImageViewer::new(("logo", include_bytes!("../../../examples/rust_logo.png")))
+            .width(Size::px(300.))
+            .height(Size::px(300.))
+            .zoomable(true)
+    }
+
+    let mut test = launch_test(image_viewer_app);
+    test.sync_and_update();
+
+    // Wait for the image to load
+    test.poll(
+        std::time::Duration::from_millis(1),
+        std::time::Duration::from_millis(70),
+    );
+    test.sync_and_update();
+
+    test.send_event(PlatformEvent::Gesture {
+        name: GestureEventName::Pinch,
+        location: (150., 150.).into(),
+        phase: TouchPhase::Moved,
+        scale: 2.,
+        rotation: 0.,
+        translation: CursorPoint::zero(),
+        velocity: CursorPoint::zero(),
+    });
+    test.sync_and_update();
+
+    let image = test
+        .find(|_, element| Image::try_downcast(element))
+        .unwrap();
+    assert_eq!(
+        image.effect.and_then(|effect| effect.scale),
+        Some(2f32.into())
+    );
+}
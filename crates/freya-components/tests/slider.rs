@@ -282,3 +282,60 @@ pub fn slider_disabled() {
     // Value should still be 50
     assert_eq!(value, 50.0);
 }
+
+#[test]
+pub fn slider_drag_outside() {
+    fn slider_app() -> impl IntoElement {
+        let mut value = use_state(|| 0.0);
+
+        rect()
+            .child(label().text(format!("Value: {}", value() as i32)))
+            .child(
+                Slider::new(move |v| value.set(v))
+                    .value(value())
+                    .size(Size::px(200.)),
+            )
+    }
+
+    let mut test = launch_test(slider_app);
+    test.sync_and_update();
+
+    test.move_cursor((105.0, 30.0));
+    test.sync_and_update();
+    test.press_cursor((105.0, 30.0));
+    test.sync_and_update();
+
+    // The slider keeps following the pointer even when it is far away from it
+    test.move_cursor((500.0, 300.0));
+    test.sync_and_update();
+
+    let label = test
+        .find(|node, element| {
+            Label::try_downcast(element)
+                .filter(|l| l.text.starts_with("Value:"))
+                .map(|_| node)
+        })
+        .unwrap();
+    assert_eq!(
+        Label::try_downcast(&*label.element()).unwrap().text,
+        "Value: 100"
+    );
+
+    // Releasing it outside stops the sliding
+    test.release_cursor((500.0, 300.0));
+    test.sync_and_update();
+    test.move_cursor((5.0, 30.0));
+    test.sync_and_update();
+
+    let label = test
+        .find(|node, element| {
+            Label::try_downcast(element)
+                .filter(|l| l.text.starts_with("Value:"))
+                .map(|_| node)
+        })
+        .unwrap();
+    assert_eq!(
+        Label::try_downcast(&*label.element()).unwrap().text,
+        "Value: 100"
+    );
+}
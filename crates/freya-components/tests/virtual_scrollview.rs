@@ -447,3 +447,75 @@ pub fn virtual_scroll_view_keyboard_navigation_horizontal() {
         "0"
     );
 }
+
+#[test]
+pub fn virtual_scroll_view_end_reached() {
+    let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+
+    let mut test = launch_test({
+        let calls = calls.clone();
+        move || {
+            let mut length = use_state(|| 0usize);
+            let calls = calls.clone();
+
+            rect()
+                .expanded()
+                // Load the first items
+                .on_mouse_up(move |_| {
+                    if length() == 0 {
+                        length.set(15);
+                    }
+                })
+                .child(
+                    VirtualScrollView::new(|i, _| {
+                        label()
+                            .key(i)
+                            .height(Size::px(50.))
+                            .text(format!("{i} Hello, World!"))
+                            .into()
+                    })
+                    .length(length())
+                    .item_size(50.)
+                    .on_end_reached(move |_| {
+                        calls.set(calls.get() + 1);
+                        // Load more items, up to 30
+                        if length() < 30 {
+                            *length.write() += 15;
+                        }
+                    }),
+                )
+        }
+    });
+    test.sync_and_update();
+    test.poll(
+        std::time::Duration::from_millis(10),
+        std::time::Duration::from_millis(50),
+    );
+
+    // There are no items so there is no end to reach
+    assert_eq!(calls.get(), 0);
+
+    // Rendering 11 of 15 items is near the end, but once 30 items are loaded it is not
+    test.click_cursor((15., 15.));
+    test.poll(
+        std::time::Duration::from_millis(10),
+        std::time::Duration::from_millis(50),
+    );
+    assert_eq!(calls.get(), 1);
+
+    // Scroll near the end of the 30 items
+    test.scroll((5., 5.), (0., -600.));
+    test.poll(
+        std::time::Duration::from_millis(10),
+        std::time::Duration::from_millis(50),
+    );
+    assert_eq!(calls.get(), 2);
+
+    // The length didn't change so it is not notified again
+    test.scroll((5., 5.), (0., -50.));
+    test.poll(
+        std::time::Duration::from_millis(10),
+        std::time::Duration::from_millis(50),
+    );
+    assert_eq!(calls.get(), 2);
+}
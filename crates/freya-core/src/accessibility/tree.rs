@@ -220,24 +220,7 @@ impl AccessibilityTree {
             }
             (group_nodes, self.focused_id)
         } else {
-            let mut nodes = Vec::new();
-
-            tree.traverse_depth(|node_id| {
-                let accessibility_state = tree.accessibility_state.get(&node_id).unwrap();
-                let member_accessibility_id = accessibility_state.a11y_member_of;
-
-                // Exclude nodes that are members of groups except for the parent of the group
-                if let Some(member_accessibility_id) = member_accessibility_id
-                    && member_accessibility_id != accessibility_state.a11y_id
-                {
-                    return;
-                }
-                if accessibility_state.a11y_focusable == Focusable::Enabled {
-                    nodes.push(accessibility_state.a11y_id);
-                }
-            });
-
-            (nodes, self.focused_id)
+            (Self::navigable_nodes(tree), self.focused_id)
         };
 
         let node_index = navigable_nodes
@@ -278,6 +261,28 @@ impl AccessibilityTree {
         tracing::info!("Focused {:?} node.", self.focused_id);
     }
 
+    /// Get the nodes that are navigated across with the keyboard (e.g `Tab`), in order.
+    pub fn navigable_nodes(tree: &Tree) -> Vec<AccessibilityId> {
+        let mut nodes = Vec::new();
+
+        tree.traverse_depth(|node_id| {
+            let accessibility_state = tree.accessibility_state.get(&node_id).unwrap();
+            let member_accessibility_id = accessibility_state.a11y_member_of;
+
+            // Exclude nodes that are members of groups except for the parent of the group
+            if let Some(member_accessibility_id) = member_accessibility_id
+                && member_accessibility_id != accessibility_state.a11y_id
+            {
+                return;
+            }
+            if accessibility_state.a11y_focusable == Focusable::Enabled {
+                nodes.push(accessibility_state.a11y_id);
+            }
+        });
+
+        nodes
+    }
+
     /// Send the necessary wheel events to scroll views so that the given focused [NodeId] is visible on screen.
     fn scroll_to(
         &self,
@@ -1,12 +1,16 @@
 use std::{
     sync::{
         Arc,
+        Mutex,
         atomic::{
             AtomicU32,
             Ordering,
         },
     },
-    time::Duration,
+    time::{
+        Duration,
+        Instant,
+    },
 };
 
 use tracing::info;
@@ -14,7 +18,10 @@ use tracing::info;
 use crate::prelude::consume_root_context;
 
 #[derive(Clone)]
-pub struct AnimationClock(Arc<AtomicU32>);
+pub struct AnimationClock {
+    speed: Arc<AtomicU32>,
+    manual_time: Arc<Mutex<Option<Instant>>>,
+}
 
 impl Default for AnimationClock {
     fn default() -> Self {
@@ -33,32 +40,50 @@ impl AnimationClock {
     }
 
     pub fn new() -> Self {
-        Self(Arc::new(AtomicU32::new(Self::DEFAULT_SPEED.to_bits())))
+        Self {
+            speed: Arc::new(AtomicU32::new(Self::DEFAULT_SPEED.to_bits())),
+            manual_time: Arc::default(),
+        }
     }
 
     pub fn speed(&self) -> f32 {
-        let bits = self.0.load(Ordering::Relaxed);
+        let bits = self.speed.load(Ordering::Relaxed);
         (f32::from_bits(bits) * 100.0).round() / 100.0
     }
 
     pub fn enable(&self) {
-        self.0
+        self.speed
             .store(Self::DEFAULT_SPEED.to_bits(), Ordering::Relaxed);
         info!("Animation clock speed disabled");
     }
 
     pub fn disable(&self) {
-        self.0
+        self.speed
             .store(Self::DISABLED_SPEED.to_bits(), Ordering::Relaxed);
         info!("Animation clock speed disabled");
     }
 
     pub fn set_speed(&self, speed: f32) {
         let speed = speed.clamp(Self::MIN_SPEED, Self::MAX_SPEED);
-        self.0.store(speed.to_bits(), Ordering::Relaxed);
+        self.speed.store(speed.to_bits(), Ordering::Relaxed);
         info!("Animation clock speed changed to {:.2}x", speed);
     }
 
+    /// Current time of the clock, which is the real time unless it is driven manually,
+    /// see [AnimationClock::set_manual_time].
+    pub fn now(&self) -> Instant {
+        self.manual_time
+            .lock()
+            .unwrap()
+            .unwrap_or_else(Instant::now)
+    }
+
+    /// Drive the clock manually from the given time, e.g to replay recorded interactions deterministically in tests,
+    /// or go back to the real time with `None`.
+    pub fn set_manual_time(&self, time: Option<Instant>) {
+        *self.manual_time.lock().unwrap() = time;
+    }
+
     pub fn correct_elapsed_duration(&self, elapsed: Duration) -> Duration {
         let scaled_secs = elapsed.as_secs_f32() * self.speed();
         Duration::from_secs_f32(scaled_secs)
@@ -36,6 +36,7 @@ use crate::{
     events::{
         data::{
             Event,
+            GestureEventData,
             KeyboardEventData,
             MouseEventData,
             PointerEventData,
@@ -52,6 +53,7 @@ use crate::{
         FileEventData,
         ImePreeditEventData,
         MaybeExt,
+        PressEventData,
     },
     text_cache::TextCache,
     tree::{
@@ -108,6 +110,12 @@ pub trait ElementExt: Any {
         None
     }
 
+    /// Distance from the top of the element to the baseline of its first line of text,
+    /// given the data returned by [ElementExt::measure].
+    fn baseline(&self, _layout_data: &Rc<dyn Any>) -> Option<f32> {
+        None
+    }
+
     fn should_hook_measurement(&self) -> bool {
         false
     }
@@ -381,4 +389,6 @@ pub enum EventHandlerType {
     Pointer(EventHandler<Event<PointerEventData>>),
     ImePreedit(EventHandler<Event<ImePreeditEventData>>),
     File(EventHandler<Event<FileEventData>>),
+    Press(EventHandler<Event<PressEventData>>),
+    Gesture(EventHandler<Event<GestureEventData>>),
 }
@@ -17,6 +17,7 @@ use torin::{
     prelude::{
         Alignment,
         Direction,
+        LayoutDirection,
         Length,
         Position,
         VisibleSize,
@@ -177,7 +178,7 @@ pub trait EventHandlersExt: Sized {
         mouse_down => EventName::MouseDown;
         mouse_up => EventName::MouseUp;
         mouse_move => EventName::MouseMove;
-
+        double_click => EventName::DoubleClick;
     }
 
     event_handlers! {
@@ -218,6 +219,17 @@ pub trait EventHandlersExt: Sized {
         touch_start => EventName::TouchStart;
         touch_move => EventName::TouchMove;
         touch_end => EventName::TouchEnd;
+        long_press => EventName::LongPress;
+    }
+
+    event_handlers! {
+        Gesture,
+        GestureEventData;
+
+        pinch => EventName::Pinch;
+        rotate => EventName::Rotate;
+        pan => EventName::Pan;
+        swipe => EventName::Swipe;
     }
 
     event_handlers! {
@@ -288,6 +300,20 @@ pub trait EventHandlersExt: Sized {
         })
     }
 
+    /// Gets triggered when:
+    /// - **Click**: There is a `MouseUp` (Right button) event in the element
+    /// - **Activated**: The element is focused and there is a keydown event pressing the menu key or `Shift+F10`
+    fn on_context_menu(
+        mut self,
+        on_context_menu: impl Into<EventHandler<Event<PressEventData>>>,
+    ) -> Self {
+        self.get_event_handlers().insert(
+            EventName::ContextMenu,
+            EventHandlerType::Press(on_context_menu.into()),
+        );
+        self
+    }
+
     /// Also called the context menu click in other platforms.
     /// Gets triggered when:
     /// - **Click**: There is a `MouseUp` (Right button) event in the same element that there had been a `MouseDown` just before
@@ -469,6 +495,56 @@ where
         self.get_layout().layout.visible_height = visible_height.into();
         self
     }
+
+    /// Keep the given `width / height` ratio by deriving the size of the axis
+    /// decided by its children from the other one, e.g. `16. / 9.`.
+    fn ratio(mut self, aspect_ratio: impl Into<f32>) -> Self {
+        self.get_layout().layout.aspect_ratio = Some(aspect_ratio.into());
+        self
+    }
+
+    /// Horizontal flow of this element and its descendants, inherited from the parent by default.
+    /// [LayoutDirection::Rtl] mirrors the horizontal start and end, paddings, margins and absolute positions.
+    fn layout_direction(mut self, layout_direction: LayoutDirection) -> Self {
+        self.get_layout().layout.layout_direction = layout_direction;
+        self
+    }
+
+    /// Override the cross alignment of the parent for this element.
+    fn align_self(mut self, align_self: Alignment) -> Self {
+        self.get_layout().layout.align_self = Some(align_self);
+        self
+    }
+
+    /// Shrink factor used to fit this element inside a [Content::Flex] parent when its children overflow.
+    fn flex_shrink(mut self, flex_shrink: impl Into<f32>) -> Self {
+        self.get_layout().layout.flex_shrink = Length::new(flex_shrink.into());
+        self
+    }
+
+    /// Column of the [Content::Grid] parent where this element is placed.
+    fn grid_column(mut self, column: usize) -> Self {
+        self.get_layout().layout.grid_position.column = column;
+        self
+    }
+
+    /// Row of the [Content::Grid] parent where this element is placed.
+    fn grid_row(mut self, row: usize) -> Self {
+        self.get_layout().layout.grid_position.row = row;
+        self
+    }
+
+    /// How many columns of the [Content::Grid] parent this element occupies.
+    fn grid_column_span(mut self, column_span: usize) -> Self {
+        self.get_layout().layout.grid_position.column_span = column_span;
+        self
+    }
+
+    /// How many rows of the [Content::Grid] parent this element occupies.
+    fn grid_row_span(mut self, row_span: usize) -> Self {
+        self.get_layout().layout.grid_position.row_span = row_span;
+        self
+    }
 }
 
 pub trait LayoutExt
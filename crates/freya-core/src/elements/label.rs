@@ -13,6 +13,7 @@ use freya_engine::prelude::{
     ParagraphStyle,
     SkParagraph,
     SkRect,
+    TextDirection,
     TextStyle,
 };
 use rustc_hash::FxHashMap;
@@ -197,6 +198,7 @@ impl ElementExt for LabelElement {
             max_lines: None,
             line_height: None,
             width: context.area_size.width,
+            layout_direction: context.torin_node.layout_direction,
         };
         let paragraph = context
             .text_cache
@@ -239,6 +241,13 @@ impl ElementExt for LabelElement {
                 paragraph_style.set_text_style(&text_style);
                 paragraph_style.set_max_lines(self.max_lines);
                 paragraph_style.set_text_align(context.text_style_state.text_align.into());
+                paragraph_style.set_text_direction(
+                    if context.torin_node.layout_direction.is_rtl() {
+                        TextDirection::RTL
+                    } else {
+                        TextDirection::LTR
+                    },
+                );
 
                 let mut paragraph_builder =
                     ParagraphBuilder::new(&paragraph_style, &*context.font_collection);
@@ -249,6 +258,7 @@ impl ElementExt for LabelElement {
                 paragraph.layout(
                     if self.max_lines == Some(1)
                         && context.text_style_state.text_align == TextAlign::default()
+                        && !context.torin_node.layout_direction.is_rtl()
                         && !paragraph_style.ellipsized()
                     {
                         f32::MAX
@@ -267,6 +277,11 @@ impl ElementExt for LabelElement {
         Some((size, paragraph))
     }
 
+    fn baseline(&self, layout_data: &Rc<dyn Any>) -> Option<f32> {
+        let paragraph = layout_data.downcast_ref::<SkParagraph>()?;
+        Some(paragraph.alphabetic_baseline())
+    }
+
     fn should_hook_measurement(&self) -> bool {
         true
     }
@@ -17,10 +17,12 @@ use freya_engine::prelude::{
     PaintStyle,
     ParagraphBuilder,
     ParagraphStyle,
+    PathBuilder,
     RectHeightStyle,
     RectWidthStyle,
     SkParagraph,
     SkRect,
+    TextDirection,
     TextStyle,
 };
 use rustc_hash::FxHashMap;
@@ -122,7 +124,10 @@ pub struct ParagraphElement {
     pub event_handlers: FxHashMap<EventName, EventHandlerType>,
     pub sk_paragraph: ParagraphHolder,
     pub cursor_index: Option<usize>,
+    pub secondary_cursor_indexes: Vec<usize>,
     pub highlights: Vec<(usize, usize)>,
+    pub colored_highlights: Vec<((usize, usize), Color)>,
+    pub underlines: Vec<((usize, usize), Color)>,
     pub max_lines: Option<usize>,
     pub line_height: Option<f32>,
     pub relative_layer: Layer,
@@ -144,7 +149,10 @@ impl Default for ParagraphElement {
             event_handlers: Default::default(),
             sk_paragraph: Default::default(),
             cursor_index: Default::default(),
+            secondary_cursor_indexes: Default::default(),
             highlights: Default::default(),
+            colored_highlights: Default::default(),
+            underlines: Default::default(),
             max_lines: Default::default(),
             line_height: Default::default(),
             relative_layer: Default::default(),
@@ -155,6 +163,15 @@ impl Default for ParagraphElement {
     }
 }
 
+impl ParagraphElement {
+    /// Indexes of the main and secondary cursors.
+    fn cursor_indexes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.cursor_index
+            .into_iter()
+            .chain(self.secondary_cursor_indexes.iter().copied())
+    }
+}
+
 impl Display for ParagraphElement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(
@@ -207,7 +224,10 @@ impl ElementExt for ParagraphElement {
         }
 
         if self.cursor_index != paragraph.cursor_index
+            || self.secondary_cursor_indexes != paragraph.secondary_cursor_indexes
             || self.highlights != paragraph.highlights
+            || self.colored_highlights != paragraph.colored_highlights
+            || self.underlines != paragraph.underlines
             || self.cursor_mode != paragraph.cursor_mode
             || self.vertical_align != paragraph.vertical_align
         {
@@ -260,6 +280,7 @@ impl ElementExt for ParagraphElement {
             max_lines: self.max_lines,
             line_height: self.line_height,
             width: context.area_size.width,
+            layout_direction: context.torin_node.layout_direction,
         };
         let paragraph = context
             .text_cache
@@ -302,6 +323,13 @@ impl ElementExt for ParagraphElement {
                 paragraph_style.set_text_style(&text_style);
                 paragraph_style.set_max_lines(self.max_lines);
                 paragraph_style.set_text_align(context.text_style_state.text_align.into());
+                paragraph_style.set_text_direction(
+                    if context.torin_node.layout_direction.is_rtl() {
+                        TextDirection::RTL
+                    } else {
+                        TextDirection::LTR
+                    },
+                );
 
                 let mut paragraph_builder =
                     ParagraphBuilder::new(&paragraph_style, &*context.font_collection);
@@ -331,6 +359,7 @@ impl ElementExt for ParagraphElement {
                 paragraph.layout(
                     if self.max_lines == Some(1)
                         && context.text_style_state.text_align == TextAlign::default()
+                        && !context.torin_node.layout_direction.is_rtl()
                         && !paragraph_style.ellipsized()
                     {
                         f32::MAX
@@ -356,6 +385,12 @@ impl ElementExt for ParagraphElement {
         Some((size, Rc::new(())))
     }
 
+    fn baseline(&self, _layout_data: &Rc<dyn Any>) -> Option<f32> {
+        let paragraph = self.sk_paragraph.0.borrow();
+        let ParagraphHolderInner { paragraph, .. } = paragraph.as_ref()?;
+        Some(paragraph.alphabetic_baseline())
+    }
+
     fn should_hook_measurement(&self) -> bool {
         true
     }
@@ -394,8 +429,17 @@ impl ElementExt for ParagraphElement {
             CursorMode::Expanded => vertical_offset * 2.,
         };
 
-        // Draw highlights
-        for (from, to) in self.highlights.iter() {
+        // Draw highlights, the colored ones go below the selection ones
+        let highlights = self
+            .colored_highlights
+            .iter()
+            .map(|(highlight, color)| (highlight, *color))
+            .chain(
+                self.highlights
+                    .iter()
+                    .map(|highlight| (highlight, self.cursor_style_data.highlight_color)),
+            );
+        for ((from, to), color) in highlights {
             if from == to {
                 continue;
             }
@@ -409,7 +453,7 @@ impl ElementExt for ParagraphElement {
             let mut highlights_paint = Paint::default();
             highlights_paint.set_anti_alias(true);
             highlights_paint.set_style(PaintStyle::Fill);
-            highlights_paint.set_color(self.cursor_style_data.highlight_color);
+            highlights_paint.set_color(color);
 
             if rects.is_empty() && *from == 0 {
                 let avg_line_height =
@@ -435,61 +479,62 @@ impl ElementExt for ParagraphElement {
             }
         }
 
-        // We exclude those highlights that on the same start and end (e.g the user just started dragging)
-        let visible_highlights = self
-            .highlights
-            .iter()
-            .filter(|highlight| highlight.0 != highlight.1)
-            .count()
-            > 0;
-
-        // Draw block cursor behind text if needed
-        if let Some(cursor_index) = self.cursor_index
-            && self.cursor_style == CursorStyle::Block
-            && let Some(cursor_rect) = paragraph
-                .get_rects_for_range(
-                    cursor_index..cursor_index + 1,
-                    RectHeightStyle::Tight,
-                    RectWidthStyle::Tight,
-                )
-                .first()
-                .map(|text| text.rect)
-                .or_else(|| {
-                    // Show the cursor at the end of the text if possible
-                    let text_len = paragraph
-                        .get_glyph_position_at_coordinate((f32::MAX, f32::MAX))
-                        .position as usize;
-                    let last_rects = paragraph.get_rects_for_range(
-                        text_len.saturating_sub(1)..text_len,
+        // A cursor at the edge of a non-empty highlight is the end of its own selection, so it is hidden.
+        // Empty highlights are excluded (e.g the user just started dragging)
+        let is_cursor_selecting = |cursor_index: usize| {
+            self.highlights
+                .iter()
+                .any(|&(from, to)| from != to && (cursor_index == from || cursor_index == to))
+        };
+
+        // Draw block cursors behind text if needed
+        for cursor_index in self.cursor_indexes() {
+            if self.cursor_style == CursorStyle::Block
+                && let Some(cursor_rect) = paragraph
+                    .get_rects_for_range(
+                        cursor_index..cursor_index + 1,
                         RectHeightStyle::Tight,
                         RectWidthStyle::Tight,
-                    );
+                    )
+                    .first()
+                    .map(|text| text.rect)
+                    .or_else(|| {
+                        // Show the cursor at the end of the text if possible
+                        let text_len = paragraph
+                            .get_glyph_position_at_coordinate((f32::MAX, f32::MAX))
+                            .position as usize;
+                        let last_rects = paragraph.get_rects_for_range(
+                            text_len.saturating_sub(1)..text_len,
+                            RectHeightStyle::Tight,
+                            RectWidthStyle::Tight,
+                        );
 
-                    if let Some(last_rect) = last_rects.first() {
-                        let mut caret = last_rect.rect;
-                        caret.left = caret.right;
-                        Some(caret)
-                    } else {
-                        let avg_line_height =
-                            paragraph.height() / paragraph.get_line_metrics().len().max(1) as f32;
-                        Some(SkRect::new(0., 0., 6., avg_line_height))
-                    }
-                })
-        {
-            let width = (cursor_rect.right - cursor_rect.left).max(6.0);
-            let cursor_rect = SkRect::new(
-                cursor_area.min_x() + cursor_rect.left,
-                cursor_area.min_y() + cursor_rect.top + cursor_vertical_offset,
-                cursor_area.min_x() + cursor_rect.left + width,
-                cursor_area.min_y() + cursor_rect.bottom + cursor_vertical_size_offset,
-            );
+                        if let Some(last_rect) = last_rects.first() {
+                            let mut caret = last_rect.rect;
+                            caret.left = caret.right;
+                            Some(caret)
+                        } else {
+                            let avg_line_height = paragraph.height()
+                                / paragraph.get_line_metrics().len().max(1) as f32;
+                            Some(SkRect::new(0., 0., 6., avg_line_height))
+                        }
+                    })
+            {
+                let width = (cursor_rect.right - cursor_rect.left).max(6.0);
+                let cursor_rect = SkRect::new(
+                    cursor_area.min_x() + cursor_rect.left,
+                    cursor_area.min_y() + cursor_rect.top + cursor_vertical_offset,
+                    cursor_area.min_x() + cursor_rect.left + width,
+                    cursor_area.min_y() + cursor_rect.bottom + cursor_vertical_size_offset,
+                );
 
-            let mut paint = Paint::default();
-            paint.set_anti_alias(true);
-            paint.set_style(PaintStyle::Fill);
-            paint.set_color(self.cursor_style_data.color);
+                let mut paint = Paint::default();
+                paint.set_anti_alias(true);
+                paint.set_style(PaintStyle::Fill);
+                paint.set_color(self.cursor_style_data.color);
 
-            context.canvas.draw_rect(cursor_rect, &paint);
+                context.canvas.draw_rect(cursor_rect, &paint);
+            }
         }
 
         // Draw text (always uses visible_area with vertical_offset)
@@ -498,69 +543,112 @@ impl ElementExt for ParagraphElement {
             (visible_area.min_x(), visible_area.min_y() + vertical_offset),
         );
 
-        // Draw cursor
-        if let Some(cursor_index) = self.cursor_index
-            && !visible_highlights
-        {
-            let cursor_rects = paragraph.get_rects_for_range(
-                cursor_index..cursor_index + 1,
+        // Draw wavy underlines
+        for ((from, to), color) in self.underlines.iter() {
+            let (from, to) = if from < to { (from, to) } else { (to, from) };
+            let rects = paragraph.get_rects_for_range(
+                *from..*to,
                 RectHeightStyle::Tight,
                 RectWidthStyle::Tight,
             );
-            if let Some(cursor_rect) = cursor_rects.first().map(|text| text.rect).or_else(|| {
-                // Show the cursor at the end of the text if possible
-                let text_len = paragraph
-                    .get_glyph_position_at_coordinate((f32::MAX, f32::MAX))
-                    .position as usize;
-                let last_rects = paragraph.get_rects_for_range(
-                    text_len.saturating_sub(1)..text_len,
-                    RectHeightStyle::Tight,
-                    RectWidthStyle::Tight,
-                );
 
-                if let Some(last_rect) = last_rects.first() {
-                    let mut caret = last_rect.rect;
-                    caret.left = caret.right;
-                    Some(caret)
-                } else {
-                    None
-                }
-            }) {
-                let paint_color = self.cursor_style_data.color;
-                match self.cursor_style {
-                    CursorStyle::Underline => {
-                        let thickness = 2.0;
-                        let underline_rect = SkRect::new(
-                            cursor_area.min_x() + cursor_rect.left,
-                            cursor_area.min_y() + cursor_rect.bottom - thickness
-                                + cursor_vertical_offset,
-                            cursor_area.min_x() + cursor_rect.right,
-                            cursor_area.min_y() + cursor_rect.bottom + cursor_vertical_size_offset,
-                        );
+            let mut paint = Paint::default();
+            paint.set_anti_alias(true);
+            paint.set_style(PaintStyle::Stroke);
+            paint.set_stroke_width(1.);
+            paint.set_color(*color);
 
-                        let mut paint = Paint::default();
-                        paint.set_anti_alias(true);
-                        paint.set_style(PaintStyle::Fill);
-                        paint.set_color(paint_color);
+            for rect in rects {
+                const WAVE_WIDTH: f32 = 2.;
+                const WAVE_HEIGHT: f32 = 1.5;
+                let left = visible_area.min_x() + rect.rect.left;
+                let right = visible_area.min_x() + rect.rect.right.max(rect.rect.left + 6.);
+                let bottom =
+                    visible_area.min_y() + rect.rect.bottom + vertical_offset - WAVE_HEIGHT;
+
+                let mut path = PathBuilder::new();
+                path.move_to((left, bottom));
+                let mut x = left;
+                let mut up = true;
+                while x < right {
+                    x = (x + WAVE_WIDTH).min(right);
+                    let y = if up { bottom - WAVE_HEIGHT } else { bottom };
+                    path.line_to((x, y));
+                    up = !up;
+                }
+                context.canvas.draw_path(&path.detach(), &paint);
+            }
+        }
 
-                        context.canvas.draw_rect(underline_rect, &paint);
-                    }
-                    CursorStyle::Line => {
-                        let cursor_rect = SkRect::new(
-                            cursor_area.min_x() + cursor_rect.left,
-                            cursor_area.min_y() + cursor_rect.top + cursor_vertical_offset,
-                            cursor_area.min_x() + cursor_rect.left + 2.,
-                            cursor_area.min_y() + cursor_rect.bottom + cursor_vertical_size_offset,
+        // Draw cursors
+        for cursor_index in self.cursor_indexes() {
+            if !is_cursor_selecting(cursor_index) {
+                let cursor_rects = paragraph.get_rects_for_range(
+                    cursor_index..cursor_index + 1,
+                    RectHeightStyle::Tight,
+                    RectWidthStyle::Tight,
+                );
+                if let Some(cursor_rect) =
+                    cursor_rects.first().map(|text| text.rect).or_else(|| {
+                        // Show the cursor at the end of the text if possible
+                        let text_len = paragraph
+                            .get_glyph_position_at_coordinate((f32::MAX, f32::MAX))
+                            .position as usize;
+                        let last_rects = paragraph.get_rects_for_range(
+                            text_len.saturating_sub(1)..text_len,
+                            RectHeightStyle::Tight,
+                            RectWidthStyle::Tight,
                         );
 
-                        let mut paint = Paint::default();
-                        paint.set_anti_alias(true);
-                        paint.set_style(PaintStyle::Fill);
-                        paint.set_color(paint_color);
-
-                        context.canvas.draw_rect(cursor_rect, &paint);
+                        if let Some(last_rect) = last_rects.first() {
+                            let mut caret = last_rect.rect;
+                            caret.left = caret.right;
+                            Some(caret)
+                        } else {
+                            None
+                        }
+                    })
+                {
+                    let paint_color = self.cursor_style_data.color;
+                    match self.cursor_style {
+                        CursorStyle::Underline => {
+                            let thickness = 2.0;
+                            let underline_rect = SkRect::new(
+                                cursor_area.min_x() + cursor_rect.left,
+                                cursor_area.min_y() + cursor_rect.bottom - thickness
+                                    + cursor_vertical_offset,
+                                cursor_area.min_x() + cursor_rect.right,
+                                cursor_area.min_y()
+                                    + cursor_rect.bottom
+                                    + cursor_vertical_size_offset,
+                            );
+
+                            let mut paint = Paint::default();
+                            paint.set_anti_alias(true);
+                            paint.set_style(PaintStyle::Fill);
+                            paint.set_color(paint_color);
+
+                            context.canvas.draw_rect(underline_rect, &paint);
+                        }
+                        CursorStyle::Line => {
+                            let cursor_rect = SkRect::new(
+                                cursor_area.min_x() + cursor_rect.left,
+                                cursor_area.min_y() + cursor_rect.top + cursor_vertical_offset,
+                                cursor_area.min_x() + cursor_rect.left + 2.,
+                                cursor_area.min_y()
+                                    + cursor_rect.bottom
+                                    + cursor_vertical_size_offset,
+                            );
+
+                            let mut paint = Paint::default();
+                            paint.set_anti_alias(true);
+                            paint.set_style(PaintStyle::Fill);
+                            paint.set_color(paint_color);
+
+                            context.canvas.draw_rect(cursor_rect, &paint);
+                        }
+                        _ => {}
                     }
-                    _ => {}
                 }
             }
         }
@@ -670,6 +758,12 @@ impl Paragraph {
         self
     }
 
+    /// Additional cursors, drawn the same way as the [cursor_index](Self::cursor_index) one.
+    pub fn secondary_cursor_indexes(mut self, cursor_indexes: impl Into<Vec<usize>>) -> Self {
+        self.element.secondary_cursor_indexes = cursor_indexes.into();
+        self
+    }
+
     pub fn highlights(mut self, highlights: impl Into<Option<Vec<(usize, usize)>>>) -> Self {
         if let Some(highlights) = highlights.into() {
             self.element.highlights = highlights;
@@ -677,6 +771,21 @@ impl Paragraph {
         self
     }
 
+    /// Highlights with their own color, such as search matches. Unlike [highlights](Self::highlights) they don't hide the cursor.
+    pub fn colored_highlights(
+        mut self,
+        colored_highlights: impl Into<Vec<((usize, usize), Color)>>,
+    ) -> Self {
+        self.element.colored_highlights = colored_highlights.into();
+        self
+    }
+
+    /// Wavy underlines with their own color, such as diagnostics.
+    pub fn underlines(mut self, underlines: impl Into<Vec<((usize, usize), Color)>>) -> Self {
+        self.element.underlines = underlines.into();
+        self
+    }
+
     pub fn max_lines(mut self, max_lines: impl Into<Option<usize>>) -> Self {
         self.element.max_lines = max_lines.into();
         self
@@ -8,12 +8,19 @@ use std::{
     rc::Rc,
 };
 
+use ragnarok::PointerCapture;
 use torin::prelude::{
     Area,
     CursorPoint,
     Size2D,
 };
 
+use crate::{
+    elements::extensions::PressEventData,
+    node_id::NodeId,
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum MouseButton {
     Left,
@@ -68,6 +75,9 @@ pub struct Event<D> {
     pub(crate) data: D,
     pub(crate) propagate: Rc<RefCell<bool>>,
     pub(crate) default: Rc<RefCell<bool>>,
+    /// Node whose handler is being called.
+    pub(crate) node_id: NodeId,
+    pub(crate) pointer_capture: Rc<RefCell<Option<PointerCapture<NodeId>>>>,
 }
 
 impl<D> Deref for Event<D> {
@@ -84,6 +94,8 @@ impl<D> Event<D> {
             data: data(self.data),
             propagate: self.propagate,
             default: self.default,
+            node_id: self.node_id,
+            pointer_capture: self.pointer_capture,
         }
     }
 
@@ -92,6 +104,8 @@ impl<D> Event<D> {
             data: data(self.data)?,
             propagate: self.propagate,
             default: self.default,
+            node_id: self.node_id,
+            pointer_capture: self.pointer_capture,
         })
     }
 
@@ -107,6 +121,17 @@ impl<D> Event<D> {
         *self.default.borrow_mut() = false;
     }
 
+    /// Route the upcoming pointer movement and release events to the Node handling this event,
+    /// even if the pointer leaves it or the window. The capture ends once the pointer is released.
+    pub fn capture_pointer(&self) {
+        *self.pointer_capture.borrow_mut() = Some(PointerCapture::Capture(self.node_id));
+    }
+
+    /// Stop routing the pointer events to the Node capturing the pointer.
+    pub fn release_pointer(&self) {
+        *self.pointer_capture.borrow_mut() = Some(PointerCapture::Release);
+    }
+
     #[must_use]
     pub fn get_prevent_default(&self) -> Rc<RefCell<bool>> {
         self.default.clone()
@@ -139,6 +164,7 @@ impl SizedEventData {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Copy)]
 pub enum WheelSource {
     Device,
@@ -173,6 +199,7 @@ impl WheelEventData {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub enum TouchPhase {
     Started,
@@ -181,6 +208,7 @@ pub enum TouchPhase {
     Cancelled,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Force {
     Calibrated {
@@ -249,6 +277,50 @@ impl PointerEventData {
     }
 }
 
+/// Data of a gesture event.
+///
+/// Every gesture event of the same update carries the same data,
+/// so a pinch handler can also read the translation of the gesture, for instance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureEventData {
+    /// Center of the gesture.
+    pub global_location: CursorPoint,
+    /// Center of the gesture relative to the element.
+    pub element_location: CursorPoint,
+    pub phase: TouchPhase,
+    /// Scale change since the previous update of the gesture, `1.0` means no change.
+    pub scale: f64,
+    /// Clockwise rotation in degrees since the previous update of the gesture.
+    pub rotation: f64,
+    /// Movement of the center since the previous update of the gesture.
+    /// For swipes this is the whole movement of the finger.
+    pub translation: CursorPoint,
+    /// Velocity of the finger in pixels per second, only set for swipes.
+    pub velocity: CursorPoint,
+}
+
+impl GestureEventData {
+    pub(crate) fn new(
+        global_location: CursorPoint,
+        element_location: CursorPoint,
+        phase: TouchPhase,
+        scale: f64,
+        rotation: f64,
+        translation: CursorPoint,
+        velocity: CursorPoint,
+    ) -> Self {
+        Self {
+            global_location,
+            element_location,
+            phase,
+            scale,
+            rotation,
+            translation,
+            velocity,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ImePreeditEventData {
     pub text: String,
@@ -283,4 +355,6 @@ pub enum EventType {
     Pointer(PointerEventData),
     ImePreedit(ImePreeditEventData),
     File(FileEventData),
+    Press(PressEventData),
+    Gesture(GestureEventData),
 }
@@ -8,6 +8,7 @@ use crate::{
     events::{
         data::{
             EventType,
+            GestureEventData,
             KeyboardEventData,
             MouseEventData,
             PointerEventData,
@@ -21,6 +22,7 @@ use crate::{
     prelude::{
         FileEventData,
         ImePreeditEventData,
+        PressEventData,
     },
 };
 /// Event emitted to the Tree.
@@ -75,6 +77,47 @@ impl EmmitableEvent {
         let bubbles = name.does_bubble();
 
         match platform_event {
+            PlatformEvent::Mouse {
+                name: platform_event_name,
+                cursor,
+                button,
+            } if name == EventName::ContextMenu => {
+                let global_location = cursor / scale_factor;
+                let element_x =
+                    (cursor.x - node_area.unwrap_or_default().min_x() as f64) / scale_factor;
+                let element_y =
+                    (cursor.y - node_area.unwrap_or_default().min_y() as f64) / scale_factor;
+
+                let event_data = EventType::Press(PressEventData::Mouse(MouseEventData {
+                    global_location,
+                    element_location: CursorPoint::new(element_x, element_y),
+                    button,
+                }));
+
+                Self {
+                    node_id,
+                    name,
+                    source_event: platform_event_name.into(),
+                    data: event_data,
+                    bubbles,
+                }
+            }
+            PlatformEvent::Keyboard {
+                name: platform_event_name,
+                ref key,
+                code,
+                modifiers,
+            } if name == EventName::ContextMenu => Self {
+                node_id,
+                name,
+                source_event: platform_event_name.into(),
+                data: EventType::Press(PressEventData::Keyboard(KeyboardEventData::new(
+                    key.clone(),
+                    code,
+                    modifiers,
+                ))),
+                bubbles,
+            },
             PlatformEvent::Mouse {
                 name: platform_event_name,
                 cursor,
@@ -263,6 +306,37 @@ impl EmmitableEvent {
                 data: EventType::File(FileEventData::new(cursor, file_path)),
                 bubbles,
             },
+            PlatformEvent::Gesture {
+                name: platform_event_name,
+                location,
+                phase,
+                scale,
+                rotation,
+                translation,
+                velocity,
+            } => {
+                let global_location = location / scale_factor;
+                let element_x =
+                    (location.x - node_area.unwrap_or_default().min_x() as f64) / scale_factor;
+                let element_y =
+                    (location.y - node_area.unwrap_or_default().min_y() as f64) / scale_factor;
+
+                Self {
+                    node_id,
+                    name,
+                    source_event: platform_event_name.into(),
+                    data: EventType::Gesture(GestureEventData::new(
+                        global_location,
+                        CursorPoint::new(element_x, element_y),
+                        phase,
+                        scale,
+                        rotation,
+                        translation / scale_factor,
+                        velocity / scale_factor,
+                    )),
+                    bubbles,
+                }
+            }
         }
     }
 }
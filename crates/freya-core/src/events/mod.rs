@@ -4,3 +4,5 @@ pub mod executor;
 pub mod measurer;
 pub mod name;
 pub mod platform;
+pub mod recognizer;
+pub mod script;
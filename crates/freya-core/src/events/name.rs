@@ -4,6 +4,10 @@ pub enum EventName {
     MouseUp,
     MouseDown,
     MouseMove,
+    DoubleClick,
+
+    // Platform Mouse or Keyboard
+    ContextMenu,
 
     // Platform Mouse or Touch
     PointerPress,
@@ -22,6 +26,13 @@ pub enum EventName {
     TouchStart,
     TouchMove,
     TouchEnd,
+    LongPress,
+
+    // Platform Gesture
+    Pinch,
+    Rotate,
+    Pan,
+    Swipe,
 
     GlobalPointerMove,
     GlobalPointerPress,
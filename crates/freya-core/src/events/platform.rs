@@ -21,11 +21,14 @@ use crate::{
     },
 };
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, PartialEq, Copy, Eq, Hash)]
 pub enum MouseEventName {
     MouseUp,
     MouseDown,
     MouseMove,
+    DoubleClick,
+    ContextMenu,
 }
 
 impl From<MouseEventName> for EventName {
@@ -34,10 +37,13 @@ impl From<MouseEventName> for EventName {
             MouseEventName::MouseUp => EventName::MouseUp,
             MouseEventName::MouseMove => EventName::MouseMove,
             MouseEventName::MouseDown => EventName::MouseDown,
+            MouseEventName::DoubleClick => EventName::DoubleClick,
+            MouseEventName::ContextMenu => EventName::ContextMenu,
         }
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, PartialEq, Copy, Eq, Hash)]
 pub enum WheelEventName {
     Wheel,
@@ -51,10 +57,12 @@ impl From<WheelEventName> for EventName {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, PartialEq, Copy, Eq, Hash)]
 pub enum KeyboardEventName {
     KeyDown,
     KeyUp,
+    ContextMenu,
 }
 
 impl From<KeyboardEventName> for EventName {
@@ -62,10 +70,12 @@ impl From<KeyboardEventName> for EventName {
         match value {
             KeyboardEventName::KeyDown => EventName::KeyDown,
             KeyboardEventName::KeyUp => EventName::KeyUp,
+            KeyboardEventName::ContextMenu => EventName::ContextMenu,
         }
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, PartialEq, Copy, Eq, Hash)]
 pub enum FileEventName {
     FileDrop,
@@ -84,6 +94,7 @@ impl From<FileEventName> for EventName {
 }
 
 /// Data for [PlatformEvent].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, PartialEq)]
 pub enum PlatformEvent {
     /// A Mouse Event.
@@ -126,8 +137,19 @@ pub enum PlatformEvent {
         cursor: CursorPoint,
         file_path: Option<PathBuf>,
     },
+    /// A Gesture event.
+    Gesture {
+        name: GestureEventName,
+        location: CursorPoint,
+        phase: TouchPhase,
+        scale: f64,
+        rotation: f64,
+        translation: CursorPoint,
+        velocity: CursorPoint,
+    },
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, PartialEq, Copy, Eq, Hash)]
 pub enum ImeEventName {
     Preedit,
@@ -141,12 +163,14 @@ impl From<ImeEventName> for EventName {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, PartialEq, Copy, Eq, Hash)]
 pub enum TouchEventName {
     TouchStart,
     TouchMove,
     TouchEnd,
     TouchCancel,
+    LongPress,
 }
 
 impl From<TouchEventName> for EventName {
@@ -156,6 +180,27 @@ impl From<TouchEventName> for EventName {
             TouchEventName::TouchMove => EventName::TouchMove,
             TouchEventName::TouchEnd => EventName::TouchEnd,
             TouchEventName::TouchCancel => EventName::TouchCancel,
+            TouchEventName::LongPress => EventName::LongPress,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Copy, Eq, Hash)]
+pub enum GestureEventName {
+    Pinch,
+    Rotate,
+    Pan,
+    Swipe,
+}
+
+impl From<GestureEventName> for EventName {
+    fn from(value: GestureEventName) -> Self {
+        match value {
+            GestureEventName::Pinch => EventName::Pinch,
+            GestureEventName::Rotate => EventName::Rotate,
+            GestureEventName::Pan => EventName::Pan,
+            GestureEventName::Swipe => EventName::Swipe,
         }
     }
 }
@@ -210,6 +255,7 @@ impl ragnarok::SourceEvent for PlatformEvent {
             Self::ImePreedit { name, .. } => (*name).into(),
             Self::Touch { name, .. } => (*name).into(),
             Self::File { name, .. } => (*name).into(),
+            Self::Gesture { name, .. } => (*name).into(),
         }
     }
 
@@ -219,6 +265,7 @@ impl ragnarok::SourceEvent for PlatformEvent {
             PlatformEvent::Wheel { cursor, .. } => Some(*cursor),
             PlatformEvent::Touch { location, .. } => Some(*location),
             PlatformEvent::File { cursor, .. } => Some(*cursor),
+            PlatformEvent::Gesture { location, .. } => Some(*location),
             _ => None,
         }
     }
@@ -0,0 +1,356 @@
+use std::{
+    collections::BTreeMap,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use keyboard_types::{
+    Key,
+    Modifiers,
+    NamedKey,
+};
+use rustc_hash::FxHashMap;
+use torin::prelude::CursorPoint;
+
+use crate::events::{
+    data::{
+        MouseButton,
+        TouchPhase,
+    },
+    platform::{
+        GestureEventName,
+        KeyboardEventName,
+        MouseEventName,
+        PlatformEvent,
+        TouchEventName,
+    },
+};
+
+/// Max time between two clicks for them to be a double click.
+pub const DOUBLE_CLICK_DURATION: Duration = Duration::from_millis(500);
+
+/// Time a touch must be held without moving for it to be a long press.
+pub const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+
+/// Max time a touch can last for it to be a swipe.
+pub const SWIPE_DURATION: Duration = Duration::from_millis(500);
+
+/// Max distance the pointer can move and still be considered the same click or touch.
+const LOCATION_THRESHOLD: f64 = 5.0;
+
+/// Min distance a finger must travel for its touch to be a swipe.
+const SWIPE_DISTANCE: f64 = 50.0;
+
+/// Derives the [PlatformEvent]s that depend on previous events or on timing,
+/// such as double clicks, context menus, long presses or touch gestures.
+#[derive(Default)]
+pub struct EventsRecognizer {
+    last_click: Option<(Instant, CursorPoint)>,
+    held_touches: FxHashMap<u64, (Instant, CursorPoint)>,
+    gestures: GesturesRecognizer,
+}
+
+impl EventsRecognizer {
+    /// Append the events derived from the given [PlatformEvent]s, which happened at `now`.
+    /// Long presses that became due before these events are prepended.
+    pub fn recognize(&mut self, platform_events: &mut Vec<PlatformEvent>, now: Instant) {
+        let long_presses = self.poll(now);
+        platform_events.splice(0..0, long_presses);
+
+        let mut recognized_events = Vec::new();
+
+        for platform_event in platform_events.iter() {
+            self.gestures
+                .recognize(platform_event, now, &mut recognized_events);
+
+            match platform_event {
+                PlatformEvent::Mouse {
+                    name: MouseEventName::MouseUp,
+                    cursor,
+                    button: Some(MouseButton::Left),
+                } => {
+                    let is_double_click =
+                        self.last_click.take().is_some_and(|(instant, location)| {
+                            now.saturating_duration_since(instant) <= DOUBLE_CLICK_DURATION
+                                && location.distance_to(*cursor) <= LOCATION_THRESHOLD
+                        });
+                    if is_double_click {
+                        recognized_events.push(PlatformEvent::Mouse {
+                            name: MouseEventName::DoubleClick,
+                            cursor: *cursor,
+                            button: Some(MouseButton::Left),
+                        });
+                    } else {
+                        self.last_click = Some((now, *cursor));
+                    }
+                }
+                PlatformEvent::Mouse {
+                    name: MouseEventName::MouseUp,
+                    cursor,
+                    button: Some(MouseButton::Right),
+                } => {
+                    recognized_events.push(PlatformEvent::Mouse {
+                        name: MouseEventName::ContextMenu,
+                        cursor: *cursor,
+                        button: Some(MouseButton::Right),
+                    });
+                }
+                PlatformEvent::Keyboard {
+                    name: KeyboardEventName::KeyDown,
+                    key,
+                    code,
+                    modifiers,
+                } if *key == Key::Named(NamedKey::ContextMenu)
+                    || (*key == Key::Named(NamedKey::F10)
+                        && modifiers.contains(Modifiers::SHIFT)) =>
+                {
+                    recognized_events.push(PlatformEvent::Keyboard {
+                        name: KeyboardEventName::ContextMenu,
+                        key: key.clone(),
+                        code: *code,
+                        modifiers: *modifiers,
+                    });
+                }
+                PlatformEvent::Touch {
+                    name: TouchEventName::TouchStart,
+                    location,
+                    finger_id,
+                    ..
+                } => {
+                    self.held_touches.insert(*finger_id, (now, *location));
+                }
+                PlatformEvent::Touch {
+                    name: TouchEventName::TouchMove,
+                    location,
+                    finger_id,
+                    ..
+                } => {
+                    // Moving the finger away cancels the long press
+                    self.held_touches.retain(|id, (_, start_location)| {
+                        id != finger_id
+                            || start_location.distance_to(*location) <= LOCATION_THRESHOLD
+                    });
+                }
+                PlatformEvent::Touch {
+                    name: TouchEventName::TouchEnd | TouchEventName::TouchCancel,
+                    finger_id,
+                    ..
+                } => {
+                    self.held_touches.remove(finger_id);
+                }
+                _ => {}
+            }
+        }
+
+        platform_events.extend(recognized_events);
+    }
+
+    /// Get the events that became due by `now`, such as long presses of touches
+    /// held for at least [LONG_PRESS_DURATION].
+    /// Meant to be called periodically, e.g once [EventsRecognizer::next_deadline] is reached.
+    pub fn poll(&mut self, now: Instant) -> Vec<PlatformEvent> {
+        let mut long_presses = Vec::new();
+        self.held_touches.retain(|finger_id, (instant, location)| {
+            if now.saturating_duration_since(*instant) < LONG_PRESS_DURATION {
+                return true;
+            }
+            long_presses.push(PlatformEvent::Touch {
+                name: TouchEventName::LongPress,
+                location: *location,
+                finger_id: *finger_id,
+                phase: TouchPhase::Started,
+                force: None,
+            });
+            false
+        });
+        long_presses
+    }
+
+    /// When is [EventsRecognizer::poll] going to have new events, if ever.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.held_touches
+            .values()
+            .map(|(instant, _)| *instant + LONG_PRESS_DURATION)
+            .min()
+    }
+}
+
+struct TrackedTouch {
+    started_at: Instant,
+    start_location: CursorPoint,
+    location: CursorPoint,
+}
+
+/// Derives pinch, rotate, pan and swipe gestures from the touches stream.
+#[derive(Default)]
+struct GesturesRecognizer {
+    touches: BTreeMap<u64, TrackedTouch>,
+    is_panning: bool,
+    is_pinching: bool,
+    /// Whether more than one finger has been down since the first touch started.
+    is_multi_touch: bool,
+}
+
+impl GesturesRecognizer {
+    /// Center of all the fingers.
+    fn center(&self) -> CursorPoint {
+        let sum = self
+            .touches
+            .values()
+            .fold(CursorPoint::zero(), |sum, touch| {
+                sum + touch.location.to_vector()
+            });
+        sum / self.touches.len().max(1) as f64
+    }
+
+    /// Distance and angle in degrees between the first two fingers.
+    fn span(&self) -> Option<(f64, f64)> {
+        let mut touches = self.touches.values();
+        let first = touches.next()?.location;
+        let second = touches.next()?.location;
+        let vector = second - first;
+        Some((vector.length(), vector.y.atan2(vector.x).to_degrees()))
+    }
+
+    fn recognize(
+        &mut self,
+        platform_event: &PlatformEvent,
+        now: Instant,
+        recognized_events: &mut Vec<PlatformEvent>,
+    ) {
+        let PlatformEvent::Touch {
+            name,
+            location,
+            finger_id,
+            ..
+        } = platform_event
+        else {
+            return;
+        };
+
+        match name {
+            TouchEventName::TouchStart => {
+                self.touches.insert(
+                    *finger_id,
+                    TrackedTouch {
+                        started_at: now,
+                        start_location: *location,
+                        location: *location,
+                    },
+                );
+                self.is_multi_touch |= self.touches.len() > 1;
+            }
+            TouchEventName::TouchMove => {
+                let previous_center = self.center();
+                let previous_span = self.span();
+                let Some(touch) = self.touches.get_mut(finger_id) else {
+                    return;
+                };
+                touch.location = *location;
+
+                let center = self.center();
+                let translation = (center - previous_center).to_point();
+                let (scale, rotation) = match (previous_span, self.span()) {
+                    (Some((previous_distance, previous_angle)), Some((distance, angle)))
+                        if previous_distance > 0. =>
+                    {
+                        let rotation = (angle - previous_angle + 540.) % 360. - 180.;
+                        (distance / previous_distance, rotation)
+                    }
+                    _ => (1., 0.),
+                };
+                let gesture = |name, phase| PlatformEvent::Gesture {
+                    name,
+                    location: center,
+                    phase,
+                    scale,
+                    rotation,
+                    translation,
+                    velocity: CursorPoint::zero(),
+                };
+
+                if self.is_panning {
+                    recognized_events.push(gesture(GestureEventName::Pan, TouchPhase::Moved));
+                } else if self.touches.values().any(|touch| {
+                    touch.start_location.distance_to(touch.location) > LOCATION_THRESHOLD
+                }) {
+                    // Small movements don't start a pan
+                    self.is_panning = true;
+                    recognized_events.push(gesture(GestureEventName::Pan, TouchPhase::Started));
+                }
+
+                if self.touches.len() > 1 {
+                    let phase = if self.is_pinching {
+                        TouchPhase::Moved
+                    } else {
+                        TouchPhase::Started
+                    };
+                    self.is_pinching = true;
+                    recognized_events.push(gesture(GestureEventName::Pinch, phase));
+                    recognized_events.push(gesture(GestureEventName::Rotate, phase));
+                }
+            }
+            TouchEventName::TouchEnd | TouchEventName::TouchCancel => {
+                let center = self.center();
+                let Some(mut touch) = self.touches.remove(finger_id) else {
+                    return;
+                };
+                touch.location = *location;
+
+                let phase = if *name == TouchEventName::TouchEnd {
+                    TouchPhase::Ended
+                } else {
+                    TouchPhase::Cancelled
+                };
+                let gesture_end = |name| PlatformEvent::Gesture {
+                    name,
+                    location: center,
+                    phase,
+                    scale: 1.,
+                    rotation: 0.,
+                    translation: CursorPoint::zero(),
+                    velocity: CursorPoint::zero(),
+                };
+
+                if self.is_pinching && self.touches.len() < 2 {
+                    self.is_pinching = false;
+                    recognized_events.push(gesture_end(GestureEventName::Pinch));
+                    recognized_events.push(gesture_end(GestureEventName::Rotate));
+                }
+
+                if !self.touches.is_empty() {
+                    return;
+                }
+
+                if self.is_panning {
+                    recognized_events.push(gesture_end(GestureEventName::Pan));
+                }
+
+                let elapsed = now.saturating_duration_since(touch.started_at);
+                let translation = (touch.location - touch.start_location).to_point();
+                if phase == TouchPhase::Ended
+                    && !self.is_multi_touch
+                    && elapsed <= SWIPE_DURATION
+                    && translation.to_vector().length() >= SWIPE_DISTANCE
+                {
+                    let velocity = translation / elapsed.as_secs_f64().max(0.001);
+                    recognized_events.push(PlatformEvent::Gesture {
+                        name: GestureEventName::Swipe,
+                        location: touch.location,
+                        phase,
+                        scale: 1.,
+                        rotation: 0.,
+                        translation,
+                        velocity,
+                    });
+                }
+
+                self.is_panning = false;
+                self.is_multi_touch = false;
+            }
+            _ => {}
+        }
+    }
+}
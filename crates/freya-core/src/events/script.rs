@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use torin::prelude::{
+    CursorPoint,
+    Size2D,
+};
+
+use crate::events::platform::PlatformEvent;
+
+/// A recorded sequence of user interactions that can be replayed, for instance in tests with `freya-testing`.
+///
+/// Locations are stored in logical pixels so a script can be replayed with a different scale factor.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InteractionScript {
+    /// Logical size of the window where the script was recorded.
+    pub size: Size2D,
+    pub events: Vec<ScriptedEvent>,
+}
+
+/// A [PlatformEvent] of an [InteractionScript].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScriptedEvent {
+    /// Time elapsed since the previous event.
+    pub delay: Duration,
+    pub event: PlatformEvent,
+}
+
+impl InteractionScript {
+    pub fn new(size: Size2D) -> Self {
+        Self {
+            size,
+            events: Vec::new(),
+        }
+    }
+
+    /// Add an event that happened `delay` after the previous one.
+    pub fn push(&mut self, delay: Duration, event: PlatformEvent) {
+        self.events.push(ScriptedEvent { delay, event });
+    }
+
+    /// Total time it takes to replay the script.
+    pub fn duration(&self) -> Duration {
+        self.events.iter().map(|event| event.delay).sum()
+    }
+}
+
+impl PlatformEvent {
+    /// Multiply all the locations of this event by `scale_factor`,
+    /// e.g `1. / window.scale_factor()` to turn physical locations into logical ones.
+    pub fn scaled(mut self, scale_factor: f64) -> Self {
+        let scale = |point: &mut CursorPoint| *point *= scale_factor;
+        match &mut self {
+            Self::Mouse { cursor, .. } | Self::File { cursor, .. } => scale(cursor),
+            Self::Wheel { cursor, .. } => scale(cursor),
+            Self::Touch { location, .. } => scale(location),
+            Self::Gesture {
+                location,
+                translation,
+                velocity,
+                ..
+            } => {
+                scale(location);
+                scale(translation);
+                scale(velocity);
+            }
+            Self::Keyboard { .. } | Self::ImePreedit { .. } => {}
+        }
+        self
+    }
+}
@@ -170,6 +170,8 @@ pub mod integration {
             measurer::*,
             name::*,
             platform::*,
+            recognizer::*,
+            script::*,
         },
         lifecycle::state::State,
         node_id::NodeId,
@@ -18,6 +18,7 @@ use futures_lite::{
 };
 use itertools::Itertools;
 use pathgraph::PathGraph;
+use ragnarok::PointerCapture;
 use rustc_hash::{
     FxHashMap,
     FxHashSet,
@@ -167,6 +168,8 @@ pub struct Runner {
 
     pub(crate) sender: futures_channel::mpsc::UnboundedSender<Message>,
     pub(crate) receiver: futures_channel::mpsc::UnboundedReceiver<Message>,
+
+    pub(crate) pointer_capture: Rc<RefCell<Option<PointerCapture<NodeId>>>>,
 }
 
 impl Debug for Runner {
@@ -267,6 +270,8 @@ impl Runner {
 
             sender,
             receiver,
+
+            pointer_capture: Rc::default(),
         }
     }
 
@@ -353,6 +358,11 @@ impl Runner {
         let mut current_target = Some((path, *scope_id));
         while let Some((path, scope_id)) = current_target.take() {
             let scope = self.scopes.get(&scope_id).cloned().unwrap();
+            let current_node_id = scope
+                .borrow()
+                .nodes
+                .get(&path)
+                .map_or(node_id, |path_node| path_node.node_id);
             scope.borrow().with_element(&path, |element| {
                 match element {
                     PathElement::Component { .. } => {
@@ -378,6 +388,10 @@ impl Runner {
                                                         data: data.clone(),
                                                         propagate: propagate.clone(),
                                                         default: default.clone(),
+                                                        node_id: current_node_id,
+                                                        pointer_capture: self
+                                                            .pointer_capture
+                                                            .clone(),
                                                     });
                                                 }
                                                 Some(_) => unreachable!(),
@@ -394,6 +408,10 @@ impl Runner {
                                                         data: data.clone(),
                                                         propagate: propagate.clone(),
                                                         default: default.clone(),
+                                                        node_id: current_node_id,
+                                                        pointer_capture: self
+                                                            .pointer_capture
+                                                            .clone(),
                                                     });
                                                 }
                                                 Some(_) => unreachable!(),
@@ -410,6 +428,10 @@ impl Runner {
                                                         data: data.clone(),
                                                         propagate: propagate.clone(),
                                                         default: default.clone(),
+                                                        node_id: current_node_id,
+                                                        pointer_capture: self
+                                                            .pointer_capture
+                                                            .clone(),
                                                     });
                                                 }
                                                 Some(_) => unreachable!(),
@@ -426,6 +448,10 @@ impl Runner {
                                                         data: data.clone(),
                                                         propagate: propagate.clone(),
                                                         default: default.clone(),
+                                                        node_id: current_node_id,
+                                                        pointer_capture: self
+                                                            .pointer_capture
+                                                            .clone(),
                                                     });
                                                 }
                                                 Some(_) => unreachable!(),
@@ -442,6 +468,10 @@ impl Runner {
                                                         data: data.clone(),
                                                         propagate: propagate.clone(),
                                                         default: default.clone(),
+                                                        node_id: current_node_id,
+                                                        pointer_capture: self
+                                                            .pointer_capture
+                                                            .clone(),
                                                     });
                                                 }
                                                 Some(_) => unreachable!(),
@@ -458,6 +488,10 @@ impl Runner {
                                                         data: data.clone(),
                                                         propagate: propagate.clone(),
                                                         default: default.clone(),
+                                                        node_id: current_node_id,
+                                                        pointer_capture: self
+                                                            .pointer_capture
+                                                            .clone(),
                                                     });
                                                 }
                                                 Some(_) => unreachable!(),
@@ -474,6 +508,10 @@ impl Runner {
                                                         data: data.clone(),
                                                         propagate: propagate.clone(),
                                                         default: default.clone(),
+                                                        node_id: current_node_id,
+                                                        pointer_capture: self
+                                                            .pointer_capture
+                                                            .clone(),
                                                     });
                                                 }
                                                 Some(_) => unreachable!(),
@@ -490,6 +528,50 @@ impl Runner {
                                                         data: data.clone(),
                                                         propagate: propagate.clone(),
                                                         default: default.clone(),
+                                                        node_id: current_node_id,
+                                                        pointer_capture: self
+                                                            .pointer_capture
+                                                            .clone(),
+                                                    });
+                                                }
+                                                Some(_) => unreachable!(),
+                                                _ => {}
+                                            }
+                                        }
+                                    }
+                                    EventType::Press(data) => {
+                                        let event_handlers = element.events_handlers();
+                                        if let Some(event_handlers) = event_handlers {
+                                            match event_handlers.get(&event_name) {
+                                                Some(EventHandlerType::Press(handler)) => {
+                                                    handler.call(Event {
+                                                        data: data.clone(),
+                                                        propagate: propagate.clone(),
+                                                        default: default.clone(),
+                                                        node_id: current_node_id,
+                                                        pointer_capture: self
+                                                            .pointer_capture
+                                                            .clone(),
+                                                    });
+                                                }
+                                                Some(_) => unreachable!(),
+                                                _ => {}
+                                            }
+                                        }
+                                    }
+                                    EventType::Gesture(data) => {
+                                        let event_handlers = element.events_handlers();
+                                        if let Some(event_handlers) = event_handlers {
+                                            match event_handlers.get(&event_name) {
+                                                Some(EventHandlerType::Gesture(handler)) => {
+                                                    handler.call(Event {
+                                                        data: data.clone(),
+                                                        propagate: propagate.clone(),
+                                                        default: default.clone(),
+                                                        node_id: current_node_id,
+                                                        pointer_capture: self
+                                                            .pointer_capture
+                                                            .clone(),
                                                     });
                                                 }
                                                 Some(_) => unreachable!(),
@@ -545,6 +627,11 @@ impl Runner {
         *default.borrow()
     }
 
+    /// Take the pointer capture requested by the last handled event, if any.
+    pub fn take_pointer_capture(&mut self) -> Option<PointerCapture<NodeId>> {
+        self.pointer_capture.borrow_mut().take()
+    }
+
     #[cfg_attr(feature = "hotpath", hotpath::measure)]
     pub async fn handle_events(&mut self) {
         loop {
@@ -1,13 +1,15 @@
 use freya_engine::prelude::SkTextAlign;
 
+/// Horizontal alignment of text. Defaults to [TextAlign::Start],
+/// which is the left edge for left-to-right text and the right edge for right-to-left text.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Default, Debug, Copy, Clone, Hash, PartialEq, Eq)]
 pub enum TextAlign {
-    #[default]
     Left = 0,
     Right = 1,
     Center = 2,
     Justify = 3,
+    #[default]
     Start = 4,
     End = 5,
 }
@@ -1,6 +1,7 @@
 use std::hash::Hash;
 
 use freya_engine::prelude::SkParagraph;
+use torin::prelude::LayoutDirection;
 
 use crate::{
     data::TextStyleState,
@@ -15,6 +16,7 @@ pub struct CachedParagraph<'a> {
     pub max_lines: Option<usize>,
     pub line_height: Option<f32>,
     pub width: f32,
+    pub layout_direction: LayoutDirection,
 }
 
 impl Hash for CachedParagraph<'_> {
@@ -26,6 +28,7 @@ impl Hash for CachedParagraph<'_> {
             v.to_bits().hash(state)
         }
         self.width.to_bits().hash(state);
+        self.layout_direction.hash(state);
     }
 }
 
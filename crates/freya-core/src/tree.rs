@@ -28,6 +28,7 @@ use torin::{
     },
     torin::{
         DirtyReason,
+        LayoutStats,
         Torin,
     },
 };
@@ -124,6 +125,11 @@ impl Tree {
         self.elements.len()
     }
 
+    /// Statistics of the last layout measurement.
+    pub fn layout_stats(&self) -> &LayoutStats<NodeId> {
+        self.layout.stats()
+    }
+
     pub fn traverse_depth(&self, mut then: impl FnMut(NodeId)) {
         let mut buffer = vec![NodeId::ROOT];
         while let Some(node_id) = buffer.pop() {
@@ -730,6 +736,10 @@ impl LayoutMeasurer<NodeId> for LayoutMeasurerAdapter<'_> {
         }
     }
 
+    fn baseline(&mut self, node_id: NodeId, data: &Rc<dyn Any>) -> Option<f32> {
+        self.elements.get(&node_id)?.baseline(data)
+    }
+
     fn notify_layout_references(
         &mut self,
         node_id: NodeId,
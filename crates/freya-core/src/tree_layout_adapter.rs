@@ -54,4 +54,11 @@ impl TreeAdapter<NodeId> for TreeAdapterFreya<'_> {
     fn children_of(&mut self, node_id: &NodeId) -> Vec<NodeId> {
         self.children.get(node_id).cloned().unwrap_or_default()
     }
+
+    fn is_scrollable(&self, node_id: &NodeId) -> bool {
+        self.elements
+            .get(node_id)
+            .and_then(|node| node.effect())
+            .is_some_and(|effect| effect.scrollable)
+    }
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use freya::helpers::*;
 use freya_core::{
     integration::*,
@@ -239,6 +241,200 @@ fn pointer_events() {
     assert_eq!(*state.peek(), 6);
 }
 
+#[test]
+fn double_click_and_context_menu_events() {
+    fn app() -> Element {
+        let mut state = use_consume::<State<i32>>();
+        rect()
+            .expanded()
+            .background((255, 255, 255))
+            .on_double_click(move |_| *state.write() += 1)
+            .on_context_menu(move |_| *state.write() += 10)
+            .into()
+    }
+
+    let (mut test, state) = TestingRunner::new(
+        app,
+        (500., 500.).into(),
+        |runner| runner.provide_root_context(|| State::create(0)),
+        1.,
+    );
+    test.sync_and_update();
+
+    test.click_cursor((15., 15.));
+    assert_eq!(*state.peek(), 0);
+
+    test.click_cursor((15., 15.));
+    assert_eq!(*state.peek(), 1);
+
+    // A third click starts a new double click
+    test.click_cursor((15., 15.));
+    assert_eq!(*state.peek(), 1);
+
+    test.send_event(PlatformEvent::Mouse {
+        name: MouseEventName::MouseUp,
+        cursor: (15., 15.).into(),
+        button: Some(MouseButton::Right),
+    });
+    test.sync_and_update();
+
+    assert_eq!(*state.peek(), 11);
+}
+
+#[test]
+fn long_press_events() {
+    fn app() -> Element {
+        let mut state = use_consume::<State<i32>>();
+        rect()
+            .expanded()
+            .background((255, 255, 255))
+            .on_long_press(move |_| *state.write() += 1)
+            .into()
+    }
+
+    let (mut test, state) = TestingRunner::new(
+        app,
+        (500., 500.).into(),
+        |runner| runner.provide_root_context(|| State::create(0)),
+        1.,
+    );
+    test.sync_and_update();
+
+    let touch = |test: &mut TestingRunner, name, location: (f64, f64)| {
+        test.send_event(PlatformEvent::Touch {
+            name,
+            location: location.into(),
+            finger_id: 0,
+            phase: TouchPhase::Started,
+            force: None,
+        });
+        test.sync_and_update();
+    };
+
+    // Holding the touch long enough is a long press
+    touch(&mut test, TouchEventName::TouchStart, (15., 15.));
+    test.poll(Duration::from_millis(50), LONG_PRESS_DURATION);
+    test.poll_n(Duration::from_millis(50), 2);
+    assert_eq!(*state.peek(), 1);
+    touch(&mut test, TouchEventName::TouchEnd, (15., 15.));
+
+    // Releasing the touch before is not
+    touch(&mut test, TouchEventName::TouchStart, (15., 15.));
+    touch(&mut test, TouchEventName::TouchEnd, (15., 15.));
+    test.poll(Duration::from_millis(50), LONG_PRESS_DURATION);
+    test.poll_n(Duration::from_millis(50), 2);
+    assert_eq!(*state.peek(), 1);
+
+    // Neither is moving it away
+    touch(&mut test, TouchEventName::TouchStart, (15., 15.));
+    touch(&mut test, TouchEventName::TouchMove, (100., 100.));
+    test.poll(Duration::from_millis(50), LONG_PRESS_DURATION);
+    test.poll_n(Duration::from_millis(50), 2);
+    assert_eq!(*state.peek(), 1);
+}
+
+#[test]
+fn touch_gestures() {
+    #[derive(Default, Clone, Copy, PartialEq, Debug)]
+    struct Gestures {
+        scale: f64,
+        pan: f64,
+        swipe: f64,
+    }
+
+    fn app() -> Element {
+        let mut state = use_consume::<State<Gestures>>();
+        rect()
+            .expanded()
+            .background((255, 255, 255))
+            .on_pinch(move |e: Event<GestureEventData>| state.write().scale *= e.scale)
+            .on_pan(move |e: Event<GestureEventData>| state.write().pan += e.translation.x)
+            .on_swipe(move |e: Event<GestureEventData>| state.write().swipe = e.translation.x)
+            .into()
+    }
+
+    let (mut test, state) = TestingRunner::new(
+        app,
+        (500., 500.).into(),
+        |runner| {
+            runner.provide_root_context(|| {
+                State::create(Gestures {
+                    scale: 1.,
+                    ..Default::default()
+                })
+            })
+        },
+        1.,
+    );
+    test.sync_and_update();
+
+    let mut touch = |name, location: (f64, f64), finger_id, phase| {
+        test.send_event(PlatformEvent::Touch {
+            name,
+            location: location.into(),
+            finger_id,
+            phase,
+            force: None,
+        });
+        test.sync_and_update();
+    };
+
+    // Spread two fingers apart
+    touch(
+        TouchEventName::TouchStart,
+        (100., 100.),
+        0,
+        TouchPhase::Started,
+    );
+    touch(
+        TouchEventName::TouchStart,
+        (200., 100.),
+        1,
+        TouchPhase::Started,
+    );
+    touch(
+        TouchEventName::TouchMove,
+        (300., 100.),
+        1,
+        TouchPhase::Moved,
+    );
+    touch(TouchEventName::TouchEnd, (300., 100.), 1, TouchPhase::Ended);
+    touch(TouchEventName::TouchEnd, (100., 100.), 0, TouchPhase::Ended);
+
+    assert_eq!(
+        *state.peek(),
+        Gestures {
+            scale: 2.,
+            pan: 50.,
+            swipe: 0.
+        }
+    );
+
+    // Quickly move a single finger
+    touch(
+        TouchEventName::TouchStart,
+        (100., 100.),
+        0,
+        TouchPhase::Started,
+    );
+    touch(
+        TouchEventName::TouchMove,
+        (200., 100.),
+        0,
+        TouchPhase::Moved,
+    );
+    touch(TouchEventName::TouchEnd, (200., 100.), 0, TouchPhase::Ended);
+
+    assert_eq!(
+        *state.peek(),
+        Gestures {
+            scale: 2.,
+            pan: 150.,
+            swipe: 100.
+        }
+    );
+}
+
 #[test]
 fn pointer_enter_leave_at_large_coordinates() {
     fn app() -> Element {
@@ -362,3 +558,132 @@ fn large_coordinate_hover_does_not_trigger_other_elements() {
     test.sync_and_update();
     assert_eq!(*counters.0.peek(), (11, 11));
 }
+
+#[test]
+fn replay_interaction_script() {
+    fn app() -> Element {
+        let mut state = use_consume::<State<(i32, i32)>>();
+        rect()
+            .width(Size::px(100.))
+            .height(Size::px(100.))
+            .on_mouse_up(move |_| state.write().0 += 1)
+            .on_double_click(move |_| state.write().1 += 1)
+            .into()
+    }
+
+    let mut script = InteractionScript::new((250., 250.).into());
+    for (delay, name) in [
+        (0, MouseEventName::MouseDown),
+        (10, MouseEventName::MouseUp),
+        (500, MouseEventName::MouseDown),
+        (10, MouseEventName::MouseUp),
+        (50, MouseEventName::MouseDown),
+        (10, MouseEventName::MouseUp),
+    ] {
+        script.push(
+            std::time::Duration::from_millis(delay),
+            PlatformEvent::Mouse {
+                name,
+                cursor: (40., 40.).into(),
+                button: Some(MouseButton::Left),
+            },
+        );
+    }
+
+    // Locations are logical, so they are scaled on replay
+    let (mut test, state) = TestingRunner::new(
+        app,
+        (500., 500.).into(),
+        |runner| runner.provide_root_context(|| State::create((0, 0))),
+        2.,
+    );
+    test.sync_and_update();
+
+    // Only the last two clicks are close enough in the script timestamps to be a double click
+    test.replay(&script);
+    assert_eq!(*state.peek(), (3, 1));
+}
+
+#[test]
+fn replay_double_click() {
+    fn app() -> Element {
+        let mut state = use_consume::<State<i32>>();
+        rect()
+            .expanded()
+            .on_double_click(move |_| *state.write() += 1)
+            .into()
+    }
+
+    let mut script = InteractionScript::new((500., 500.).into());
+    for (delay, name) in [
+        (0, MouseEventName::MouseDown),
+        (10, MouseEventName::MouseUp),
+        (90, MouseEventName::MouseDown),
+        (10, MouseEventName::MouseUp),
+    ] {
+        script.push(
+            Duration::from_millis(delay),
+            PlatformEvent::Mouse {
+                name,
+                cursor: (40., 40.).into(),
+                button: Some(MouseButton::Left),
+            },
+        );
+    }
+
+    let (mut test, state) = TestingRunner::new(
+        app,
+        (500., 500.).into(),
+        |runner| runner.provide_root_context(|| State::create(0)),
+        1.,
+    );
+    test.sync_and_update();
+
+    test.replay(&script);
+    assert_eq!(*state.peek(), 1);
+}
+
+#[test]
+fn replay_long_press() {
+    fn app() -> Element {
+        let mut state = use_consume::<State<i32>>();
+        rect()
+            .expanded()
+            .on_long_press(move |_| *state.write() += 1)
+            .into()
+    }
+
+    let mut script = InteractionScript::new((500., 500.).into());
+    for (delay, name) in [
+        (Duration::ZERO, TouchEventName::TouchStart),
+        (
+            LONG_PRESS_DURATION + Duration::from_millis(100),
+            TouchEventName::TouchEnd,
+        ),
+    ] {
+        script.push(
+            delay,
+            PlatformEvent::Touch {
+                name,
+                location: (15., 15.).into(),
+                finger_id: 0,
+                phase: TouchPhase::Started,
+                force: None,
+            },
+        );
+    }
+
+    let (mut test, state) = TestingRunner::new(
+        app,
+        (500., 500.).into(),
+        |runner| runner.provide_root_context(|| State::create(0)),
+        1.,
+    );
+    test.sync_and_update();
+
+    // The touch is held on the virtual clock, so the replay does not wait in real time
+    let started = std::time::Instant::now();
+    test.replay(&script);
+    assert!(started.elapsed() < LONG_PRESS_DURATION);
+    assert_eq!(*state.peek(), 1);
+}
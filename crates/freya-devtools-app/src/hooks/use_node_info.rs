@@ -1,6 +1,7 @@
 use freya_core::integration::NodeId;
 use freya_devtools::NodeInfo;
 use freya_radio::hooks::use_radio;
+use torin::torin::DirtyReason;
 
 use crate::state::DevtoolsChannel;
 
@@ -15,3 +16,16 @@ pub fn use_node_info(node_id: NodeId, window_id: u64) -> Option<NodeInfo> {
         .find(|node| node.node_id == node_id)
         .cloned()
 }
+
+/// Why was the node measured again in the last layout measurement, if it was dirty.
+pub fn use_node_dirty_reason(node_id: NodeId, window_id: u64) -> Option<DirtyReason> {
+    let radio = use_radio(DevtoolsChannel::UpdatedTree);
+    let state = radio.read();
+
+    state
+        .layout_stats
+        .get(&window_id)?
+        .dirty
+        .get(&node_id)
+        .copied()
+}
@@ -59,6 +59,7 @@ pub fn app() -> impl IntoElement {
     use_init_root_theme(|| DARK_THEME);
     use_init_radio_station::<DevtoolsState, DevtoolsChannel>(|| DevtoolsState {
         nodes: HashMap::new(),
+        layout_stats: HashMap::new(),
         expanded_nodes: HashSet::default(),
         client: Arc::default(),
         animation_speed: AnimationClock::DEFAULT_SPEED / AnimationClock::MAX_SPEED * 100.,
@@ -90,6 +91,12 @@ pub fn app() -> impl IntoElement {
                                     .nodes
                                     .insert(window_id, nodes);
                             }
+                            OutgoingMessageAction::LayoutStats { window_id, stats } => {
+                                radio
+                                    .write_channel(DevtoolsChannel::UpdatedTree)
+                                    .layout_stats
+                                    .insert(window_id, stats);
+                            }
                         }
                     }
                 })
@@ -101,10 +108,11 @@ pub fn app() -> impl IntoElement {
             loop {
                 println!("Connecting to server...");
                 connect(radio).await.ok();
-                radio
-                    .write_channel(DevtoolsChannel::UpdatedTree)
-                    .nodes
-                    .clear();
+                {
+                    let mut state = radio.write_channel(DevtoolsChannel::UpdatedTree);
+                    state.nodes.clear();
+                    state.layout_stats.clear();
+                }
                 Timer::after(Duration::from_secs(2)).await;
             }
         })
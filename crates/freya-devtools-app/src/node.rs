@@ -1,7 +1,11 @@
 use freya::prelude::*;
 use freya_core::integration::NodeId;
+use torin::torin::DirtyReason;
 
-use crate::hooks::use_node_info;
+use crate::hooks::{
+    use_node_dirty_reason,
+    use_node_info,
+};
 
 #[derive(PartialEq)]
 pub struct NodeElement {
@@ -25,6 +29,7 @@ impl Component for NodeElement {
     }
 
     fn render(&self) -> impl IntoElement {
+        let dirty_reason = use_node_dirty_reason(self.node_id, self.window_id);
         let Some(node) = use_node_info(self.node_id, self.window_id) else {
             return rect().into_element();
         };
@@ -150,6 +155,16 @@ impl Component for NodeElement {
                                     format!(", id: {}", id)
                                 })
                                 .color(Color::from_rgb(200, 200, 200)),
+                            )
+                            .span(
+                                // Highlight the nodes relaid out in the last layout measurement
+                                Span::new(match dirty_reason {
+                                    Some(DirtyReason::None) => ", relaid out",
+                                    Some(DirtyReason::Reorder) => ", reordered",
+                                    Some(DirtyReason::InnerLayout) => ", inner relaid out",
+                                    None => "",
+                                })
+                                .color(Color::from_rgb(255, 165, 0)),
                             ),
                     ),
             );
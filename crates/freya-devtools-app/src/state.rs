@@ -19,10 +19,12 @@ use freya_devtools::{
 };
 use freya_radio::hooks::RadioChannel;
 use smol::net::TcpStream;
+use torin::torin::LayoutStats;
 use tungstenite::Message;
 
 pub struct DevtoolsState {
     pub(crate) nodes: HashMap<u64, Vec<NodeInfo>>,
+    pub(crate) layout_stats: HashMap<u64, LayoutStats<NodeId>>,
     pub(crate) expanded_nodes: HashSet<(u64, NodeId)>,
     pub(crate) client: Arc<Mutex<Option<WebSocketSender<TcpStream>>>>,
     pub(crate) animation_speed: f32,
@@ -9,6 +9,7 @@ pub struct Misc;
 impl Component for Misc {
     fn render(&self) -> impl IntoElement {
         let mut radio = use_radio(DevtoolsChannel::Misc);
+        let tree_radio = use_radio(DevtoolsChannel::UpdatedTree);
 
         use_side_effect(move || {
             let radio = radio.read();
@@ -45,5 +46,22 @@ impl Component for Misc {
                     })
                     .child("Reset"),
             )
+            .child("Last Layout")
+            .children(
+                tree_radio
+                    .read()
+                    .layout_stats
+                    .iter()
+                    .map(|(window_id, stats)| {
+                        format!(
+                            "Window {window_id}: {} dirty, {} measured, {} cached, {} translated",
+                            stats.dirty.len(),
+                            stats.measured_nodes,
+                            stats.cached_nodes,
+                            stats.translated_nodes
+                        )
+                        .into()
+                    }),
+            )
     }
 }
@@ -112,6 +112,31 @@ impl NodeStateAttributes for NodeState {
             ),
             ("content", AttributeType::Content(&self.layout.content)),
             ("spacing", AttributeType::Length(self.layout.spacing)),
+            (
+                "align_self",
+                AttributeType::Text(
+                    self.layout
+                        .align_self
+                        .as_ref()
+                        .map_or_else(|| "auto".to_string(), Alignment::pretty),
+                ),
+            ),
+            (
+                "flex_shrink",
+                AttributeType::Measure(self.layout.flex_shrink.get()),
+            ),
+            (
+                "aspect_ratio",
+                AttributeType::OptionalMeasure(self.layout.aspect_ratio),
+            ),
+            (
+                "grid_position",
+                AttributeType::Text(self.layout.grid_position.pretty()),
+            ),
+            (
+                "layout_direction",
+                AttributeType::Text(self.layout.layout_direction.pretty()),
+            ),
         ]
     }
     fn style_attributes(&'_ self) -> Vec<(&'_ str, AttributeType<'_>)> {
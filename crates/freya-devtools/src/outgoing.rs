@@ -1,7 +1,9 @@
+use freya_core::integration::NodeId;
 use serde::{
     Deserialize,
     Serialize,
 };
+use torin::torin::LayoutStats;
 
 use crate::node_info::NodeInfo;
 
@@ -16,4 +18,9 @@ pub enum OutgoingMessageAction {
         window_id: u64,
         nodes: Vec<NodeInfo>,
     },
+    /// Statistics of the last layout measurement of a window.
+    LayoutStats {
+        window_id: u64,
+        stats: LayoutStats<NodeId>,
+    },
 }
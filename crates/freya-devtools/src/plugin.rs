@@ -242,6 +242,14 @@ impl FreyaPlugin for DevtoolsPlugin {
 
                 self.sync(window.id(), window.scale_factor() as f32, tree);
             }
+            PluginEvent::FinishedMeasuringLayout { window, tree } => {
+                self.broadcast(OutgoingMessage {
+                    action: OutgoingMessageAction::LayoutStats {
+                        window_id: window.id().into(),
+                        stats: tree.layout_stats().clone(),
+                    },
+                });
+            }
             PluginEvent::WindowCreated {
                 window,
                 animation_clock,
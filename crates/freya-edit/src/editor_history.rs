@@ -33,6 +33,10 @@ pub enum HistoryChange {
 pub struct HistoryTransaction {
     pub timestamp: Instant,
     pub changes: Vec<HistoryChange>,
+    /// Committed transactions are never grouped with other changes.
+    pub(crate) is_committed: bool,
+    /// Grouped transactions are only grouped with other grouped transactions.
+    pub(crate) is_grouped: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -43,6 +47,8 @@ pub struct EditorHistory {
     pub version: usize,
     /// After how many seconds since the last transaction a change should be grouped with the last transaction.
     transaction_threshold_groping: Duration,
+    /// Changes of the transaction started with [EditorHistory::start_transaction], if any.
+    pending_transaction: Option<Vec<HistoryChange>>,
 }
 
 impl EditorHistory {
@@ -52,10 +58,64 @@ impl EditorHistory {
             current_transaction: 0,
             version: 0,
             transaction_threshold_groping,
+            pending_transaction: None,
         }
     }
 
+    /// Group all the changes pushed until [EditorHistory::commit_transaction] is called,
+    /// so they are undone and redone as a single change.
+    pub fn start_transaction(&mut self) {
+        self.pending_transaction.get_or_insert_default();
+    }
+
+    /// Push the changes grouped since [EditorHistory::start_transaction] was called
+    /// as a single transaction, regardless of how recent the last transaction is.
+    pub fn commit_transaction(&mut self) {
+        let Some(changes) = self.pending_transaction.take() else {
+            return;
+        };
+        if changes.is_empty() {
+            return;
+        }
+
+        self.push_transaction(changes, true, false);
+    }
+
+    /// Like [EditorHistory::commit_transaction], but the changes are grouped with the last transaction
+    /// if it was also committed with this method and is recent enough, the same way single changes are.
+    pub fn commit_grouped_transaction(&mut self) {
+        let Some(changes) = self.pending_transaction.take() else {
+            return;
+        };
+        if changes.is_empty() {
+            return;
+        }
+
+        if self.can_redo() {
+            self.transactions.drain(self.current_transaction..);
+        }
+
+        let last_transaction = self
+            .transactions
+            .get_mut(self.current_transaction.saturating_sub(1));
+        if let Some(last_transaction) = last_transaction
+            && last_transaction.is_grouped
+            && last_transaction.timestamp.elapsed() <= self.transaction_threshold_groping
+        {
+            last_transaction.changes.extend(changes);
+            last_transaction.timestamp = Instant::now();
+            return;
+        }
+
+        self.push_transaction(changes, true, true);
+    }
+
     pub fn push_change(&mut self, change: HistoryChange) {
+        if let Some(pending_transaction) = &mut self.pending_transaction {
+            pending_transaction.push(change);
+            return;
+        }
+
         if self.can_redo() {
             self.transactions.drain(self.current_transaction..);
         }
@@ -64,6 +124,7 @@ impl EditorHistory {
             .transactions
             .get_mut(self.current_transaction.saturating_sub(1));
         if let Some(last_transaction) = last_transaction
+            && !last_transaction.is_committed
             && last_transaction.timestamp.elapsed() <= self.transaction_threshold_groping
         {
             last_transaction.changes.push(change);
@@ -71,9 +132,24 @@ impl EditorHistory {
             return;
         }
 
+        self.push_transaction(vec![change], false, false);
+    }
+
+    fn push_transaction(
+        &mut self,
+        changes: Vec<HistoryChange>,
+        is_committed: bool,
+        is_grouped: bool,
+    ) {
+        if self.can_redo() {
+            self.transactions.drain(self.current_transaction..);
+        }
+
         self.transactions.push(HistoryTransaction {
             timestamp: Instant::now(),
-            changes: vec![change],
+            changes,
+            is_committed,
+            is_grouped,
         });
 
         self.current_transaction = self.transactions.len();
@@ -398,4 +474,131 @@ mod test {
         assert_eq!(selection, TextSelection::new_cursor(5));
         assert_eq!(rope.to_string(), "Hello");
     }
+
+    #[test]
+    fn test_transaction_undo_redo() {
+        let mut rope = Rope::new();
+        let mut history = EditorHistory::new(Duration::ZERO);
+
+        rope.insert(0, "ab");
+
+        history.start_transaction();
+        rope.insert_char(2, '!');
+        history.push_change(HistoryChange::InsertChar {
+            idx: 2,
+            ch: '!',
+            len: 1,
+            selection: TextSelection::new_cursor(2),
+        });
+        rope.insert_char(1, '!');
+        history.push_change(HistoryChange::InsertChar {
+            idx: 1,
+            ch: '!',
+            len: 1,
+            selection: TextSelection::new_cursor(1),
+        });
+        assert!(!history.can_undo());
+        history.commit_transaction();
+        assert_eq!(rope.to_string(), "a!b!");
+
+        assert!(history.can_undo());
+        history.undo(&mut rope);
+        assert_eq!(rope.to_string(), "ab");
+        assert!(!history.can_undo());
+
+        history.redo(&mut rope);
+        assert_eq!(rope.to_string(), "a!b!");
+    }
+
+    #[test]
+    fn test_transaction_is_not_grouped() {
+        let mut rope = Rope::new();
+        let mut history = EditorHistory::new(Duration::from_secs(60));
+
+        rope.insert(0, "ab");
+        history.push_change(HistoryChange::InsertText {
+            idx: 0,
+            text: "ab".to_owned(),
+            len: 2,
+            selection: TextSelection::new_cursor(0),
+        });
+
+        history.start_transaction();
+        rope.insert_char(2, '!');
+        history.push_change(HistoryChange::InsertChar {
+            idx: 2,
+            ch: '!',
+            len: 1,
+            selection: TextSelection::new_cursor(2),
+        });
+        rope.insert_char(1, '!');
+        history.push_change(HistoryChange::InsertChar {
+            idx: 1,
+            ch: '!',
+            len: 1,
+            selection: TextSelection::new_cursor(1),
+        });
+        history.commit_transaction();
+
+        rope.insert_char(4, '?');
+        history.push_change(HistoryChange::InsertChar {
+            idx: 4,
+            ch: '?',
+            len: 1,
+            selection: TextSelection::new_cursor(4),
+        });
+        assert_eq!(rope.to_string(), "a!b!?");
+
+        // Even if they happened close in time, the transaction is its own undo step
+        history.undo(&mut rope);
+        assert_eq!(rope.to_string(), "a!b!");
+        history.undo(&mut rope);
+        assert_eq!(rope.to_string(), "ab");
+        history.undo(&mut rope);
+        assert_eq!(rope.to_string(), "");
+    }
+
+    #[test]
+    fn test_grouped_transactions() {
+        let mut rope = Rope::new();
+        let mut history = EditorHistory::new(Duration::from_secs(60));
+
+        rope.insert(0, "ab");
+        history.push_change(HistoryChange::InsertText {
+            idx: 0,
+            text: "ab".to_owned(),
+            len: 2,
+            selection: TextSelection::new_cursor(0),
+        });
+
+        for ch in ['!', '?'] {
+            history.start_transaction();
+            let end = rope.len_chars();
+            rope.insert_char(end, ch);
+            history.push_change(HistoryChange::InsertChar {
+                idx: end,
+                ch,
+                len: 1,
+                selection: TextSelection::new_cursor(end),
+            });
+            rope.insert_char(1, ch);
+            history.push_change(HistoryChange::InsertChar {
+                idx: 1,
+                ch,
+                len: 1,
+                selection: TextSelection::new_cursor(1),
+            });
+            history.commit_grouped_transaction();
+        }
+        assert_eq!(rope.to_string(), "a?!b!?");
+
+        // Consecutive grouped transactions are undone together, but not with the previous change
+        history.undo(&mut rope);
+        assert_eq!(rope.to_string(), "ab");
+        history.redo(&mut rope);
+        assert_eq!(rope.to_string(), "a?!b!?");
+        history.undo(&mut rope);
+        history.undo(&mut rope);
+        assert_eq!(rope.to_string(), "");
+    }
 }
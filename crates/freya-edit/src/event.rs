@@ -60,6 +60,24 @@ impl EditableEvent<'_> {
 
                 let mut text_editor = editor.write();
 
+                // Alt adds a new cursor, dragging afterwards selects a block
+                if dragging.peek().alt {
+                    let char_position = paragraph.get_glyph_position_at_coordinate(
+                        location.mul(*scale_factor).to_i32().to_tuple(),
+                    );
+                    let pos = text_editor
+                        .measure_selection(char_position.position as usize, editor_line)
+                        .pos();
+                    text_editor.add_selection(TextSelection::new_cursor(pos));
+
+                    let mut dragging = dragging.write();
+                    dragging.clicked = true;
+                    dragging.block_anchor = Some(pos);
+                    return;
+                }
+
+                text_editor.clear_secondary_selections();
+
                 if dragging.peek().shift || dragging.peek().clicked {
                     text_editor.selection_mut().set_as_range();
                 } else {
@@ -143,6 +161,22 @@ impl EditableEvent<'_> {
                         .get_glyph_position_at_coordinate(dist_position.to_i32().to_tuple());
                     let to = dist_char.position as usize;
 
+                    if let Some(block_anchor) = dragging.peek().block_anchor {
+                        let pos = editor.peek().measure_selection(to, editor_line).pos();
+                        if pos != block_anchor {
+                            editor.write_if(|mut text_editor| {
+                                let current_selections = (
+                                    text_editor.selection().clone(),
+                                    text_editor.secondary_selections().to_vec(),
+                                );
+                                text_editor.set_block_selection(block_anchor, pos);
+                                current_selections.0 != *text_editor.selection()
+                                    || current_selections.1 != text_editor.secondary_selections()
+                            });
+                        }
+                        return;
+                    }
+
                     if editor.peek().get_selection().is_none() {
                         editor.write().selection_mut().set_as_range();
                     }
@@ -159,7 +193,9 @@ impl EditableEvent<'_> {
                 }
             }
             EditableEvent::Release => {
-                dragging.write().clicked = false;
+                let mut dragging = dragging.write();
+                dragging.clicked = false;
+                dragging.block_anchor = None;
             }
             EditableEvent::KeyDown { key, modifiers } => {
                 match key {
@@ -167,6 +203,9 @@ impl EditableEvent<'_> {
                     Key::Named(NamedKey::Shift) => {
                         dragging.write().shift = true;
                     }
+                    Key::Named(NamedKey::Alt) => {
+                        dragging.write().alt = true;
+                    }
                     // Handle editing
                     _ => {
                         editor.write_if(|mut editor| {
@@ -189,6 +228,9 @@ impl EditableEvent<'_> {
                 if *key == Key::Named(NamedKey::Shift) {
                     dragging.write().shift = false;
                 }
+                if *key == Key::Named(NamedKey::Alt) {
+                    dragging.write().alt = false;
+                }
             }
         };
     }
@@ -197,5 +239,8 @@ impl EditableEvent<'_> {
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct TextDragging {
     pub shift: bool,
+    pub alt: bool,
     pub clicked: bool,
+    /// Where the block selection started, when pressing with Alt.
+    pub block_anchor: Option<usize>,
 }
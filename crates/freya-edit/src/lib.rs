@@ -2,6 +2,7 @@ mod config;
 mod editor_history;
 mod event;
 mod rope_editor;
+mod search;
 mod text_editor;
 mod use_editable;
 
@@ -14,5 +15,6 @@ pub use ropey::{
     Rope,
     RopeSlice,
 };
+pub use search::*;
 pub use text_editor::*;
 pub use use_editable::*;
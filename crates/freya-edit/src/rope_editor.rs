@@ -33,6 +33,7 @@ pub struct PreeditState {
 pub struct RopeEditor {
     pub(crate) rope: Rope,
     pub(crate) selection: TextSelection,
+    pub(crate) secondary_selections: Vec<TextSelection>,
     pub(crate) indentation: u8,
     pub(crate) history: EditorHistory,
     pub(crate) preedit: Option<PreeditState>,
@@ -55,6 +56,7 @@ impl RopeEditor {
         Self {
             rope: Rope::from_str(&text),
             selection,
+            secondary_selections: Vec::new(),
             indentation,
             history,
             preedit: None,
@@ -259,6 +261,14 @@ impl TextEditor for RopeEditor {
         &mut self.selection
     }
 
+    fn secondary_selections(&self) -> &[TextSelection] {
+        &self.secondary_selections
+    }
+
+    fn secondary_selections_mut(&mut self) -> Option<&mut Vec<TextSelection>> {
+        Some(&mut self.secondary_selections)
+    }
+
     fn has_any_selection(&self) -> bool {
         self.selection.is_range()
     }
@@ -352,9 +362,17 @@ impl<'a> Iterator for LinesIterator<'a> {
 mod test {
     use std::time::Duration;
 
+    use keyboard_types::{
+        Key,
+        Modifiers,
+        NamedKey,
+    };
+
     use super::RopeEditor;
     use crate::{
         EditorHistory,
+        SearchQuery,
+        TextEvent,
         TextSelection,
         text_editor::TextEditor,
     };
@@ -428,4 +446,123 @@ mod test {
         assert_eq!(ed.rope().to_string(), "Hello");
         assert_eq!(ed.cursor_pos(), 5);
     }
+
+    #[test]
+    fn multiple_selections_edit_and_undo_together() {
+        let mut ed = editor("ab\ncd\nef");
+        ed.move_cursor_to(1);
+        ed.add_selection(TextSelection::new_cursor(4));
+        ed.add_selection(TextSelection::new_cursor(7));
+
+        ed.process_key(
+            &Key::Character("!".into()),
+            &Modifiers::empty(),
+            false,
+            true,
+            false,
+        );
+        assert_eq!(ed.rope().to_string(), "a!b\nc!d\ne!f");
+        assert_eq!(ed.cursor_pos(), 10);
+        assert_eq!(
+            ed.secondary_selections(),
+            [TextSelection::new_cursor(2), TextSelection::new_cursor(6)]
+        );
+
+        ed.process_key(
+            &Key::Named(NamedKey::Backspace),
+            &Modifiers::empty(),
+            false,
+            true,
+            false,
+        );
+        assert_eq!(ed.rope().to_string(), "ab\ncd\nef");
+
+        // Each edit of all the selections is undone at once
+        let modifiers = if cfg!(target_os = "macos") {
+            Modifiers::META
+        } else {
+            Modifiers::CONTROL
+        };
+        ed.process_key(&Key::Character("z".into()), &modifiers, false, true, false);
+        assert_eq!(ed.rope().to_string(), "a!b\nc!d\ne!f");
+        assert!(ed.secondary_selections().is_empty());
+
+        ed.process_key(&Key::Character("z".into()), &modifiers, false, true, false);
+        assert_eq!(ed.rope().to_string(), "ab\ncd\nef");
+    }
+
+    #[test]
+    fn block_selection() {
+        let mut ed = editor("hello\nhi\nworld");
+        ed.set_block_selection(1, 12);
+
+        assert_eq!(ed.selection(), &TextSelection::new_range((10, 12)));
+        assert_eq!(
+            ed.secondary_selections(),
+            [
+                TextSelection::new_range((1, 3)),
+                TextSelection::new_range((7, 8))
+            ]
+        );
+
+        ed.process_key(
+            &Key::Character("X".into()),
+            &Modifiers::empty(),
+            false,
+            true,
+            false,
+        );
+        assert_eq!(ed.rope().to_string(), "hXlo\nhX\nwXld");
+    }
+
+    #[test]
+    fn find_and_replace() {
+        let mut ed = editor("foo bar\nFoo foobar foo");
+        let query = SearchQuery::new("foo")
+            .with_case_sensitive(false)
+            .with_whole_word(true);
+
+        assert_eq!(
+            ed.find_all(&query).unwrap(),
+            vec![(0, 3), (8, 11), (19, 22)]
+        );
+
+        ed.move_cursor_to(4);
+        ed.find_next(&query).unwrap();
+        assert_eq!(ed.selection(), &TextSelection::new_range((8, 11)));
+        ed.find_previous(&query).unwrap();
+        assert_eq!(ed.selection(), &TextSelection::new_range((0, 3)));
+        // Wraps around to the last match
+        ed.find_previous(&query).unwrap();
+        assert_eq!(ed.selection(), &TextSelection::new_range((19, 22)));
+
+        // Replaces the selected match and selects the next one
+        let event = ed.replace_next(&query, "baz").unwrap();
+        assert!(event.contains(TextEvent::TEXT_CHANGED));
+        assert_eq!(ed.rope().to_string(), "foo bar\nFoo foobar baz");
+        assert_eq!(ed.selection(), &TextSelection::new_range((0, 3)));
+    }
+
+    #[test]
+    fn replace_all_is_undone_at_once() {
+        let mut ed = editor("a=1\nb=22\nc");
+        let query = SearchQuery::regex(r"(\w)=(\d+)");
+
+        ed.replace_all(&query, "$2=$1").unwrap();
+        assert_eq!(ed.rope().to_string(), "1=a\n22=b\nc");
+        assert_eq!(ed.cursor_pos(), 3);
+
+        ed.undo();
+        assert_eq!(ed.rope().to_string(), "a=1\nb=22\nc");
+        ed.redo();
+        assert_eq!(ed.rope().to_string(), "1=a\n22=b\nc");
+
+        // Invalid regular expressions are reported and nothing changes
+        assert!(ed.replace_all(&SearchQuery::regex("("), "").is_err());
+        assert!(
+            ed.replace_all(&SearchQuery::new("missing"), "")
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
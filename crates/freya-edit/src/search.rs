@@ -0,0 +1,235 @@
+use regex::{
+    Captures,
+    Regex,
+    RegexBuilder,
+};
+
+/// Error returned when the query of a [SearchQuery] is not a valid regular expression.
+pub type SearchError = regex::Error;
+
+/// A match of a [SearchQuery] and the text it is replaced with.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SearchReplacement {
+    /// Range of the match in UTF-16 code units.
+    pub range: (usize, usize),
+    pub text: String,
+}
+
+/// How the text of a [SearchQuery] is interpreted.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SearchMode {
+    /// Match the text as it is.
+    #[default]
+    Plain,
+    /// Match the text as a regular expression.
+    Regex,
+}
+
+/// Text to find in a [TextEditor](crate::TextEditor), see [TextEditor::find_all](crate::TextEditor::find_all).
+#[derive(Clone, PartialEq, Debug)]
+pub struct SearchQuery {
+    pub(crate) text: String,
+    pub(crate) mode: SearchMode,
+    pub(crate) case_sensitive: bool,
+    pub(crate) whole_word: bool,
+}
+
+impl SearchQuery {
+    /// Create a case-sensitive [SearchQuery] that matches the given text as it is.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            mode: SearchMode::Plain,
+            case_sensitive: true,
+            whole_word: false,
+        }
+    }
+
+    /// Create a case-sensitive [SearchQuery] that matches the given regular expression.
+    /// Replacements can refer to its capture groups with `$1` or `${name}`.
+    pub fn regex(text: impl Into<String>) -> Self {
+        Self::new(text).with_mode(SearchMode::Regex)
+    }
+
+    /// Specify how the text is interpreted
+    pub fn with_mode(mut self, mode: SearchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Specify whether the case of letters must match or not
+    pub fn with_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Only match whole words, not the text inside other words
+    pub fn with_whole_word(mut self, whole_word: bool) -> Self {
+        self.whole_word = whole_word;
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn mode(&self) -> SearchMode {
+        self.mode
+    }
+
+    pub fn case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    pub fn whole_word(&self) -> bool {
+        self.whole_word
+    }
+
+    /// Check that the query can be searched, which fails for invalid regular expressions.
+    pub fn validate(&self) -> Result<(), SearchError> {
+        self.build().map(|_| ())
+    }
+
+    fn build(&self) -> Result<Regex, SearchError> {
+        let pattern = match self.mode {
+            SearchMode::Plain => regex::escape(&self.text),
+            SearchMode::Regex => self.text.clone(),
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive)
+            .multi_line(true)
+            .build()
+    }
+
+    /// Call `on_match` with the range in UTF-16 code units and the captures of every match in the given text.
+    /// Empty matches are skipped.
+    fn search(
+        &self,
+        text: &str,
+        mut on_match: impl FnMut((usize, usize), &Captures),
+    ) -> Result<(), SearchError> {
+        if self.text.is_empty() {
+            return Ok(());
+        }
+        let regex = self.build()?;
+
+        // Byte and UTF-16 offsets of the end of the last match, to convert the byte offsets incrementally
+        let mut last_byte = 0;
+        let mut last_utf16 = 0;
+        for captures in regex.captures_iter(text) {
+            let matched = captures.get(0).unwrap();
+            if matched.is_empty() || (self.whole_word && !is_whole_word(text, matched.range())) {
+                continue;
+            }
+            let from = last_utf16 + text[last_byte..matched.start()].encode_utf16().count();
+            let to = from + matched.as_str().encode_utf16().count();
+            last_byte = matched.end();
+            last_utf16 = to;
+
+            on_match((from, to), &captures);
+        }
+
+        Ok(())
+    }
+
+    /// Find the ranges in UTF-16 code units of all the matches in the given text.
+    pub fn find_matches(&self, text: &str) -> Result<Vec<(usize, usize)>, SearchError> {
+        let mut matches = Vec::new();
+        self.search(text, |range, _| matches.push(range))?;
+        Ok(matches)
+    }
+
+    /// Find all the matches in the given text along with the text each of them is replaced with.
+    pub fn find_replacements(
+        &self,
+        text: &str,
+        replacement: &str,
+    ) -> Result<Vec<SearchReplacement>, SearchError> {
+        let mut replacements = Vec::new();
+        self.search(text, |range, captures| {
+            let text = match self.mode {
+                SearchMode::Plain => replacement.to_string(),
+                SearchMode::Regex => {
+                    let mut expanded = String::new();
+                    captures.expand(replacement, &mut expanded);
+                    expanded
+                }
+            };
+            replacements.push(SearchReplacement { range, text });
+        })?;
+        Ok(replacements)
+    }
+}
+
+/// Whether the given byte range of the text is not surrounded by word characters.
+fn is_whole_word(text: &str, range: std::ops::Range<usize>) -> bool {
+    let is_word_char = |ch: char| ch.is_alphanumeric() || ch == '_';
+    !text[..range.start]
+        .chars()
+        .next_back()
+        .is_some_and(is_word_char)
+        && !text[range.end..].chars().next().is_some_and(is_word_char)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn plain_and_case_insensitive() {
+        let text = "Hello hello HELLO";
+
+        assert_eq!(
+            SearchQuery::new("hello").find_matches(text).unwrap(),
+            vec![(6, 11)]
+        );
+        assert_eq!(
+            SearchQuery::new("hello")
+                .with_case_sensitive(false)
+                .find_matches(text)
+                .unwrap(),
+            vec![(0, 5), (6, 11), (12, 17)]
+        );
+        // Regex syntax is matched literally
+        assert_eq!(
+            SearchQuery::new("l+").find_matches("all+").unwrap(),
+            vec![(2, 4)]
+        );
+        assert!(SearchQuery::new("").find_matches(text).unwrap().is_empty());
+    }
+
+    #[test]
+    fn whole_word() {
+        let query = SearchQuery::new("foo").with_whole_word(true);
+
+        assert_eq!(
+            query.find_matches("foofoo foo foo_bar (foo)").unwrap(),
+            vec![(7, 10), (20, 23)]
+        );
+    }
+
+    #[test]
+    fn regex_and_replacements() {
+        let query = SearchQuery::regex(r"(\w+)@(\w+)");
+
+        assert_eq!(
+            query.find_replacements("a@b 😀 cd@ef", "$2@$1").unwrap(),
+            vec![
+                SearchReplacement {
+                    range: (0, 3),
+                    text: "b@a".to_string()
+                },
+                SearchReplacement {
+                    range: (7, 12),
+                    text: "ef@cd".to_string()
+                }
+            ]
+        );
+        // Lines are matched with ^ and $
+        assert_eq!(
+            SearchQuery::regex("^b").find_matches("a\nb").unwrap(),
+            vec![(2, 3)]
+        );
+        assert!(SearchQuery::regex("(").validate().is_err());
+    }
+}
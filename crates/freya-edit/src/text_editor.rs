@@ -1,7 +1,12 @@
 use std::{
     borrow::Cow,
-    cmp::Ordering,
+    cmp::{
+        Ordering,
+        Reverse,
+    },
     fmt::Display,
+    iter,
+    mem,
     ops::Range,
 };
 
@@ -13,7 +18,14 @@ use keyboard_types::{
 };
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::editor_history::EditorHistory;
+use crate::{
+    editor_history::EditorHistory,
+    search::{
+        SearchError,
+        SearchQuery,
+        SearchReplacement,
+    },
+};
 
 #[derive(PartialEq, Clone, Debug, Copy, Hash)]
 pub enum EditorLine {
@@ -85,6 +97,17 @@ impl TextSelection {
         }
     }
 
+    /// Move both the start and end positions by the given offset.
+    pub fn offset(&mut self, offset: isize) {
+        match self {
+            Self::Cursor(pos) => *pos = pos.saturating_add_signed(offset),
+            Self::Range { from, to } => {
+                *from = from.saturating_add_signed(offset);
+                *to = to.saturating_add_signed(offset);
+            }
+        }
+    }
+
     pub fn is_range(&self) -> bool {
         matches!(self, Self::Range { .. })
     }
@@ -171,6 +194,83 @@ pub trait TextEditor {
     /// Get a mutable reference to text selection
     fn selection_mut(&mut self) -> &mut TextSelection;
 
+    /// Get the secondary selections, these are edited along with the main selection.
+    ///
+    /// Editors without storage for them, see [TextEditor::secondary_selections_mut], have none.
+    fn secondary_selections(&self) -> &[TextSelection] {
+        &[]
+    }
+
+    /// Get a mutable reference to the secondary selections, if the editor has storage for them.
+    ///
+    /// Editors returning `None`, the default, only support a single selection.
+    fn secondary_selections_mut(&mut self) -> Option<&mut Vec<TextSelection>> {
+        None
+    }
+
+    /// Make the given selection the main one, the current main selection becomes a secondary selection.
+    fn add_selection(&mut self, selection: TextSelection) {
+        let previous_selection = mem::replace(self.selection_mut(), selection.clone());
+        let Some(secondary_selections) = self.secondary_selections_mut() else {
+            return;
+        };
+        secondary_selections.retain(|secondary_selection| *secondary_selection != selection);
+        if previous_selection != selection && !secondary_selections.contains(&previous_selection) {
+            secondary_selections.push(previous_selection);
+        }
+    }
+
+    /// Remove the secondary selections, leaving only the main selection.
+    fn clear_secondary_selections(&mut self) {
+        if let Some(secondary_selections) = self.secondary_selections_mut() {
+            secondary_selections.clear();
+        }
+    }
+
+    /// Select the block between the lines and columns of the given positions, with one selection per line.
+    /// The selection in the line of `to` becomes the main selection.
+    fn set_block_selection(&mut self, from: usize, to: usize) {
+        let row_and_col = |pos: usize| {
+            let row = self.char_to_line(self.utf16_cu_to_char(pos));
+            (row, pos - self.char_to_utf16_cu(self.line_to_char(row)))
+        };
+        let (from_row, from_col) = row_and_col(from);
+        let (to_row, to_col) = row_and_col(to);
+
+        let mut selections = (from_row.min(to_row)..=from_row.max(to_row))
+            .map(|row| {
+                let row_char = self.char_to_utf16_cu(self.line_to_char(row));
+                // Columns past the end of a line are clamped, without including the line break
+                let row_len = self
+                    .line(row)
+                    .map(|line| {
+                        line.text
+                            .trim_end_matches(['\n', '\r'])
+                            .encode_utf16()
+                            .count()
+                    })
+                    .unwrap_or_default();
+                let from = row_char + from_col.min(row_len);
+                let to = row_char + to_col.min(row_len);
+                if from == to {
+                    TextSelection::new_cursor(to)
+                } else {
+                    TextSelection::new_range((from, to))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let main_selection = if to_row < from_row {
+            selections.remove(0)
+        } else {
+            selections.pop().unwrap()
+        };
+        *self.selection_mut() = main_selection;
+        if let Some(secondary_selections) = self.secondary_selections_mut() {
+            *secondary_selections = selections;
+        }
+    }
+
     /// Get the cursor row
     fn cursor_row(&self) -> usize {
         let pos = self.cursor_pos();
@@ -285,7 +385,56 @@ pub trait TextEditor {
 
     // Return the visible selected text for the given editor line
     fn get_visible_selection(&self, editor_line: EditorLine) -> Option<(usize, usize)> {
-        let (selected_from, selected_to) = match self.selection() {
+        self.get_visible_selection_of(self.selection(), editor_line)
+    }
+
+    // Return the visible selected text of the main and secondary selections for the given editor line
+    fn get_visible_selections(&self, editor_line: EditorLine) -> Vec<(usize, usize)> {
+        iter::once(self.selection())
+            .chain(self.secondary_selections())
+            .filter_map(|selection| self.get_visible_selection_of(selection, editor_line))
+            .collect()
+    }
+
+    // Return the visible parts of the given ranges, such as search matches, for the given editor line
+    fn get_visible_ranges(
+        &self,
+        ranges: &[(usize, usize)],
+        editor_line: EditorLine,
+    ) -> Vec<(usize, usize)> {
+        ranges
+            .iter()
+            .filter_map(|range| {
+                self.get_visible_selection_of(&TextSelection::new_range(*range), editor_line)
+            })
+            .collect()
+    }
+
+    // Return the positions of the secondary cursors in the given editor line
+    fn get_visible_secondary_cursors(&self, editor_line: EditorLine) -> Vec<usize> {
+        self.secondary_selections()
+            .iter()
+            .filter_map(|selection| {
+                let pos = selection.pos();
+                match editor_line {
+                    EditorLine::Paragraph(line_index) => {
+                        let row = self.char_to_line(self.utf16_cu_to_char(pos));
+                        (row == line_index)
+                            .then(|| pos - self.char_to_utf16_cu(self.line_to_char(row)))
+                    }
+                    EditorLine::SingleParagraph => Some(pos),
+                }
+            })
+            .collect()
+    }
+
+    // Return the visible text of the given selection for the given editor line
+    fn get_visible_selection_of(
+        &self,
+        selection: &TextSelection,
+        editor_line: EditorLine,
+    ) -> Option<(usize, usize)> {
+        let (selected_from, selected_to) = match selection {
             TextSelection::Cursor(_) => return None,
             TextSelection::Range { from, to } => (*from, *to),
         };
@@ -374,7 +523,7 @@ pub trait TextEditor {
         selection
     }
 
-    // Process a Keyboard event
+    // Process a Keyboard event in all the selections
     fn process_key(
         &mut self,
         key: &Key,
@@ -382,6 +531,150 @@ pub trait TextEditor {
         allow_tabs: bool,
         allow_changes: bool,
         allow_clipboard: bool,
+    ) -> TextEvent {
+        if self.secondary_selections().is_empty() {
+            return self.process_key_for_selection(
+                key,
+                modifiers,
+                allow_tabs,
+                allow_changes,
+                allow_clipboard,
+            );
+        }
+
+        let meta_or_ctrl = if cfg!(target_os = "macos") {
+            modifiers.meta()
+        } else {
+            modifiers.ctrl()
+        };
+
+        match key {
+            // Go back to a single selection
+            Key::Named(NamedKey::Escape) => {
+                self.clear_secondary_selections();
+                TextEvent::SELECTION_CHANGED
+            }
+            Key::Character(character) if meta_or_ctrl => match character.as_str() {
+                // Select all, undo and redo go back to a single selection
+                "a" | "z" | "y" => {
+                    self.clear_secondary_selections();
+                    self.process_key_for_selection(
+                        key,
+                        modifiers,
+                        allow_tabs,
+                        allow_changes,
+                        allow_clipboard,
+                    ) | TextEvent::SELECTION_CHANGED
+                }
+
+                // Copy the selected text of all the selections
+                "c" if allow_clipboard => {
+                    if let Some(selected) = get_selected_texts(self) {
+                        Clipboard::set(selected).ok();
+                    }
+                    TextEvent::empty()
+                }
+
+                // Cut the selected text of all the selections
+                "x" if allow_changes && allow_clipboard => {
+                    if let Some(selected) = get_selected_texts(self) {
+                        Clipboard::set(selected).ok();
+                    }
+                    self.process_selections(|editor| {
+                        if editor.selection().is_range() {
+                            editor.process_key_for_selection(
+                                &Key::Named(NamedKey::Backspace),
+                                modifiers,
+                                allow_tabs,
+                                allow_changes,
+                                allow_clipboard,
+                            )
+                        } else {
+                            TextEvent::empty()
+                        }
+                    })
+                }
+
+                _ => self.process_selections(|editor| {
+                    editor.process_key_for_selection(
+                        key,
+                        modifiers,
+                        allow_tabs,
+                        allow_changes,
+                        allow_clipboard,
+                    )
+                }),
+            },
+            _ => self.process_selections(|editor| {
+                editor.process_key_for_selection(
+                    key,
+                    modifiers,
+                    allow_tabs,
+                    allow_changes,
+                    allow_clipboard,
+                )
+            }),
+        }
+    }
+
+    /// Run the given callback once per selection, as if each one was the main selection.
+    ///
+    /// Selections are processed from the end of the text to the start so the changes made in one selection
+    /// don't move the ones not yet processed, and all the changes are recorded as a single history transaction.
+    /// Consecutive calls are grouped together if recent enough, so multi-cursor typing is undone at once.
+    fn process_selections(&mut self, mut process: impl FnMut(&mut Self) -> TextEvent) -> TextEvent {
+        let mut selections = self
+            .secondary_selections_mut()
+            .map(mem::take)
+            .unwrap_or_default();
+        selections.push(self.selection().clone());
+
+        let mut order = (0..selections.len()).collect::<Vec<_>>();
+        order.sort_by_key(|i| Reverse(selections[*i].start().min(selections[*i].end())));
+
+        let mut event = TextEvent::empty();
+
+        self.editor_history().start_transaction();
+        for (processed, i) in order.iter().enumerate() {
+            *self.selection_mut() = selections[*i].clone();
+
+            let len_before = self.len_utf16_cu();
+            event |= process(self);
+            selections[*i] = self.selection().clone();
+
+            // Already processed selections come after this one so they are moved by its changes
+            let offset = self.len_utf16_cu() as isize - len_before as isize;
+            for j in &order[..processed] {
+                selections[*j].offset(offset);
+            }
+        }
+        self.editor_history().commit_grouped_transaction();
+
+        // Selections that ended up in the same place are merged
+        let main_selection = selections.pop().unwrap();
+        let mut secondary_selections: Vec<TextSelection> = Vec::new();
+        for selection in selections {
+            if selection != main_selection && !secondary_selections.contains(&selection) {
+                secondary_selections.push(selection);
+            }
+        }
+
+        *self.selection_mut() = main_selection;
+        if let Some(selections) = self.secondary_selections_mut() {
+            *selections = secondary_selections;
+        }
+
+        event
+    }
+
+    // Process a Keyboard event in the main selection
+    fn process_key_for_selection(
+        &mut self,
+        key: &Key,
+        modifiers: &Modifiers,
+        allow_tabs: bool,
+        allow_changes: bool,
+        allow_clipboard: bool,
     ) -> TextEvent {
         let mut event = TextEvent::empty();
 
@@ -663,4 +956,146 @@ pub trait TextEditor {
 
         (pos, pos)
     }
+
+    /// Find all the matches of the given query, as ranges of UTF-16 code units.
+    fn find_all(&self, query: &SearchQuery) -> Result<Vec<(usize, usize)>, SearchError> {
+        query.find_matches(&get_text(self))
+    }
+
+    /// Select the first match after the main selection, wrapping around to the start of the text.
+    fn find_next(&mut self, query: &SearchQuery) -> Result<TextEvent, SearchError> {
+        let matches = self.find_all(query)?;
+        let pos = self.selection().start().max(self.selection().end());
+        let next = matches
+            .iter()
+            .find(|(from, _)| *from >= pos)
+            .or(matches.first());
+        Ok(select_match(self, next.copied()))
+    }
+
+    /// Select the last match before the main selection, wrapping around to the end of the text.
+    fn find_previous(&mut self, query: &SearchQuery) -> Result<TextEvent, SearchError> {
+        let matches = self.find_all(query)?;
+        let pos = self.selection().start().min(self.selection().end());
+        let previous = matches
+            .iter()
+            .rev()
+            .find(|(_, to)| *to <= pos)
+            .or(matches.last());
+        Ok(select_match(self, previous.copied()))
+    }
+
+    /// Replace the main selection if it is a match of the given query and select the next match.
+    /// Like [find_next](Self::find_next) when the main selection is not a match.
+    fn replace_next(
+        &mut self,
+        query: &SearchQuery,
+        replacement: &str,
+    ) -> Result<TextEvent, SearchError> {
+        let selection = self.get_selection_range();
+        let replacements = query.find_replacements(&get_text(self), replacement)?;
+        let mut event = TextEvent::empty();
+
+        if let Some(SearchReplacement {
+            range: (from, to),
+            text,
+        }) = replacements
+            .into_iter()
+            .find(|replacement| Some(replacement.range) == selection)
+        {
+            self.clear_secondary_selections();
+            self.editor_history().start_transaction();
+            let inserted_text_len = replace_range(self, from..to, &text);
+            self.editor_history().commit_transaction();
+            *self.selection_mut() = TextSelection::new_cursor(from + inserted_text_len);
+            event.insert(TextEvent::TEXT_CHANGED);
+        }
+
+        Ok(event | self.find_next(query)?)
+    }
+
+    /// Replace all the matches of the given query, as a single history transaction.
+    /// The cursor is left at the end of the first replacement.
+    fn replace_all(
+        &mut self,
+        query: &SearchQuery,
+        replacement: &str,
+    ) -> Result<TextEvent, SearchError> {
+        let replacements = query.find_replacements(&get_text(self), replacement)?;
+        let Some(first_replacement) = replacements.first() else {
+            return Ok(TextEvent::empty());
+        };
+        let first_from = first_replacement.range.0;
+
+        self.clear_secondary_selections();
+        self.editor_history().start_transaction();
+        // Replace from the end so the ranges not yet replaced are not moved
+        let mut inserted_text_len = 0;
+        for SearchReplacement {
+            range: (from, to),
+            text,
+        } in replacements.iter().rev()
+        {
+            inserted_text_len = replace_range(self, *from..*to, text);
+        }
+        self.editor_history().commit_transaction();
+        *self.selection_mut() = TextSelection::new_cursor(first_from + inserted_text_len);
+
+        Ok(TextEvent::TEXT_CHANGED | TextEvent::CURSOR_CHANGED | TextEvent::SELECTION_CHANGED)
+    }
+}
+
+/// Get the whole text of the editor.
+fn get_text<T: TextEditor + ?Sized>(editor: &T) -> String {
+    (0..editor.len_lines())
+        .filter_map(|line_idx| editor.line(line_idx))
+        .map(|line| line.text)
+        .collect()
+}
+
+/// Make the given range, if any, the only selection.
+fn select_match<T: TextEditor + ?Sized>(
+    editor: &mut T,
+    range: Option<(usize, usize)>,
+) -> TextEvent {
+    let Some(range) = range else {
+        return TextEvent::empty();
+    };
+    let selection = TextSelection::new_range(range);
+    if *editor.selection() == selection && editor.secondary_selections().is_empty() {
+        return TextEvent::empty();
+    }
+    editor.clear_secondary_selections();
+    *editor.selection_mut() = selection;
+    TextEvent::CURSOR_CHANGED | TextEvent::SELECTION_CHANGED
+}
+
+/// Replace the given range with the given text, returning the length of the inserted text.
+fn replace_range<T: TextEditor + ?Sized>(editor: &mut T, range: Range<usize>, text: &str) -> usize {
+    let from = range.start;
+    editor.remove(range);
+    if text.is_empty() {
+        0
+    } else {
+        editor.insert(text, from)
+    }
+}
+
+/// Get the selected text of all the selections, in order and separated by line breaks.
+fn get_selected_texts<T: TextEditor + ?Sized>(editor: &mut T) -> Option<String> {
+    let main_selection = editor.selection().clone();
+    let mut selections = iter::once(main_selection.clone())
+        .chain(editor.secondary_selections().iter().cloned())
+        .filter(TextSelection::is_range)
+        .collect::<Vec<_>>();
+    selections.sort_by_key(|selection| selection.start().min(selection.end()));
+
+    let mut texts = Vec::new();
+    for selection in selections {
+        *editor.selection_mut() = selection;
+        texts.extend(editor.get_selected_text());
+    }
+    *editor.selection_mut() = main_selection;
+
+    (!texts.is_empty()).then(|| texts.join("\n"))
 }
@@ -2008,13 +2008,36 @@ pub enum BlurStyle {
 }
 
 pub mod svg {
+    use std::ops::Deref;
+
     use super::{
-        Canvas,
         Color,
+        Data,
         LocalResourceProvider,
+        Rect,
         Size,
     };
 
+    pub struct Canvas;
+
+    impl Canvas {
+        pub fn new(_bounds: impl AsRef<Rect>, _flags: impl Into<Option<u32>>) -> Canvas {
+            unimplemented!("This is mocked")
+        }
+
+        pub fn end(self) -> Data {
+            unimplemented!("This is mocked")
+        }
+    }
+
+    impl Deref for Canvas {
+        type Target = super::Canvas;
+
+        fn deref(&self) -> &Self::Target {
+            unimplemented!("This is mocked")
+        }
+    }
+
     pub enum LengthUnit {
         Percentage,
         PX,
@@ -2093,7 +2116,7 @@ pub mod svg {
             unimplemented!("This is mocked")
         }
 
-        pub fn render(&self, _canvas: &Canvas) {
+        pub fn render(&self, _canvas: &super::Canvas) {
             unimplemented!("This is mocked")
         }
 
@@ -2103,6 +2126,66 @@ pub mod svg {
     }
 }
 
+pub mod pdf {
+    use std::io;
+
+    use super::Document;
+
+    #[derive(Default)]
+    pub struct Metadata<'a> {
+        pub title: String,
+        pub author: String,
+        pub subject: String,
+        pub keywords: String,
+        pub creator: String,
+        pub producer: String,
+        _marker: std::marker::PhantomData<&'a ()>,
+    }
+
+    pub fn new_document<'a>(
+        _writer: &'a mut impl io::Write,
+        _metadata: Option<&'a Metadata<'a>>,
+    ) -> Document<'a> {
+        unimplemented!("This is mocked")
+    }
+}
+
+pub mod document {
+    pub mod state {
+        pub struct Open;
+
+        pub struct OnPage;
+    }
+}
+
+pub struct Document<'a, State = document::state::Open> {
+    _marker: std::marker::PhantomData<(&'a (), State)>,
+}
+
+impl<'a> Document<'a, document::state::Open> {
+    pub fn begin_page(
+        self,
+        _size: impl Into<Size>,
+        _content: Option<&Rect>,
+    ) -> Document<'a, document::state::OnPage> {
+        unimplemented!("This is mocked")
+    }
+
+    pub fn close(self) {
+        unimplemented!("This is mocked")
+    }
+}
+
+impl<'a> Document<'a, document::state::OnPage> {
+    pub fn canvas(&mut self) -> &Canvas {
+        unimplemented!("This is mocked")
+    }
+
+    pub fn end_page(self) -> Document<'a> {
+        unimplemented!("This is mocked")
+    }
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
 pub struct Size;
@@ -2163,6 +2246,24 @@ impl Surface {
     pub fn new_surface_with_dimensions(&mut self, dim: impl Into<ISize>) -> Option<Self> {
         unimplemented!("This is mocked")
     }
+
+    pub fn read_pixels(
+        &mut self,
+        dst_info: &ImageInfo,
+        dst_pixels: &mut [u8],
+        dst_row_bytes: usize,
+        src: impl Into<IPoint>,
+    ) -> bool {
+        unimplemented!("This is mocked")
+    }
+}
+
+pub struct IPoint;
+
+impl From<(i32, i32)> for IPoint {
+    fn from(source: (i32, i32)) -> Self {
+        unimplemented!("This is mocked")
+    }
 }
 
 pub struct ISize;
@@ -8,6 +8,8 @@ pub use skia_safe::gpu::gl::{
 pub use skia_safe::gpu::mtl;
 #[cfg(any(target_os = "linux", target_os = "windows"))]
 pub use skia_safe::gpu::vk;
+#[cfg(feature = "pdf")]
+pub use skia_safe::pdf;
 pub use skia_safe::{
     AlphaType,
     Bitmap,
@@ -21,6 +23,7 @@ pub use skia_safe::{
     ColorType,
     CubicResampler,
     Data,
+    Document,
     EncodedImageFormat,
     FilterMode,
     Font,
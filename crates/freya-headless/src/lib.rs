@@ -0,0 +1,360 @@
+//! Headless rendering for Freya applications.
+//!
+//! Render the same components used in the desktop apps into PNG, SVG or PDF files without a window,
+//! e.g. for exporting reports from a server.
+//!
+//! Use [HeadlessRenderer] to mount an app, wait for it to settle and export it.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use freya::prelude::*;
+//! use freya_headless::HeadlessRenderer;
+//!
+//! fn report() -> impl IntoElement {
+//!     rect().expanded().center().child("Monthly Report")
+//! }
+//!
+//! fn main() {
+//!     let mut renderer = HeadlessRenderer::new(report, (600., 800.).into()).with_scale_factor(2.);
+//!     // Wait for the images, fonts and layout
+//!     renderer.settle(std::time::Duration::from_secs(5));
+//!     renderer.save_png("report.png").unwrap();
+//!     renderer.save_svg("report.svg").unwrap();
+//! }
+//! ```
+//!
+//! PDF documents require the `pdf` feature.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs,
+    path::Path,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use freya_core::{
+    integration::*,
+    prelude::*,
+};
+#[cfg(feature = "pdf")]
+use freya_engine::prelude::pdf;
+use freya_engine::prelude::{
+    EncodedImageFormat,
+    SkRect,
+    raster_n32_premul,
+    svg,
+};
+use torin::prelude::Size2D;
+
+mod offscreen;
+
+#[doc(hidden)]
+pub use offscreen::OffscreenRunner;
+
+/// How often [HeadlessRenderer::settle] polls the app.
+const SETTLE_STEP: Duration = Duration::from_millis(10);
+
+/// How many consecutive polls without changes are needed to consider the app settled.
+const SETTLE_IDLE_POLLS: u32 = 5;
+
+#[derive(Debug, thiserror::Error)]
+pub enum HeadlessError {
+    #[error("Failed to create a surface of {0}x{1}")]
+    Surface(i32, i32),
+    #[error("Failed to encode the image")]
+    Encode,
+    #[error("Failed to load the font {0}")]
+    Font(String),
+    #[error("Failed to write the file: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Render an app without a window.
+///
+/// The app is rendered at its size multiplied by the scale factor, so a `600x800` app with a scale factor of `2`
+/// produces images of `1200x1600` pixels.
+pub struct HeadlessRenderer {
+    offscreen_runner: OffscreenRunner,
+    size: Size2D,
+    background: Color,
+}
+
+impl HeadlessRenderer {
+    pub fn new(app: impl Into<AppComponent>, size: Size2D) -> Self {
+        Self::new_with_hook(app, size, |_| {}).0
+    }
+
+    /// Like [HeadlessRenderer::new] but with a `hook` to access the [Runner] before the app is mounted,
+    /// e.g. to provide root contexts.
+    pub fn new_with_hook<T>(
+        app: impl Into<AppComponent>,
+        size: Size2D,
+        hook: impl FnOnce(&mut Runner) -> T,
+    ) -> (Self, T) {
+        // There is no clipboard when rendering headlessly
+        let (offscreen_runner, hook_result) = OffscreenRunner::new(app, size, 1.0, || None, hook);
+
+        let renderer = Self {
+            offscreen_runner,
+            size,
+            background: Color::WHITE,
+        };
+
+        (renderer, hook_result)
+    }
+
+    pub fn with_scale_factor(mut self, scale_factor: f64) -> Self {
+        self.set_scale_factor(scale_factor);
+        self
+    }
+
+    pub fn with_background(mut self, background: impl Into<Color>) -> Self {
+        self.background = background.into();
+        self
+    }
+
+    /// Change the scale factor, remeasuring the layout.
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.offscreen_runner.scale_factor = scale_factor;
+        self.remeasure();
+    }
+
+    /// Change the size of the app, remeasuring the layout.
+    pub fn set_size(&mut self, size: Size2D) {
+        self.size = size;
+        self.remeasure();
+    }
+
+    /// Register custom fonts by name.
+    pub fn set_fonts(&mut self, fonts: HashMap<&str, &[u8]>) -> Result<(), HeadlessError> {
+        self.offscreen_runner.set_fonts(fonts)?;
+        self.remeasure();
+        Ok(())
+    }
+
+    /// Change the fonts used when an element does not specify any.
+    pub fn set_default_fonts(&mut self, fonts: &[Cow<'static, str>]) {
+        self.offscreen_runner.default_fonts.clear();
+        self.offscreen_runner.default_fonts.extend_from_slice(fonts);
+        self.remeasure();
+    }
+
+    fn remeasure(&mut self) {
+        self.offscreen_runner.size = self.physical_size();
+        self.offscreen_runner.remeasure();
+        self.offscreen_runner.sync_and_update();
+    }
+
+    /// Poll the app until it settles, meaning it has no more changes to apply and no assets (e.g images) being fetched,
+    /// or until the `timeout` is reached. Animations are run to completion.
+    ///
+    /// Returns `false` if the app did not settle within the `timeout`.
+    pub fn settle(&mut self, timeout: Duration) -> bool {
+        let started = Instant::now();
+        let mut settle_tracker = SettleTracker::default();
+        loop {
+            let offscreen_runner = &mut self.offscreen_runner;
+            offscreen_runner.runner.handle_events_immediately();
+            let changed = offscreen_runner.sync_and_update();
+            let loading = offscreen_runner
+                .runner
+                .run_in(|| offscreen_runner.asset_cacher.is_loading());
+
+            if settle_tracker.poll(changed || loading) {
+                return true;
+            }
+
+            if started.elapsed() >= timeout {
+                return false;
+            }
+
+            std::thread::sleep(SETTLE_STEP);
+            offscreen_runner
+                .ticker_sender
+                .broadcast_blocking(())
+                .unwrap();
+        }
+    }
+
+    /// Size in pixels of the rendered app.
+    fn physical_size(&self) -> Size2D {
+        self.size * self.offscreen_runner.scale_factor as f32
+    }
+
+    /// Render the app as a PNG image.
+    pub fn render_png(&mut self) -> Result<Vec<u8>, HeadlessError> {
+        let size = self.physical_size().ceil();
+        let (width, height) = (size.width as i32, size.height as i32);
+        let mut surface =
+            raster_n32_premul((width, height)).ok_or(HeadlessError::Surface(width, height))?;
+
+        self.offscreen_runner
+            .render(surface.canvas(), self.background);
+
+        let image = surface.image_snapshot();
+        let mut context = surface.direct_context();
+        let data = image
+            .encode(context.as_mut(), EncodedImageFormat::PNG, None)
+            .ok_or(HeadlessError::Encode)?;
+        Ok(data.to_vec())
+    }
+
+    /// Render the app as an SVG document.
+    pub fn render_svg(&mut self) -> Vec<u8> {
+        let size = self.physical_size();
+        let canvas = svg::Canvas::new(SkRect::new(0., 0., size.width, size.height), None);
+
+        self.offscreen_runner.render(&canvas, self.background);
+
+        canvas.end().to_vec()
+    }
+
+    /// Render the app as a PDF document.
+    ///
+    /// Content taller than the app is split across multiple pages, each one of the size of the app.
+    #[cfg(feature = "pdf")]
+    pub fn render_pdf(&mut self) -> Vec<u8> {
+        let size = self.physical_size();
+        let pages = page_count(self.content_height(), size.height);
+
+        let mut bytes = Vec::new();
+        let mut document = pdf::new_document(&mut bytes, None);
+        for page in 0..pages {
+            let mut page_document = document.begin_page((size.width, size.height), None);
+            let canvas = page_document.canvas();
+            canvas.translate((0., -(page as f32 * size.height)));
+            self.offscreen_runner.render(canvas, self.background);
+            document = page_document.end_page();
+        }
+        document.close();
+
+        bytes
+    }
+
+    /// Height in pixels of the whole content, which might overflow the app.
+    #[cfg(feature = "pdf")]
+    fn content_height(&self) -> f32 {
+        let tree = self.offscreen_runner.tree.borrow();
+        let mut height = 0f32;
+        tree.traverse_depth(|id| {
+            if let Some(layout_node) = tree.layout.get(&id) {
+                height = height.max(layout_node.area.max_y());
+            }
+        });
+        height
+    }
+
+    /// Render the app as a PNG image into the given file.
+    pub fn save_png(&mut self, path: impl AsRef<Path>) -> Result<(), HeadlessError> {
+        let data = self.render_png()?;
+        fs::write(path, data)?;
+        Ok(())
+    }
+
+    /// Render the app as an SVG document into the given file.
+    pub fn save_svg(&mut self, path: impl AsRef<Path>) -> Result<(), HeadlessError> {
+        let data = self.render_svg();
+        fs::write(path, data)?;
+        Ok(())
+    }
+
+    /// Render the app as a PDF document into the given file, see [HeadlessRenderer::render_pdf].
+    #[cfg(feature = "pdf")]
+    pub fn save_pdf(&mut self, path: impl AsRef<Path>) -> Result<(), HeadlessError> {
+        let data = self.render_pdf();
+        fs::write(path, data)?;
+        Ok(())
+    }
+}
+
+/// Counts the consecutive polls of [HeadlessRenderer::settle] without changes.
+#[derive(Default)]
+struct SettleTracker {
+    idle_polls: u32,
+}
+
+impl SettleTracker {
+    /// Track a poll, returning whether the app has settled.
+    fn poll(&mut self, busy: bool) -> bool {
+        if busy {
+            self.idle_polls = 0;
+        } else {
+            self.idle_polls += 1;
+        }
+        self.idle_polls >= SETTLE_IDLE_POLLS
+    }
+}
+
+/// How many pages of `page_height` are needed to fit the content, at least one.
+#[cfg(feature = "pdf")]
+fn page_count(content_height: f32, page_height: f32) -> usize {
+    (content_height / page_height).ceil().max(1.) as usize
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn settle_tracker() {
+        let mut settle_tracker = SettleTracker::default();
+        for _ in 1..SETTLE_IDLE_POLLS {
+            assert!(!settle_tracker.poll(false));
+        }
+
+        // Any change or loading asset starts counting again
+        assert!(!settle_tracker.poll(true));
+        for _ in 1..SETTLE_IDLE_POLLS {
+            assert!(!settle_tracker.poll(false));
+        }
+        assert!(settle_tracker.poll(false));
+    }
+
+    fn app() -> impl IntoElement {
+        rect().expanded().background((255, 0, 0))
+    }
+
+    #[test]
+    fn render_png_output() {
+        let mut renderer = HeadlessRenderer::new(app, (300., 200.).into()).with_scale_factor(2.);
+        let data = renderer.render_png().unwrap();
+
+        assert!(data.starts_with(b"\x89PNG\r\n\x1a\n"));
+        // The IHDR chunk holds the size in physical pixels
+        assert_eq!(&data[16..20], 600u32.to_be_bytes());
+        assert_eq!(&data[20..24], 400u32.to_be_bytes());
+    }
+
+    #[test]
+    fn render_svg_output() {
+        let mut renderer = HeadlessRenderer::new(app, (300., 200.).into());
+        let data = String::from_utf8(renderer.render_svg()).unwrap();
+
+        assert!(data.contains("<svg"));
+        assert!(data.trim_end().ends_with("</svg>"));
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn render_pdf_output() {
+        let mut renderer = HeadlessRenderer::new(app, (300., 200.).into());
+        let data = renderer.render_pdf();
+
+        assert!(data.starts_with(b"%PDF-"));
+        assert!(data.trim_ascii_end().ends_with(b"%%EOF"));
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn page_count_paging() {
+        assert_eq!(page_count(0., 800.), 1);
+        assert_eq!(page_count(800., 800.), 1);
+        assert_eq!(page_count(800.5, 800.), 2);
+        assert_eq!(page_count(2500., 800.), 4);
+    }
+}
@@ -0,0 +1,253 @@
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+};
+
+use freya_clipboard::copypasta::ClipboardProvider;
+use freya_components::{
+    cache::AssetCacher,
+    integration::integration,
+};
+use freya_core::{
+    integration::*,
+    prelude::*,
+};
+use freya_engine::prelude::{
+    Canvas,
+    FontCollection,
+    FontMgr,
+    TypefaceFontProvider,
+};
+use ragnarok::{
+    EventsExecutorRunner,
+    NodesState,
+};
+use torin::prelude::Size2D;
+
+use crate::HeadlessError;
+
+/// Mounts an app and keeps its tree, layout and accessibility up to date without a window.
+///
+/// This is the core of the [HeadlessRenderer](crate::HeadlessRenderer) and of the `TestingRunner` of `freya-testing`.
+/// Its internals are not considered a stable API.
+#[doc(hidden)]
+pub struct OffscreenRunner {
+    pub runner: Runner,
+    pub tree: Rc<RefCell<Tree>>,
+    pub nodes_state: NodesState<NodeId>,
+    pub accessibility: AccessibilityTree,
+
+    pub events_receiver: futures_channel::mpsc::UnboundedReceiver<EventsChunk>,
+    pub events_sender: futures_channel::mpsc::UnboundedSender<EventsChunk>,
+
+    pub font_manager: FontMgr,
+    pub font_collection: FontCollection,
+
+    pub platform: Platform,
+    pub asset_cacher: AssetCacher,
+
+    pub animation_clock: AnimationClock,
+    pub ticker_sender: RenderingTickerSender,
+
+    pub default_fonts: Vec<Cow<'static, str>>,
+    /// Size of the layout, in pixels.
+    pub size: Size2D,
+    pub scale_factor: f64,
+}
+
+impl OffscreenRunner {
+    /// Mount the app with the given `clipboard`, if any, and a `hook` to access the [Runner] before the app is mounted,
+    /// e.g. to provide root contexts.
+    pub fn new<T>(
+        app: impl Into<AppComponent>,
+        size: Size2D,
+        scale_factor: f64,
+        clipboard: impl FnOnce() -> Option<Box<dyn ClipboardProvider>>,
+        hook: impl FnOnce(&mut Runner) -> T,
+    ) -> (Self, T) {
+        let (events_sender, events_receiver) = futures_channel::mpsc::unbounded();
+        let app = app.into();
+        let mut runner = Runner::new(move || integration(app.clone()).into_element());
+
+        runner.provide_root_context(ScreenReader::new);
+
+        let (mut ticker_sender, ticker) = RenderingTicker::new();
+        ticker_sender.set_overflow(true);
+        runner.provide_root_context(|| ticker);
+
+        let animation_clock = runner.provide_root_context(AnimationClock::new);
+
+        let asset_cacher = runner.provide_root_context(AssetCacher::create);
+
+        let tree = Tree::default();
+        let tree = Rc::new(RefCell::new(tree));
+
+        let platform = runner.provide_root_context({
+            let tree = tree.clone();
+            || Platform {
+                focused_accessibility_id: State::create(ACCESSIBILITY_ROOT_ID),
+                focused_accessibility_node: State::create(accesskit::Node::new(
+                    accesskit::Role::Window,
+                )),
+                root_size: State::create(size),
+                navigation_mode: State::create(NavigationMode::NotKeyboard),
+                preferred_theme: State::create(PreferredTheme::Light),
+                sender: Rc::new(move |user_event| {
+                    // There is no window to redraw or to set the cursor icon of
+                    if let UserEvent::FocusAccessibilityNode(strategy) = user_event {
+                        tree.borrow_mut().accessibility_diff.request_focus(strategy);
+                    }
+                }),
+            }
+        });
+
+        runner.provide_root_context(|| State::create(clipboard()));
+
+        runner.provide_root_context(|| tree.borrow().accessibility_generator.clone());
+
+        let hook_result = hook(&mut runner);
+
+        let mut font_collection = FontCollection::new();
+        let def_mgr = FontMgr::default();
+        let provider = TypefaceFontProvider::new();
+        let font_manager: FontMgr = provider.into();
+        font_collection.set_default_font_manager(def_mgr, None);
+        font_collection.set_dynamic_font_manager(font_manager.clone());
+        font_collection.paragraph_cache_mut().turn_on(false);
+
+        runner.provide_root_context(|| font_collection.clone());
+
+        let mut offscreen_runner = Self {
+            runner,
+            tree,
+            nodes_state: NodesState::default(),
+            accessibility: AccessibilityTree::default(),
+
+            events_receiver,
+            events_sender,
+
+            font_manager,
+            font_collection,
+
+            platform,
+            asset_cacher,
+
+            animation_clock,
+            ticker_sender,
+
+            default_fonts: default_fonts(),
+            size,
+            scale_factor,
+        };
+
+        offscreen_runner.sync_and_update();
+
+        (offscreen_runner, hook_result)
+    }
+
+    /// Register custom fonts by name.
+    ///
+    /// The layout is not remeasured, see [OffscreenRunner::remeasure].
+    pub fn set_fonts(&mut self, fonts: HashMap<&str, &[u8]>) -> Result<(), HeadlessError> {
+        let mut provider = TypefaceFontProvider::new();
+        for (font_name, font_data) in fonts {
+            let ft_type = self
+                .font_collection
+                .fallback_manager()
+                .and_then(|font_manager| font_manager.new_from_data(font_data, None))
+                .ok_or_else(|| HeadlessError::Font(font_name.to_string()))?;
+            provider.register_typeface(ft_type, Some(font_name));
+        }
+        let font_manager: FontMgr = provider.into();
+        self.font_manager = font_manager.clone();
+        self.font_collection.set_dynamic_font_manager(font_manager);
+        Ok(())
+    }
+
+    /// Measure the whole layout again, e.g. after changing the size or the fonts.
+    pub fn remeasure(&mut self) {
+        self.platform.root_size.set_if_modified(self.size);
+        self.tree.borrow_mut().layout.reset();
+        self.tree.borrow_mut().text_cache.reset();
+        self.measure_layout();
+    }
+
+    fn measure_layout(&mut self) {
+        self.tree.borrow_mut().measure_layout(
+            self.size,
+            &mut self.font_collection,
+            &self.font_manager,
+            &self.events_sender,
+            self.scale_factor,
+            &self.default_fonts,
+        );
+    }
+
+    /// Apply the pending events and changes of the app, returning whether there was any.
+    pub fn sync_and_update(&mut self) -> bool {
+        let mut changed = false;
+
+        while let Ok(events_chunk) = self.events_receiver.try_recv() {
+            changed = true;
+            match events_chunk {
+                EventsChunk::Processed(processed_events) => {
+                    let events_executor_adapter = EventsExecutorAdapter {
+                        runner: &mut self.runner,
+                    };
+                    events_executor_adapter.run(&mut self.nodes_state, processed_events);
+                }
+                EventsChunk::Batch(events) => {
+                    for event in events {
+                        self.runner.handle_event(
+                            event.node_id,
+                            event.name,
+                            event.data,
+                            event.bubbles,
+                        );
+                    }
+                }
+            }
+        }
+
+        let mutations = self.runner.sync_and_update();
+        changed |= !mutations.added.is_empty()
+            || !mutations.modified.is_empty()
+            || !mutations.removed.is_empty()
+            || !mutations.moved.is_empty();
+        self.runner.run_in(|| {
+            self.tree.borrow_mut().apply_mutations(mutations);
+        });
+        self.measure_layout();
+
+        let accessibility_update = self
+            .accessibility
+            .process_updates(&mut self.tree.borrow_mut(), &self.events_sender);
+
+        self.platform
+            .focused_accessibility_id
+            .set_if_modified(accessibility_update.focus);
+        let node_id = self.accessibility.focused_node_id().unwrap();
+        let tree = self.tree.borrow();
+        let layout_node = tree.layout.get(&node_id).unwrap();
+        self.platform
+            .focused_accessibility_node
+            .set_if_modified(AccessibilityTree::create_node(node_id, layout_node, &tree));
+
+        changed
+    }
+
+    /// Render the app into the given canvas.
+    pub fn render(&mut self, canvas: &Canvas, background: Color) {
+        let render_pipeline = RenderPipeline {
+            font_collection: &mut self.font_collection,
+            font_manager: &self.font_manager,
+            tree: &self.tree.borrow(),
+            canvas,
+            scale_factor: self.scale_factor,
+            background,
+        };
+        render_pipeline.render();
+    }
+}
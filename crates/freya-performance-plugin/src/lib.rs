@@ -58,6 +58,9 @@ struct WindowMetrics {
 
     started_layout: Option<Instant>,
     finished_layout: Option<Duration>,
+    layout_dirty_nodes: usize,
+    layout_measured_nodes: usize,
+    layout_cached_nodes: usize,
 
     started_tree_updates: Option<Instant>,
     finished_tree_updates: Option<Duration>,
@@ -136,9 +139,13 @@ impl FreyaPlugin for PerformanceOverlayPlugin {
             PluginEvent::StartedMeasuringLayout { window, .. } => {
                 self.get_metrics(window.id()).started_layout = Some(Instant::now())
             }
-            PluginEvent::FinishedMeasuringLayout { window, .. } => {
+            PluginEvent::FinishedMeasuringLayout { window, tree } => {
+                let stats = tree.layout_stats();
                 let metrics = self.get_metrics(window.id());
-                metrics.finished_layout = Some(metrics.started_layout.unwrap().elapsed())
+                metrics.finished_layout = Some(metrics.started_layout.unwrap().elapsed());
+                metrics.layout_dirty_nodes = stats.dirty.len();
+                metrics.layout_measured_nodes = stats.measured_nodes;
+                metrics.layout_cached_nodes = stats.cached_nodes;
             }
             PluginEvent::StartedUpdatingTree { window, .. } => {
                 self.get_metrics(window.id()).started_tree_updates = Some(Instant::now())
@@ -271,6 +278,18 @@ impl FreyaPlugin for PerformanceOverlayPlugin {
                     14.0,
                 );
 
+                // Last layout measurement
+                add_text(
+                    &mut paragraph_builder,
+                    format!(
+                        "{} Dirty, {} Measured, {} Cached \n",
+                        metrics.layout_dirty_nodes,
+                        metrics.layout_measured_nodes,
+                        metrics.layout_cached_nodes
+                    ),
+                    14.0,
+                );
+
                 // Scale Factor
                 add_text(
                     &mut paragraph_builder,
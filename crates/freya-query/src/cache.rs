@@ -0,0 +1,315 @@
+//! Storage, in-flight runs and hook machinery shared by [use_query](crate::query::use_query)
+//! and [use_infinite_query](crate::infinite_query::use_infinite_query).
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    mem,
+    rc::Rc,
+    time::Duration,
+};
+
+use async_io::Timer;
+use freya_core::{
+    integration::FxHashSet,
+    lifecycle::context::{
+        provide_context_for_scope_id,
+        try_consume_context,
+    },
+    prelude::*,
+    scope_id::ScopeId,
+};
+use futures_util::future::{
+    AbortHandle,
+    FutureExt,
+    LocalBoxFuture,
+    Shared,
+    abortable,
+};
+
+/// Key of a cached entry, e.g. [Query](crate::query::Query).
+pub(crate) trait CacheKey: 'static + Clone + PartialEq + Eq + Hash {
+    /// Whether the entry runs automatically when subscribed to.
+    fn enabled(&self) -> bool;
+
+    /// For how long the entry is kept after there are no more subscribers.
+    fn clean_time(&self) -> Duration;
+}
+
+/// Data of a cached entry, shared by all its subscribers.
+pub(crate) trait CacheData: 'static + Clone {
+    type Key: CacheKey;
+
+    fn new(key: &Self::Key) -> Self;
+
+    fn entry(&self) -> &CacheEntry;
+
+    /// Whether the data is outdated and should run again.
+    fn is_stale(&self, key: &Self::Key) -> bool;
+
+    /// Run in the background, unless it is running already so concurrent runs are deduplicated.
+    fn run(&self, key: &Self::Key);
+
+    /// Cancel the in-flight run, if any.
+    fn cancel(&self);
+
+    /// Called every time a subscriber starts using the entry with the given key.
+    fn on_subscribe(&self, _key: &Self::Key) {}
+
+    /// Called every time a subscriber stops using the entry.
+    fn on_unsubscribe(&self) {}
+}
+
+/// Subscribers of a cached entry.
+#[derive(Clone, Default)]
+pub(crate) struct CacheEntry {
+    reactive_contexts: Rc<RefCell<FxHashSet<ReactiveContext>>>,
+    clean_task: Rc<RefCell<Option<TaskHandle>>>,
+}
+
+impl CacheEntry {
+    pub(crate) fn notify(&self) {
+        for reactive_context in self.reactive_contexts.borrow().iter() {
+            reactive_context.notify();
+        }
+    }
+
+    /// Subscribe the current reactive context, if any.
+    fn subscribe(&self) {
+        if let Some(mut reactive_context) = ReactiveContext::try_current() {
+            reactive_context.subscribe(&self.reactive_contexts);
+        }
+    }
+
+    pub(crate) fn has_subscribers(&self) -> bool {
+        !self.reactive_contexts.borrow().is_empty()
+    }
+
+    fn cancel_clean_task(&self) {
+        if let Some(clean_task) = self.clean_task.take() {
+            clean_task.cancel();
+        }
+    }
+}
+
+/// Run of a cached entry that has not settled yet.
+///
+/// `T` is kept along the run and given back if it is cancelled, e.g. to restore the previous state.
+pub(crate) struct InFlight<T>(Rc<RefCell<Option<InFlightRun<T>>>>);
+
+struct InFlightRun<T> {
+    future: Shared<LocalBoxFuture<'static, ()>>,
+    abort_handle: AbortHandle,
+    context: T,
+}
+
+impl<T> Clone for InFlight<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Default for InFlight<T> {
+    fn default() -> Self {
+        Self(Rc::default())
+    }
+}
+
+impl<T: 'static> InFlight<T> {
+    pub(crate) fn is_running(&self) -> bool {
+        self.0.borrow().is_some()
+    }
+
+    /// Run the future in the background and settle its output, unless the run gets cancelled before.
+    pub(crate) fn start<R: 'static>(
+        &self,
+        context: T,
+        future: impl Future<Output = R> + 'static,
+        settle: impl FnOnce(R) + 'static,
+    ) {
+        let (future, abort_handle) = abortable({
+            let in_flight = self.clone();
+            async move {
+                let res = future.await;
+                in_flight.0.take();
+                settle(res);
+            }
+        });
+        let future = future.map(|_| ()).boxed_local().shared();
+        spawn_forever(future.clone());
+        *self.0.borrow_mut() = Some(InFlightRun {
+            future,
+            abort_handle,
+            context,
+        });
+    }
+
+    /// Cancel the run, if any, giving back its context.
+    pub(crate) fn cancel(&self) -> Option<T> {
+        let in_flight = self.0.take()?;
+        in_flight.abort_handle.abort();
+        Some(in_flight.context)
+    }
+
+    /// Wait for the run to settle, if any.
+    ///
+    /// Keeps waiting if the run gets restarted in the meantime.
+    pub(crate) async fn settled(&self) {
+        loop {
+            let future = self
+                .0
+                .borrow()
+                .as_ref()
+                .map(|in_flight| in_flight.future.clone());
+            let Some(future) = future else {
+                break;
+            };
+            future.await;
+        }
+    }
+}
+
+/// Cached entries of a certain type, kept in the root scope.
+pub(crate) struct CacheStorage<D: CacheData> {
+    entries: State<HashMap<D::Key, D>>,
+}
+
+impl<D: CacheData> Copy for CacheStorage<D> {}
+
+impl<D: CacheData> Clone for CacheStorage<D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D: CacheData> CacheStorage<D> {
+    pub(crate) fn new_in_root() -> Self {
+        Self {
+            entries: State::create_global(HashMap::default()),
+        }
+    }
+
+    /// Get the data of the given key, creating it if necessary, without subscribing to it.
+    pub(crate) fn get_or_insert(&self, key: &D::Key) -> D {
+        self.entries
+            .write_unchecked()
+            .entry(key.clone())
+            .or_insert_with(|| D::new(key))
+            .clone()
+    }
+
+    /// Get the data of the given key, creating it if necessary, for a new subscriber.
+    fn insert_or_subscribe(&self, key: &D::Key) -> D {
+        let data = self.get_or_insert(key);
+        data.entry().cancel_clean_task();
+        data.on_subscribe(key);
+        data
+    }
+
+    /// Get the data of an existing key.
+    ///
+    /// This **will** automatically subscribe.
+    pub(crate) fn read(&self, key: &D::Key) -> D {
+        let data = self.peek(key);
+        data.entry().subscribe();
+        data
+    }
+
+    /// Get the data of an existing key.
+    ///
+    /// This **will not** automatically subscribe.
+    pub(crate) fn peek(&self, key: &D::Key) -> D {
+        self.entries.peek().get(key).cloned().unwrap()
+    }
+
+    /// Get the keys and data of the entries whose key matches.
+    pub(crate) fn matching(&self, matches: impl Fn(&D::Key) -> bool) -> Vec<(D::Key, D)> {
+        self.entries
+            .peek()
+            .iter()
+            .filter(|(key, _)| matches(key))
+            .map(|(key, data)| (key.clone(), data.clone()))
+            .collect()
+    }
+
+    /// A subscriber stopped using the entry of the given key.
+    fn unsubscribe(&self, key: D::Key) {
+        let data = self.peek(&key);
+        data.on_unsubscribe();
+
+        // Cancel the run and spawn clean up task if there no more reactive contexts
+        if data.entry().reactive_contexts.borrow().len() == 1 {
+            data.cancel();
+            self.schedule_clean(key, &data);
+        }
+    }
+
+    /// Remove the entry once its clean time has passed, unless it gets subscribed to again.
+    pub(crate) fn schedule_clean(&self, key: D::Key, data: &D) {
+        let storage = *self;
+        *data.entry().clean_task.borrow_mut() = Some(spawn_forever(async move {
+            // Wait as long as the clean time is configured
+            Timer::after(key.clean_time()).await;
+
+            // Finally clear the entry
+            storage.entries.write_unchecked().remove(&key);
+        }));
+    }
+}
+
+/// Get the storage of the given type from the root scope, creating it if necessary.
+pub(crate) fn root_storage<S: Clone + 'static>(new_in_root: impl FnOnce() -> S) -> S {
+    match try_consume_context::<S>() {
+        Some(storage) => storage,
+        None => {
+            provide_context_for_scope_id(new_in_root(), Some(ScopeId::ROOT));
+            try_consume_context::<S>().unwrap()
+        }
+    }
+}
+
+/// Subscribe the current component to the entry of the given key, which might change between renders.
+///
+/// Returns the current key.
+pub(crate) fn use_cache_entry<D: CacheData>(
+    storage: CacheStorage<D>,
+    key: D::Key,
+) -> State<D::Key> {
+    let make_entry = |key: &D::Key, prev_key: Option<D::Key>| {
+        let data = storage.insert_or_subscribe(key);
+
+        // Update the previous entry if there has been a change in the key
+        if let Some(prev_key) = prev_key {
+            storage.unsubscribe(prev_key);
+        }
+
+        // Immediately run if enabled and the data is stale
+        if key.enabled() && data.is_stale(key) {
+            data.run(key);
+        }
+    };
+
+    let mut current_key = use_hook(|| {
+        make_entry(&key, None);
+        State::create(key.clone())
+    });
+
+    if *current_key.read() != key {
+        let prev = mem::replace(&mut *current_key.write(), key.clone());
+        make_entry(&key, Some(prev));
+    }
+
+    // Update the entry when the scope is dropped
+    use_drop(move || {
+        storage.unsubscribe(current_key.peek().clone());
+    });
+
+    // Used to consider this hook call as a subscriber without rerunning the component
+    use_side_effect(move || {
+        storage.read(&current_key.peek());
+    });
+
+    current_key
+}
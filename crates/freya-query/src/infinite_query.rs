@@ -0,0 +1,655 @@
+use core::fmt;
+use std::{
+    cell::{
+        Ref,
+        RefCell,
+    },
+    future::Future,
+    hash::Hash,
+    rc::Rc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use freya_core::{
+    lifecycle::context::consume_context,
+    prelude::*,
+};
+use futures_util::future::join_all;
+
+use crate::{
+    cache::{
+        CacheData,
+        CacheEntry,
+        CacheKey,
+        CacheStorage,
+        InFlight,
+        root_storage,
+        use_cache_entry,
+    },
+    persistence::{
+        InfiniteQueryPersistence,
+        PersistenceHandle,
+    },
+    query::RetryPolicy,
+};
+
+pub trait InfiniteQueryCapability
+where
+    Self: 'static + Clone + PartialEq + Hash + Eq,
+{
+    type Page;
+    type Err;
+    type Keys: Hash + PartialEq + Clone;
+    type Cursor: Clone + PartialEq;
+
+    /// Cursor of the first page.
+    fn initial_cursor(&self, keys: &Self::Keys) -> Self::Cursor;
+
+    /// Fetch the page of the given cursor.
+    fn run(
+        &self,
+        keys: &Self::Keys,
+        cursor: &Self::Cursor,
+    ) -> impl Future<Output = Result<Self::Page, Self::Err>>;
+
+    /// Cursor of the page after the given one, `None` if it is the last page.
+    fn next_cursor(&self, page: &InfinitePage<Self>) -> Option<Self::Cursor>;
+
+    /// Cursor of the page before the given one, `None` if it is the first page.
+    ///
+    /// Defaults to `None`, meaning there are never previous pages.
+    fn previous_cursor(&self, _page: &InfinitePage<Self>) -> Option<Self::Cursor> {
+        None
+    }
+
+    /// Implement a custom logic to check if this query should be invalidated or not given a [InfiniteQueryCapability::Keys].
+    fn matches(&self, _keys: &Self::Keys) -> bool {
+        true
+    }
+
+    /// Check if a failed page fetch should be retried given its error, see [InfiniteQuery::retry].
+    fn should_retry(&self, _error: &Self::Err) -> bool {
+        true
+    }
+
+    /// Persist the loaded pages of this query so they survive app restarts, see [use_init_query_persister](crate::persistence::use_init_query_persister).
+    ///
+    /// Defaults to `None`, meaning they are not persisted.
+    fn persistence(&self) -> Option<InfiniteQueryPersistence<Self>> {
+        None
+    }
+}
+
+/// Page fetched by an [InfiniteQueryCapability].
+pub struct InfinitePage<Q: InfiniteQueryCapability> {
+    pub cursor: Q::Cursor,
+    pub data: Q::Page,
+}
+
+impl<Q> fmt::Debug for InfinitePage<Q>
+where
+    Q: InfiniteQueryCapability,
+    Q::Cursor: fmt::Debug,
+    Q::Page: fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InfinitePage")
+            .field("cursor", &self.cursor)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+/// What an infinite query is fetching.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InfiniteFetch {
+    /// The first page, or all the loaded pages again.
+    Refetch,
+    NextPage,
+    PreviousPage,
+}
+
+pub struct InfiniteQueryStateData<Q: InfiniteQueryCapability> {
+    pages: Vec<InfinitePage<Q>>,
+    next_cursor: Option<Q::Cursor>,
+    previous_cursor: Option<Q::Cursor>,
+    /// Error of the last fetch, if it failed.
+    error: Option<Q::Err>,
+    fetching: Option<InfiniteFetch>,
+    settlement_instant: Option<Instant>,
+}
+
+impl<Q> fmt::Debug for InfiniteQueryStateData<Q>
+where
+    Q: InfiniteQueryCapability,
+    Q::Cursor: fmt::Debug,
+    Q::Page: fmt::Debug,
+    Q::Err: fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InfiniteQueryStateData")
+            .field("pages", &self.pages)
+            .field("error", &self.error)
+            .field("fetching", &self.fetching)
+            .finish()
+    }
+}
+
+impl<Q: InfiniteQueryCapability> Default for InfiniteQueryStateData<Q> {
+    fn default() -> Self {
+        Self {
+            pages: Vec::new(),
+            next_cursor: None,
+            previous_cursor: None,
+            error: None,
+            fetching: None,
+            settlement_instant: None,
+        }
+    }
+}
+
+impl<Q: InfiniteQueryCapability> InfiniteQueryStateData<Q> {
+    /// Loaded pages, in order.
+    pub fn pages(&self) -> &[InfinitePage<Q>] {
+        &self.pages
+    }
+
+    /// Iterate over the data of the loaded pages, in order.
+    pub fn pages_data(&self) -> impl Iterator<Item = &Q::Page> {
+        self.pages.iter().map(|page| &page.data)
+    }
+
+    /// Error of the last fetch, if it failed.
+    pub fn error(&self) -> Option<&Q::Err> {
+        self.error.as_ref()
+    }
+
+    /// What is being fetched, if anything.
+    pub fn fetching(&self) -> Option<InfiniteFetch> {
+        self.fetching
+    }
+
+    /// Check if there is nothing loaded yet and nothing being fetched.
+    pub fn is_pending(&self) -> bool {
+        self.settlement_instant.is_none() && self.fetching.is_none()
+    }
+
+    /// Check if something is being fetched.
+    pub fn is_loading(&self) -> bool {
+        self.fetching.is_some()
+    }
+
+    /// Check if there is a page after the last loaded page.
+    pub fn has_next_page(&self) -> bool {
+        self.next_cursor.is_some()
+    }
+
+    /// Check if there is a page before the first loaded page.
+    pub fn has_previous_page(&self) -> bool {
+        self.previous_cursor.is_some()
+    }
+
+    /// Check if the state is stale or not, where stale means outdated.
+    pub fn is_stale(&self, query: &InfiniteQuery<Q>) -> bool {
+        match self.settlement_instant {
+            Some(settlement_instant) => {
+                Instant::now().duration_since(settlement_instant) >= query.stale_time
+            }
+            None => true,
+        }
+    }
+
+    fn update_cursors(&mut self, query: &Q) {
+        self.next_cursor = self.pages.last().and_then(|page| query.next_cursor(page));
+        self.previous_cursor = self
+            .pages
+            .first()
+            .and_then(|page| query.previous_cursor(page));
+    }
+}
+
+pub struct InfiniteQueriesStorage<Q: InfiniteQueryCapability> {
+    storage: CacheStorage<InfiniteQueryData<Q>>,
+}
+
+impl<Q: InfiniteQueryCapability> Copy for InfiniteQueriesStorage<Q> {}
+
+impl<Q: InfiniteQueryCapability> Clone for InfiniteQueriesStorage<Q> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+pub struct InfiniteQueryData<Q: InfiniteQueryCapability> {
+    state: Rc<RefCell<InfiniteQueryStateData<Q>>>,
+    entry: CacheEntry,
+
+    in_flight: InFlight<()>,
+
+    persistence: Option<Rc<PersistenceHandle<Vec<InfinitePage<Q>>>>>,
+}
+
+impl<Q: InfiniteQueryCapability> Clone for InfiniteQueryData<Q> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            entry: self.entry.clone(),
+
+            in_flight: self.in_flight.clone(),
+
+            persistence: self.persistence.clone(),
+        }
+    }
+}
+
+impl<Q: InfiniteQueryCapability> CacheData for InfiniteQueryData<Q> {
+    type Key = InfiniteQuery<Q>;
+
+    fn new(query: &InfiniteQuery<Q>) -> Self {
+        let persistence = PersistenceHandle::for_infinite_query(query);
+        // Rehydrate the persisted pages
+        let mut state = InfiniteQueryStateData::default();
+        if let Some((pages, settlement_instant)) = persistence
+            .as_ref()
+            .and_then(|persistence| persistence.load(query.clean_time))
+        {
+            state.pages = pages;
+            state.update_cursors(&query.query);
+            state.settlement_instant = Some(settlement_instant);
+        }
+        Self {
+            state: Rc::new(RefCell::new(state)),
+            entry: CacheEntry::default(),
+            in_flight: InFlight::default(),
+            persistence: persistence.map(Rc::new),
+        }
+    }
+
+    fn entry(&self) -> &CacheEntry {
+        &self.entry
+    }
+
+    fn is_stale(&self, query: &InfiniteQuery<Q>) -> bool {
+        self.state.borrow().is_stale(query)
+    }
+
+    /// Fetch the first page, or all the loaded pages again in order, stopping early if there are less pages now.
+    fn run(&self, query: &InfiniteQuery<Q>) {
+        if self.in_flight.is_running() {
+            return;
+        }
+
+        let (mut cursor, pages_len) = {
+            let state = self.state.borrow();
+            match state.pages.first() {
+                Some(page) => (page.cursor.clone(), state.pages.len()),
+                None => (query.query.initial_cursor(&query.keys), 1),
+            }
+        };
+
+        self.start_fetching(query, InfiniteFetch::Refetch, {
+            let query = query.clone();
+            async move {
+                let mut pages = Vec::with_capacity(pages_len);
+                while pages.len() < pages_len {
+                    let data = query.run_with_retries(&cursor).await?;
+                    let page = InfinitePage { cursor, data };
+                    let next_cursor = query.query.next_cursor(&page);
+                    pages.push(page);
+                    match next_cursor {
+                        Some(next_cursor) => cursor = next_cursor,
+                        None => break,
+                    }
+                }
+                Ok(pages)
+            }
+        });
+    }
+
+    /// Cancel the in-flight fetch, if any, keeping the loaded pages.
+    fn cancel(&self) {
+        if self.in_flight.cancel().is_some() {
+            self.state.borrow_mut().fetching = None;
+            self.entry.notify();
+        }
+    }
+}
+
+impl<Q: InfiniteQueryCapability> InfiniteQueryData<Q> {
+    /// Cancel the in-flight fetch, if any, and refetch the loaded pages.
+    fn invalidate(&self, query: &InfiniteQuery<Q>) {
+        self.cancel();
+        self.run(query);
+    }
+
+    /// Fetch the page before or after the loaded pages, unless there is none or something is being fetched already.
+    fn fetch_page(&self, query: &InfiniteQuery<Q>, fetch: InfiniteFetch) {
+        if self.in_flight.is_running() {
+            return;
+        }
+
+        let cursor = {
+            let state = self.state.borrow();
+            match fetch {
+                InfiniteFetch::NextPage => state.next_cursor.clone(),
+                _ => state.previous_cursor.clone(),
+            }
+        };
+        let Some(cursor) = cursor else {
+            return;
+        };
+
+        self.start_fetching(query, fetch, {
+            let query = query.clone();
+            async move {
+                let data = query.run_with_retries(&cursor).await?;
+                Ok(vec![InfinitePage { cursor, data }])
+            }
+        });
+    }
+
+    /// Run the given fetch in the background and add the fetched pages once it succeeds.
+    fn start_fetching(
+        &self,
+        query: &InfiniteQuery<Q>,
+        fetch: InfiniteFetch,
+        pages: impl Future<Output = Result<Vec<InfinitePage<Q>>, Q::Err>> + 'static,
+    ) {
+        self.state.borrow_mut().fetching = Some(fetch);
+        self.entry.notify();
+
+        let query = query.clone();
+        let query_data = self.clone();
+        self.in_flight
+            .start((), pages, move |res| query_data.settle(&query, fetch, res));
+    }
+
+    fn settle(
+        &self,
+        query: &InfiniteQuery<Q>,
+        fetch: InfiniteFetch,
+        res: Result<Vec<InfinitePage<Q>>, Q::Err>,
+    ) {
+        let mut state = self.state.borrow_mut();
+        match res {
+            Ok(pages) => {
+                match fetch {
+                    InfiniteFetch::Refetch => state.pages = pages,
+                    InfiniteFetch::NextPage => state.pages.extend(pages),
+                    InfiniteFetch::PreviousPage => {
+                        state.pages.splice(0..0, pages);
+                    }
+                }
+                state.update_cursors(&query.query);
+                state.error = None;
+                if let Some(persistence) = &self.persistence {
+                    persistence.store(&state.pages);
+                }
+            }
+            // Keep the previous pages if it failed
+            Err(err) => state.error = Some(err),
+        }
+        if fetch == InfiniteFetch::Refetch {
+            state.settlement_instant = Some(Instant::now());
+        }
+        state.fetching = None;
+        drop(state);
+        self.entry.notify();
+    }
+}
+
+impl<Q: InfiniteQueryCapability> InfiniteQueriesStorage<Q> {
+    fn new_in_root() -> Self {
+        Self {
+            storage: CacheStorage::new_in_root(),
+        }
+    }
+
+    fn current() -> Self {
+        root_storage(Self::new_in_root)
+    }
+
+    pub async fn invalidate_all() {
+        let storage = consume_context::<InfiniteQueriesStorage<Q>>();
+
+        // Get all the queries
+        let matching_queries = storage.storage.matching(|_| true);
+
+        // Invalidate the queries
+        Self::invalidate_queries(&matching_queries).await
+    }
+
+    pub async fn invalidate_matching(matching_keys: Q::Keys) {
+        let storage = consume_context::<InfiniteQueriesStorage<Q>>();
+
+        // Get those queries that match
+        let matching_queries = storage
+            .storage
+            .matching(|query| query.query.matches(&matching_keys));
+
+        // Invalidate the queries
+        Self::invalidate_queries(&matching_queries).await
+    }
+
+    async fn invalidate_queries(queries: &[(InfiniteQuery<Q>, InfiniteQueryData<Q>)]) {
+        for (query, query_data) in queries {
+            query_data.invalidate(query);
+        }
+
+        join_all(
+            queries
+                .iter()
+                .map(|(_, query_data)| query_data.in_flight.settled()),
+        )
+        .await;
+    }
+}
+
+#[derive(PartialEq, Clone)]
+pub struct InfiniteQuery<Q: InfiniteQueryCapability> {
+    pub(crate) query: Q,
+    pub(crate) keys: Q::Keys,
+
+    enabled: bool,
+
+    stale_time: Duration,
+    clean_time: Duration,
+    retry: RetryPolicy,
+}
+
+impl<Q: InfiniteQueryCapability> Eq for InfiniteQuery<Q> {}
+impl<Q: InfiniteQueryCapability> Hash for InfiniteQuery<Q> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.query.hash(state);
+        self.keys.hash(state);
+
+        self.enabled.hash(state);
+
+        self.stale_time.hash(state);
+        self.clean_time.hash(state);
+
+        // Intentionally left out as retries can vary from one query subscriber to another
+        // self.retry.hash(state);
+    }
+}
+
+impl<Q: InfiniteQueryCapability> CacheKey for InfiniteQuery<Q> {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn clean_time(&self) -> Duration {
+        self.clean_time
+    }
+}
+
+impl<Q: InfiniteQueryCapability> InfiniteQuery<Q> {
+    pub fn new(keys: Q::Keys, query: Q) -> Self {
+        Self {
+            query,
+            keys,
+            enabled: true,
+            stale_time: Duration::ZERO,
+            clean_time: Duration::from_secs(5 * 60),
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Enable or disable this query so that it doesnt automatically run.
+    ///
+    /// Defaults to `true`.
+    pub fn enable(self, enabled: bool) -> Self {
+        Self { enabled, ..self }
+    }
+
+    /// For how long are the pages considered stale. If a query subscriber is mounted and the pages are stale, they are refetched
+    /// otherwise the cached pages are returned.
+    ///
+    /// Defaults to [Duration::ZERO], meaning they are marked stale immediately after they have been used.
+    pub fn stale_time(self, stale_time: Duration) -> Self {
+        Self { stale_time, ..self }
+    }
+
+    /// For how long the pages are kept cached after there are no more query subscribers.
+    ///
+    /// Defaults to `5min`, meaning they are cleared automatically after 5 minutes of no subscribers to it.
+    pub fn clean_time(self, clean_time: Duration) -> Self {
+        Self { clean_time, ..self }
+    }
+
+    /// How failed page fetches are retried before settling with the error, only errors accepted by
+    /// [InfiniteQueryCapability::should_retry] are retried.
+    ///
+    /// Defaults to [RetryPolicy::default], meaning it never retries.
+    pub fn retry(self, retry: RetryPolicy) -> Self {
+        Self { retry, ..self }
+    }
+
+    /// Fetch the page of the given cursor, retrying it according to its [RetryPolicy].
+    async fn run_with_retries(&self, cursor: &Q::Cursor) -> Result<Q::Page, Q::Err> {
+        self.retry
+            .run(
+                || self.query.run(&self.keys, cursor),
+                |err| self.query.should_retry(err),
+            )
+            .await
+    }
+}
+
+pub struct InfiniteQueryReader<Q: InfiniteQueryCapability> {
+    state: Rc<RefCell<InfiniteQueryStateData<Q>>>,
+}
+
+impl<Q: InfiniteQueryCapability> InfiniteQueryReader<Q> {
+    pub fn state(&'_ self) -> Ref<'_, InfiniteQueryStateData<Q>> {
+        self.state.borrow()
+    }
+}
+
+pub struct UseInfiniteQuery<Q: InfiniteQueryCapability> {
+    query: State<InfiniteQuery<Q>>,
+}
+
+impl<Q: InfiniteQueryCapability> Clone for UseInfiniteQuery<Q> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Q: InfiniteQueryCapability> Copy for UseInfiniteQuery<Q> {}
+
+impl<Q: InfiniteQueryCapability> UseInfiniteQuery<Q> {
+    fn data(&self) -> (InfiniteQuery<Q>, InfiniteQueryData<Q>) {
+        let storage = consume_context::<InfiniteQueriesStorage<Q>>();
+        let query = self.query.peek().clone();
+        let query_data = storage.storage.peek(&query);
+        (query, query_data)
+    }
+
+    /// Read the [InfiniteQuery] state.
+    ///
+    /// This **will** automatically subscribe.
+    /// If you want a **non-subscribing** method have a look at [UseInfiniteQuery::peek].
+    pub fn read(&self) -> InfiniteQueryReader<Q> {
+        let storage = consume_context::<InfiniteQueriesStorage<Q>>();
+        let query_data = storage.storage.read(&self.query.peek());
+
+        InfiniteQueryReader {
+            state: query_data.state,
+        }
+    }
+
+    /// Read the [InfiniteQuery] state.
+    ///
+    /// This **will not** automatically subscribe.
+    /// If you want a **subscribing** method have a look at [UseInfiniteQuery::read].
+    pub fn peek(&self) -> InfiniteQueryReader<Q> {
+        let (_, query_data) = self.data();
+
+        InfiniteQueryReader {
+            state: query_data.state,
+        }
+    }
+
+    /// Fetch the page after the last loaded page in the background, unless there is none or something is being fetched already.
+    ///
+    /// This can be called directly from `VirtualScrollView::on_end_reached` to load more items as the user scrolls.
+    pub fn fetch_next_page(&self) {
+        let (query, query_data) = self.data();
+        query_data.fetch_page(&query, InfiniteFetch::NextPage);
+    }
+
+    /// Fetch the page before the first loaded page in the background, unless there is none or something is being fetched already.
+    pub fn fetch_previous_page(&self) {
+        let (query, query_data) = self.data();
+        query_data.fetch_page(&query, InfiniteFetch::PreviousPage);
+    }
+
+    /// Refetch the loaded pages and await their result.
+    ///
+    /// For a `sync` version use [UseInfiniteQuery::invalidate].
+    pub async fn invalidate_async(&self) -> InfiniteQueryReader<Q> {
+        let (query, query_data) = self.data();
+
+        // Invalidate the query
+        InfiniteQueriesStorage::invalidate_queries(&[(query, query_data.clone())]).await;
+
+        InfiniteQueryReader {
+            state: query_data.state,
+        }
+    }
+
+    /// Refetch the loaded pages in the background.
+    ///
+    /// For an `async` version use [UseInfiniteQuery::invalidate_async].
+    pub fn invalidate(&self) {
+        let (query, query_data) = self.data();
+
+        // Invalidate the query
+        query_data.invalidate(&query);
+    }
+}
+
+/// Infinite queries are like [use_query](crate::query::use_query) but the data is fetched in pages, e.g. feeds or long lists.
+///
+/// The first page is fetched using the [InfiniteQueryCapability::initial_cursor], and the following pages are fetched
+/// on demand with [UseInfiniteQuery::fetch_next_page] using the cursor returned by [InfiniteQueryCapability::next_cursor].
+///
+/// When invalidated all the loaded pages are refetched in order.
+///
+/// Only one fetch of the same query can be in flight at a time, and just like queries they can be retried,
+/// see [InfiniteQuery::retry], and persisted, see [InfiniteQueryCapability::persistence].
+///
+/// See [InfiniteQuery::stale_time] and [InfiniteQuery::clean_time].
+pub fn use_infinite_query<Q: InfiniteQueryCapability>(
+    query: InfiniteQuery<Q>,
+) -> UseInfiniteQuery<Q> {
+    let storage = InfiniteQueriesStorage::<Q>::current();
+
+    UseInfiniteQuery {
+        query: use_cache_entry(storage.storage, query),
+    }
+}
@@ -17,6 +17,7 @@
 //! - **Background Refetching**: Stale data is automatically refreshed in the background
 //! - **Invalidation**: Mutations can invalidate related queries to keep data fresh
 //! - **Deduplication**: Multiple identical queries are automatically deduplicated
+//! - **Retries**: Failed queries can be retried with exponential backoff
 //! - **Error Handling**: Built-in error states
 //! - **Reactive**: Integrates seamlessly with Freya's reactive state system
 //!
@@ -145,6 +146,46 @@
 //! QueriesStorage::<FetchUser>::invalidate_matching(1).await;
 //! ```
 //!
+//! ### Optimistic Updates
+//!
+//! Mutations can update the cached queries before the server confirms the change,
+//! the updates are rolled back if the mutation fails:
+//!
+//! ```rust, ignore
+//! # use freya::query::*;
+//! impl MutationCapability for UpdateUser {
+//!     // ... other methods
+//!
+//!     async fn on_mutate(&self, keys: &Self::Keys) -> QuerySnapshots {
+//!         QuerySnapshots::default().with(QueriesStorage::<FetchUser>::update_data(
+//!             &self.id,
+//!             |user| user.name = keys.clone(),
+//!         ))
+//!     }
+//!
+//!     // Refetch the user once the mutation settles
+//!     fn invalidates(&self, _keys: &Self::Keys) -> Vec<QueryInvalidation> {
+//!         vec![QueryInvalidation::matching::<FetchUser>(self.id)]
+//!     }
+//! }
+//! ```
+//!
+//! ### Infinite Queries
+//!
+//! Feeds and long lists can be fetched in pages with [use_infinite_query](infinite_query::use_infinite_query),
+//! loading more pages as the user scrolls:
+//!
+//! ```rust, ignore
+//! # use freya::{prelude::*, query::*};
+//! let posts = use_infinite_query(InfiniteQuery::new((), GetPosts));
+//! let items = posts.read().state().pages_data().flatten().cloned().collect::<Vec<_>>();
+//!
+//! VirtualScrollView::new_with_data(items.clone(), |i, items| label().key(i).text(items[i].clone()).into())
+//!     .length(items.len())
+//!     .item_size(25.)
+//!     .on_end_reached(move |_| posts.fetch_next_page())
+//! ```
+//!
 //! ### Custom Query Matching
 //!
 //! Control which queries get invalidated by implementing custom matching logic:
@@ -170,6 +211,50 @@
 //! }
 //! ```
 //!
+//! ### Retries
+//!
+//! Failed queries can be retried with an exponential backoff,
+//! optionally skipping the errors that are not worth retrying:
+//!
+//! ```rust, ignore
+//! # use freya::query::*;
+//! impl QueryCapability for FetchUser {
+//!     // ... other methods
+//!
+//!     fn should_retry(&self, error: &Self::Err) -> bool {
+//!         error != "Not found"
+//!     }
+//! }
+//!
+//! // Retry up to 3 times, waiting 500ms, 1s and 2s
+//! let user = use_query(
+//!     Query::new(1, FetchUser).retry(RetryPolicy::new(3).delay(Duration::from_millis(500))),
+//! );
+//! ```
+//!
+//! ### Persistence
+//!
+//! Settled results can be persisted so they are shown right away the next time the app starts,
+//! even if the backend is down, by opting-in with [QueryCapability::persistence](query::QueryCapability::persistence)
+//! and providing a [QueryPersister](persistence::QueryPersister) in the root component:
+//!
+//! ```rust, ignore
+//! # use freya::query::*;
+//! impl QueryCapability for FetchUser {
+//!     // ... other methods
+//!
+//!     fn persistence(&self) -> Option<QueryPersistence<Self>> {
+//!         // Requires the `json-persister` feature
+//!         Some(QueryPersistence::json("fetch-user"))
+//!     }
+//! }
+//!
+//! fn app() -> impl IntoElement {
+//!     use_init_query_persister(|| JsonFilePersister::new("cache/queries.json"));
+//!     // ...
+//! }
+//! ```
+//!
 //! ### Background Refetching
 //!
 //! Queries automatically refetch data in the background when components remount
@@ -194,14 +279,19 @@
 //! - **Smart Caching**: Results are cached until invalidated or expired
 //! - **Minimal Re-renders**: Only components reading changed data re-render
 
+mod cache;
 pub mod captured;
+pub mod infinite_query;
 pub mod mutation;
+pub mod persistence;
 pub mod query;
 
 pub mod prelude {
     pub use crate::{
         captured::*,
+        infinite_query::*,
         mutation::*,
+        persistence::*,
         query::*,
     };
 }
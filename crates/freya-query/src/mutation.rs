@@ -8,6 +8,7 @@ use std::{
     future::Future,
     hash::Hash,
     mem,
+    pin::Pin,
     rc::Rc,
     time::{
         Duration,
@@ -26,6 +27,19 @@ use freya_core::{
     prelude::*,
     scope_id::ScopeId,
 };
+use futures_util::future::join_all;
+
+use crate::{
+    infinite_query::{
+        InfiniteQueriesStorage,
+        InfiniteQueryCapability,
+    },
+    query::{
+        QueriesStorage,
+        QueryCapability,
+        QuerySnapshot,
+    },
+};
 
 pub trait MutationCapability
 where
@@ -43,6 +57,23 @@ where
         true
     }
 
+    /// Runs before [MutationCapability::run].
+    /// You may use this method to optimistically update [crate::query::Query]s with [QueriesStorage::update_data],
+    /// the returned snapshots are rolled back if the mutation fails.
+    fn on_mutate(&self, _keys: &Self::Keys) -> impl Future<Output = QuerySnapshots> {
+        async { QuerySnapshots::default() }
+    }
+
+    /// Runs after [MutationCapability::run] if it succeeded.
+    fn on_success(&self, _keys: &Self::Keys, _value: &Self::Ok) -> impl Future<Output = ()> {
+        async {}
+    }
+
+    /// Runs after [MutationCapability::run] if it failed, once the optimistic updates have been rolled back.
+    fn on_error(&self, _keys: &Self::Keys, _error: &Self::Err) -> impl Future<Output = ()> {
+        async {}
+    }
+
     /// Runs after [MutationCapability::run].
     /// You may use this method to invalidate [crate::query::Query]s.
     fn on_settled(
@@ -52,6 +83,98 @@ where
     ) -> impl Future<Output = ()> {
         async {}
     }
+
+    /// [crate::query::Query]s that depend on this mutation and are invalidated once it settles,
+    /// whether it succeeded or not.
+    fn invalidates(&self, _keys: &Self::Keys) -> Vec<QueryInvalidation> {
+        Vec::new()
+    }
+}
+
+/// Snapshots of the [crate::query::Query]s optimistically updated by a mutation, see [MutationCapability::on_mutate].
+#[derive(Default)]
+pub struct QuerySnapshots(Vec<Box<dyn FnOnce()>>);
+
+impl QuerySnapshots {
+    /// Add the snapshot of a [QueriesStorage::update_data] call.
+    pub fn push<Q: QueryCapability>(&mut self, snapshot: QuerySnapshot<Q>)
+    where
+        Q::Ok: 'static,
+    {
+        self.0.push(Box::new(move || snapshot.rollback()));
+    }
+
+    /// Like [QuerySnapshots::push] but chainable.
+    pub fn with<Q: QueryCapability>(mut self, snapshot: QuerySnapshot<Q>) -> Self
+    where
+        Q::Ok: 'static,
+    {
+        self.push(snapshot);
+        self
+    }
+
+    /// Restore the previous values of all the queries, in reverse order.
+    pub fn rollback(self) {
+        for rollback in self.0.into_iter().rev() {
+            rollback();
+        }
+    }
+}
+
+/// [crate::query::Query]s invalidated by a mutation, see [MutationCapability::invalidates].
+pub struct QueryInvalidation(Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()>>>>);
+
+impl QueryInvalidation {
+    /// Invalidate the queries that match the given keys, see [QueriesStorage::invalidate_matching].
+    pub fn matching<Q: QueryCapability>(keys: Q::Keys) -> Self
+    where
+        Q::Keys: 'static,
+    {
+        Self(Box::new(move || {
+            Box::pin(async move {
+                // There is nothing to invalidate if this query has never been used
+                if try_consume_context::<QueriesStorage<Q>>().is_some() {
+                    QueriesStorage::<Q>::invalidate_matching(keys).await;
+                }
+            })
+        }))
+    }
+
+    /// Invalidate all the queries, see [QueriesStorage::invalidate_all].
+    pub fn all<Q: QueryCapability>() -> Self {
+        Self(Box::new(|| {
+            Box::pin(async {
+                if try_consume_context::<QueriesStorage<Q>>().is_some() {
+                    QueriesStorage::<Q>::invalidate_all().await;
+                }
+            })
+        }))
+    }
+
+    /// Invalidate the infinite queries that match the given keys, see [InfiniteQueriesStorage::invalidate_matching].
+    pub fn infinite_matching<Q: InfiniteQueryCapability>(keys: Q::Keys) -> Self
+    where
+        Q::Keys: 'static,
+    {
+        Self(Box::new(move || {
+            Box::pin(async move {
+                if try_consume_context::<InfiniteQueriesStorage<Q>>().is_some() {
+                    InfiniteQueriesStorage::<Q>::invalidate_matching(keys).await;
+                }
+            })
+        }))
+    }
+
+    /// Invalidate all the infinite queries, see [InfiniteQueriesStorage::invalidate_all].
+    pub fn infinite_all<Q: InfiniteQueryCapability>() -> Self {
+        Self(Box::new(|| {
+            Box::pin(async {
+                if try_consume_context::<InfiniteQueriesStorage<Q>>().is_some() {
+                    InfiniteQueriesStorage::<Q>::invalidate_all().await;
+                }
+            })
+        }))
+    }
 }
 
 pub enum MutationStateData<Q: MutationCapability> {
@@ -209,11 +332,30 @@ impl<Q: MutationCapability> MutationsStorage<Q> {
             reactive_context.notify();
         }
 
+        // Optimistically update the queries
+        let snapshots = mutation.mutation.on_mutate(&keys).await;
+
         // Run
         let res = mutation.mutation.run(&keys).await;
 
+        match &res {
+            Ok(value) => mutation.mutation.on_success(&keys, value).await,
+            Err(error) => {
+                snapshots.rollback();
+                mutation.mutation.on_error(&keys, error).await
+            }
+        }
+
         // Set to Settled
         mutation.mutation.on_settled(&keys, &res).await;
+        join_all(
+            mutation
+                .mutation
+                .invalidates(&keys)
+                .into_iter()
+                .map(|QueryInvalidation(invalidate)| invalidate()),
+        )
+        .await;
         *data.state.borrow_mut() = MutationStateData::Settled {
             res,
             settlement_instant: Instant::now(),
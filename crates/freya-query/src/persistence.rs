@@ -0,0 +1,383 @@
+use std::{
+    borrow::Cow,
+    rc::Rc,
+    time::{
+        Duration,
+        Instant,
+        SystemTime,
+    },
+};
+
+use freya_core::{
+    lifecycle::context::{
+        provide_context_for_scope_id,
+        try_consume_context,
+    },
+    prelude::*,
+    scope_id::ScopeId,
+};
+
+use crate::{
+    infinite_query::{
+        InfinitePage,
+        InfiniteQuery,
+        InfiniteQueryCapability,
+    },
+    query::{
+        Query,
+        QueryCapability,
+    },
+};
+
+/// Settled query result as stored by a [QueryPersister].
+#[derive(Clone, PartialEq, Debug)]
+pub struct PersistedQuery {
+    /// Value encoded by the [QueryPersistence] of the query.
+    pub data: String,
+    /// When the value was settled.
+    pub persisted_at: SystemTime,
+}
+
+/// Backend where the settled results of queries are kept across app restarts, see [use_init_query_persister].
+pub trait QueryPersister {
+    /// Load the query persisted under the given key.
+    fn load(&self, key: &str) -> Option<PersistedQuery>;
+
+    /// Persist a query under the given key, replacing the previous one.
+    fn store(&self, key: &str, query: PersistedQuery);
+
+    /// Remove the query persisted under the given key.
+    fn remove(&self, key: &str);
+}
+
+/// How the results of a [QueryCapability] are persisted, see [QueryCapability::persistence].
+pub struct QueryPersistence<Q: QueryCapability>(PersistenceCodec<Q::Keys, Q::Ok>);
+
+impl<Q: QueryCapability> QueryPersistence<Q> {
+    /// Persist the results under the given name, which must be unique across queries
+    /// and stable across app versions, using the given functions to encode and decode them.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        encode_keys: fn(&Q::Keys) -> Option<String>,
+        encode: fn(&Q::Ok) -> Option<String>,
+        decode: fn(&str) -> Option<Q::Ok>,
+    ) -> Self {
+        Self(PersistenceCodec {
+            name: name.into(),
+            encode_keys,
+            encode,
+            decode,
+        })
+    }
+
+    /// Persist the results under the given name encoded as JSON.
+    #[cfg(feature = "json-persister")]
+    pub fn json(name: impl Into<Cow<'static, str>>) -> Self
+    where
+        Q::Keys: serde::Serialize,
+        Q::Ok: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        Self::new(
+            name,
+            |keys| serde_json::to_string(keys).ok(),
+            |value| serde_json::to_string(value).ok(),
+            |data| serde_json::from_str(data).ok(),
+        )
+    }
+}
+
+/// How the pages of an [InfiniteQueryCapability] are persisted, see [InfiniteQueryCapability::persistence].
+pub struct InfiniteQueryPersistence<Q: InfiniteQueryCapability>(
+    PersistenceCodec<Q::Keys, Vec<InfinitePage<Q>>>,
+);
+
+impl<Q: InfiniteQueryCapability> InfiniteQueryPersistence<Q> {
+    /// Persist the loaded pages under the given name, which must be unique across queries
+    /// and stable across app versions, using the given functions to encode and decode them.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        encode_keys: fn(&Q::Keys) -> Option<String>,
+        encode: fn(&Vec<InfinitePage<Q>>) -> Option<String>,
+        decode: fn(&str) -> Option<Vec<InfinitePage<Q>>>,
+    ) -> Self {
+        Self(PersistenceCodec {
+            name: name.into(),
+            encode_keys,
+            encode,
+            decode,
+        })
+    }
+
+    /// Persist the loaded pages under the given name encoded as JSON, as a list of `[cursor, page]` pairs.
+    #[cfg(feature = "json-persister")]
+    pub fn json(name: impl Into<Cow<'static, str>>) -> Self
+    where
+        Q::Keys: serde::Serialize,
+        Q::Cursor: serde::Serialize + serde::de::DeserializeOwned,
+        Q::Page: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        Self::new(
+            name,
+            |keys| serde_json::to_string(keys).ok(),
+            |pages| {
+                let pages = pages
+                    .iter()
+                    .map(|page| (&page.cursor, &page.data))
+                    .collect::<Vec<_>>();
+                serde_json::to_string(&pages).ok()
+            },
+            |data| {
+                let pages: Vec<(Q::Cursor, Q::Page)> = serde_json::from_str(data).ok()?;
+                Some(
+                    pages
+                        .into_iter()
+                        .map(|(cursor, data)| InfinitePage { cursor, data })
+                        .collect(),
+                )
+            },
+        )
+    }
+}
+
+/// Functions to encode and decode the keys and values of a query.
+struct PersistenceCodec<K, V> {
+    name: Cow<'static, str>,
+    encode_keys: fn(&K) -> Option<String>,
+    encode: fn(&V) -> Option<String>,
+    decode: fn(&str) -> Option<V>,
+}
+
+#[derive(Clone)]
+struct QueriesPersister(Rc<dyn QueryPersister>);
+
+/// Persist the settled results of the queries that opt-in with [QueryCapability::persistence]
+/// or [InfiniteQueryCapability::persistence] in the given [QueryPersister], so they are rehydrated the next time the app starts.
+///
+/// Rehydrated results are stale once they are older than the [Query::stale_time], in which case they are
+/// shown while the query reruns, and are discarded once they are older than the [Query::clean_time].
+///
+/// This must be called before any query is used, e.g in the root component.
+pub fn use_init_query_persister<P: QueryPersister + 'static>(persister: impl FnOnce() -> P) {
+    use_hook(|| {
+        provide_context_for_scope_id(QueriesPersister(Rc::new(persister())), Some(ScopeId::ROOT));
+    })
+}
+
+/// Persistence of a single query.
+pub(crate) struct PersistenceHandle<V> {
+    persister: Rc<dyn QueryPersister>,
+    encode: fn(&V) -> Option<String>,
+    decode: fn(&str) -> Option<V>,
+    key: String,
+}
+
+impl<V> PersistenceHandle<V> {
+    fn new<K>(codec: PersistenceCodec<K, V>, keys: &K) -> Option<Self> {
+        let QueriesPersister(persister) = try_consume_context::<QueriesPersister>()?;
+        let key = format!("{}:{}", codec.name, (codec.encode_keys)(keys)?);
+        Some(Self {
+            persister,
+            encode: codec.encode,
+            decode: codec.decode,
+            key,
+        })
+    }
+
+    pub(crate) fn for_query<Q: QueryCapability<Ok = V>>(query: &Query<Q>) -> Option<Self> {
+        let QueryPersistence(codec) = query.query.persistence()?;
+        Self::new(codec, &query.keys)
+    }
+
+    /// Load the persisted value and when it was settled, if it is not older than the clean time.
+    pub(crate) fn load(&self, clean_time: Duration) -> Option<(V, Instant)> {
+        let persisted = self.persister.load(&self.key)?;
+        let age = persisted.persisted_at.elapsed().unwrap_or_default();
+        let value = (age < clean_time)
+            .then(|| (self.decode)(&persisted.data))
+            .flatten();
+        let Some(value) = value else {
+            self.persister.remove(&self.key);
+            return None;
+        };
+        // So it becomes stale just like if it had been settled in this run
+        Some((value, instant_ago(age)))
+    }
+
+    /// Persist the given value.
+    pub(crate) fn store(&self, value: &V) {
+        if let Some(data) = (self.encode)(value) {
+            self.persister.store(
+                &self.key,
+                PersistedQuery {
+                    data,
+                    persisted_at: SystemTime::now(),
+                },
+            );
+        }
+    }
+}
+
+impl<Q: InfiniteQueryCapability> PersistenceHandle<Vec<InfinitePage<Q>>> {
+    pub(crate) fn for_infinite_query(query: &InfiniteQuery<Q>) -> Option<Self> {
+        let InfiniteQueryPersistence(codec) = query.query.persistence()?;
+        Self::new(codec, &query.keys)
+    }
+}
+
+/// The [Instant] of `age` ago, or the earliest representable one
+/// if the monotonic clock doesn't go that far back, e.g. it was persisted before the system booted.
+fn instant_ago(age: Duration) -> Instant {
+    let now = Instant::now();
+    now.checked_sub(age).unwrap_or_else(|| {
+        // Go back in halving steps until nothing else can be subtracted
+        let mut earliest = now;
+        let mut step = age;
+        while !step.is_zero() {
+            match earliest.checked_sub(step) {
+                Some(instant) => earliest = instant,
+                None => step /= 2,
+            }
+        }
+        earliest
+    })
+}
+
+#[cfg(feature = "json-persister")]
+pub use json::JsonFilePersister;
+
+#[cfg(feature = "json-persister")]
+mod json {
+    use std::{
+        cell::RefCell,
+        collections::HashMap,
+        fs,
+        path::PathBuf,
+        time::{
+            Duration,
+            SystemTime,
+        },
+    };
+
+    use serde::{
+        Deserialize,
+        Serialize,
+    };
+
+    use super::{
+        PersistedQuery,
+        QueryPersister,
+    };
+
+    #[derive(Serialize, Deserialize)]
+    struct JsonEntry {
+        data: String,
+        /// Milliseconds since the Unix epoch.
+        persisted_at: u64,
+    }
+
+    /// [QueryPersister] that keeps all the queries in a single JSON file.
+    ///
+    /// The file is read once when created and rewritten every time a query is persisted,
+    /// failing to read or write it is ignored as it is just a cache.
+    pub struct JsonFilePersister {
+        path: PathBuf,
+        entries: RefCell<HashMap<String, JsonEntry>>,
+    }
+
+    impl JsonFilePersister {
+        pub fn new(path: impl Into<PathBuf>) -> Self {
+            let path = path.into();
+            let entries = fs::read(&path)
+                .ok()
+                .and_then(|content| serde_json::from_slice(&content).ok())
+                .unwrap_or_default();
+            Self {
+                path,
+                entries: RefCell::new(entries),
+            }
+        }
+
+        fn save(&self) {
+            if let Some(parent) = self.path.parent() {
+                fs::create_dir_all(parent).ok();
+            }
+            if let Ok(content) = serde_json::to_vec(&*self.entries.borrow()) {
+                fs::write(&self.path, content).ok();
+            }
+        }
+    }
+
+    impl QueryPersister for JsonFilePersister {
+        fn load(&self, key: &str) -> Option<PersistedQuery> {
+            self.entries.borrow().get(key).map(|entry| PersistedQuery {
+                data: entry.data.clone(),
+                persisted_at: SystemTime::UNIX_EPOCH + Duration::from_millis(entry.persisted_at),
+            })
+        }
+
+        fn store(&self, key: &str, query: PersistedQuery) {
+            let persisted_at = query
+                .persisted_at
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64;
+            self.entries.borrow_mut().insert(
+                key.to_string(),
+                JsonEntry {
+                    data: query.data,
+                    persisted_at,
+                },
+            );
+            self.save();
+        }
+
+        fn remove(&self, key: &str) {
+            if self.entries.borrow_mut().remove(key).is_some() {
+                self.save();
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn json_file_persister() {
+            let path = std::env::temp_dir()
+                .join(format!("freya-query-{}/queries.json", std::process::id()));
+            let query = PersistedQuery {
+                data: "\"Marc\"".to_string(),
+                persisted_at: SystemTime::UNIX_EPOCH + Duration::from_millis(1000),
+            };
+
+            let persister = JsonFilePersister::new(&path);
+            persister.store("user:0", query.clone());
+            persister.store("user:1", query.clone());
+            persister.remove("user:1");
+
+            // Read it back from the file
+            let persister = JsonFilePersister::new(&path);
+            assert_eq!(persister.load("user:0"), Some(query));
+            assert_eq!(persister.load("user:1"), None);
+
+            fs::remove_dir_all(path.parent().unwrap()).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn instant_ago_clamps() {
+        let age = Duration::from_secs(60);
+        assert!(instant_ago(age).elapsed() >= age);
+
+        // Older than the monotonic clock, so it is as old as possible
+        let earliest = instant_ago(Duration::MAX);
+        assert!(earliest <= instant_ago(age));
+        assert!(earliest.checked_sub(Duration::from_secs(1)).is_none());
+    }
+}
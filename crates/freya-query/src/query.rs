@@ -4,7 +4,6 @@ use std::{
         Ref,
         RefCell,
     },
-    collections::HashMap,
     future::Future,
     hash::Hash,
     mem,
@@ -17,18 +16,28 @@ use std::{
 
 use async_io::Timer;
 use freya_core::{
-    integration::FxHashSet,
     lifecycle::context::{
         consume_context,
-        provide_context_for_scope_id,
         try_consume_context,
     },
     prelude::*,
-    scope_id::ScopeId,
 };
-use futures_util::stream::{
-    FuturesUnordered,
-    StreamExt,
+use futures_util::future::join_all;
+
+use crate::{
+    cache::{
+        CacheData,
+        CacheEntry,
+        CacheKey,
+        CacheStorage,
+        InFlight,
+        root_storage,
+        use_cache_entry,
+    },
+    persistence::{
+        PersistenceHandle,
+        QueryPersistence,
+    },
 };
 
 pub trait QueryCapability
@@ -46,6 +55,18 @@ where
     fn matches(&self, _keys: &Self::Keys) -> bool {
         true
     }
+
+    /// Check if a failed run should be retried given its error, see [Query::retry].
+    fn should_retry(&self, _error: &Self::Err) -> bool {
+        true
+    }
+
+    /// Persist the settled results of this query so they survive app restarts, see [use_init_query_persister](crate::persistence::use_init_query_persister).
+    ///
+    /// Defaults to `None`, meaning they are not persisted.
+    fn persistence(&self) -> Option<QueryPersistence<Self>> {
+        None
+    }
 }
 
 pub enum QueryStateData<Q: QueryCapability> {
@@ -137,6 +158,23 @@ impl<Q: QueryCapability> QueryStateData<Q> {
         }
     }
 
+    fn ok_mut(&mut self) -> Option<&mut Q::Ok> {
+        match self {
+            Self::Settled { res: Ok(res), .. } => Some(res),
+            Self::Loading { res: Some(Ok(res)) } => Some(res),
+            _ => None,
+        }
+    }
+
+    fn settlement_instant(&self) -> Option<Instant> {
+        match self {
+            Self::Settled {
+                settlement_instant, ..
+            } => Some(*settlement_instant),
+            _ => None,
+        }
+    }
+
     fn into_loading(self) -> QueryStateData<Q> {
         match self {
             QueryStateData::Pending => QueryStateData::Loading { res: None },
@@ -147,7 +185,7 @@ impl<Q: QueryCapability> QueryStateData<Q> {
 }
 
 pub struct QueriesStorage<Q: QueryCapability> {
-    storage: State<HashMap<Query<Q>, QueryData<Q>>>,
+    storage: CacheStorage<QueryData<Q>>,
 }
 
 impl<Q: QueryCapability> Copy for QueriesStorage<Q> {}
@@ -160,54 +198,107 @@ impl<Q: QueryCapability> Clone for QueriesStorage<Q> {
 
 pub struct QueryData<Q: QueryCapability> {
     state: Rc<RefCell<QueryStateData<Q>>>,
-    reactive_contexts: Rc<RefCell<FxHashSet<ReactiveContext>>>,
+    entry: CacheEntry,
 
     interval_task: Rc<RefCell<Option<(Duration, TaskHandle)>>>,
-    clean_task: Rc<RefCell<Option<TaskHandle>>>,
+    /// Keeps the settlement of the value before it started loading, restored if the run is cancelled.
+    in_flight: InFlight<Option<Instant>>,
+
+    persistence: Option<Rc<PersistenceHandle<Q::Ok>>>,
 }
 
 impl<Q: QueryCapability> Clone for QueryData<Q> {
     fn clone(&self) -> Self {
         Self {
             state: self.state.clone(),
-            reactive_contexts: self.reactive_contexts.clone(),
+            entry: self.entry.clone(),
 
             interval_task: self.interval_task.clone(),
-            clean_task: self.clean_task.clone(),
+            in_flight: self.in_flight.clone(),
+
+            persistence: self.persistence.clone(),
         }
     }
 }
 
-impl<Q: QueryCapability> QueriesStorage<Q> {
-    fn new_in_root() -> Self {
+impl<Q: QueryCapability> CacheData for QueryData<Q> {
+    type Key = Query<Q>;
+
+    fn new(query: &Query<Q>) -> Self {
+        let persistence = PersistenceHandle::for_query(query);
+        // Rehydrate the persisted result
+        let state = persistence
+            .as_ref()
+            .and_then(|persistence| persistence.load(query.clean_time))
+            .map(|(res, settlement_instant)| QueryStateData::Settled {
+                res: Ok(res),
+                settlement_instant,
+            })
+            .unwrap_or(QueryStateData::Pending);
         Self {
-            storage: State::create_global(HashMap::default()),
+            state: Rc::new(RefCell::new(state)),
+            entry: CacheEntry::default(),
+            interval_task: Rc::default(),
+            in_flight: InFlight::default(),
+            persistence: persistence.map(Rc::new),
         }
     }
 
-    fn insert_or_get_query(&mut self, query: Query<Q>) -> QueryData<Q> {
-        let query_clone = query.clone();
-        let mut storage = self.storage.write_unchecked();
+    fn entry(&self) -> &CacheEntry {
+        &self.entry
+    }
 
-        let query_data = storage.entry(query).or_insert_with(|| QueryData {
-            state: Rc::new(RefCell::new(QueryStateData::Pending)),
-            reactive_contexts: Rc::new(RefCell::new(FxHashSet::default())),
-            interval_task: Rc::default(),
-            clean_task: Rc::default(),
-        });
-        let query_data_clone = query_data.clone();
+    fn is_stale(&self, query: &Query<Q>) -> bool {
+        self.state.borrow().is_stale(query)
+    }
 
-        // Cancel clean task
-        if let Some(clean_task) = query_data.clean_task.take() {
-            clean_task.cancel();
+    fn run(&self, query: &Query<Q>) {
+        if self.in_flight.is_running() {
+            return;
         }
 
+        // Set to Loading
+        let state = mem::replace(&mut *self.state.borrow_mut(), QueryStateData::Pending);
+        let previous_settlement_instant = state.settlement_instant();
+        *self.state.borrow_mut() = state.into_loading();
+        self.entry.notify();
+
+        let query = query.clone();
+        let query_data = self.clone();
+        self.in_flight.start(
+            previous_settlement_instant,
+            async move { query.run_with_retries().await },
+            // Set to Settled
+            move |res| query_data.settle(res),
+        );
+    }
+
+    /// Cancel the in-flight run, if any, restoring the previous state.
+    fn cancel(&self) {
+        let Some(previous_settlement_instant) = self.in_flight.cancel() else {
+            return;
+        };
+
+        let state = mem::replace(&mut *self.state.borrow_mut(), QueryStateData::Pending);
+        *self.state.borrow_mut() = match (state, previous_settlement_instant) {
+            (QueryStateData::Loading { res: Some(res) }, Some(settlement_instant)) => {
+                QueryStateData::Settled {
+                    res,
+                    settlement_instant,
+                }
+            }
+            _ => QueryStateData::Pending,
+        };
+        self.entry.notify();
+    }
+
+    fn on_subscribe(&self, query: &Query<Q>) {
         // Start an interval task if necessary
         // If multiple queries subscribers use different intervals the interval task
         // will run using the shortest interval
-        let interval = query_clone.interval_time;
-        let interval_enabled = query_clone.interval_time != Duration::MAX;
-        let interval_task = &mut *query_data.interval_task.borrow_mut();
+        let interval = query.interval_time;
+        let interval_enabled = query.interval_time != Duration::MAX;
+        let interval_task = &mut *self.interval_task.borrow_mut();
 
         let create_interval_task = match interval_task {
             None if interval_enabled => true,
@@ -222,103 +313,75 @@ impl<Q: QueryCapability> QueriesStorage<Q> {
             _ => false,
         };
         if create_interval_task {
+            let query = query.clone();
+            let query_data = self.clone();
             let task = spawn_forever(async move {
                 loop {
                     // Wait as long as the stale time is configured
                     Timer::after(interval).await;
 
-                    // Run the query
-                    QueriesStorage::<Q>::run_queries(&[(&query_clone, &query_data_clone)]).await;
+                    // Run the query, joining the in-flight run if any
+                    query_data.run(&query);
+                    query_data.in_flight.settled().await;
                 }
             });
             *interval_task = Some((interval, task));
         }
-
-        query_data.clone()
     }
 
-    fn update_tasks(&mut self, query: Query<Q>) {
-        let storage_clone = self.storage;
-        let mut storage = self.storage.write_unchecked();
-
-        let query_data = storage.get_mut(&query).unwrap();
-
+    fn on_unsubscribe(&self) {
         // Cancel interval task
-        if let Some((_, interval_task)) = query_data.interval_task.take() {
+        if let Some((_, interval_task)) = self.interval_task.take() {
             interval_task.cancel();
         }
+    }
+}
 
-        // Spawn clean up task if there no more reactive contexts
-        if query_data.reactive_contexts.borrow().len() == 1 {
-            *query_data.clean_task.borrow_mut() = Some(spawn_forever(async move {
-                // Wait as long as the stale time is configured
-                Timer::after(query.clean_time).await;
-
-                // Finally clear the query
-                let mut storage = storage_clone.write_unchecked();
-                storage.remove(&query);
-            }));
+impl<Q: QueryCapability> QueryData<Q> {
+    /// Cancel the in-flight run, if any, and run the query again so the result is never older than the invalidation.
+    fn invalidate(&self, query: &Query<Q>) {
+        self.cancel();
+        self.run(query);
+    }
+
+    fn settle(&self, res: Result<Q::Ok, Q::Err>) {
+        if let (Some(persistence), Ok(value)) = (&self.persistence, &res) {
+            persistence.store(value);
         }
+        *self.state.borrow_mut() = QueryStateData::Settled {
+            res,
+            settlement_instant: Instant::now(),
+        };
+        self.entry.notify();
+    }
+}
+
+impl<Q: QueryCapability> QueriesStorage<Q> {
+    fn new_in_root() -> Self {
+        Self {
+            storage: CacheStorage::new_in_root(),
+        }
+    }
+
+    fn current() -> Self {
+        root_storage(Self::new_in_root)
     }
 
     pub async fn get(get_query: GetQuery<Q>) -> QueryReader<Q> {
         let query: Query<Q> = get_query.into();
 
-        let mut storage = match try_consume_context::<QueriesStorage<Q>>() {
-            Some(storage) => storage,
-            None => {
-                provide_context_for_scope_id(
-                    QueriesStorage::<Q>::new_in_root(),
-                    Some(ScopeId::ROOT),
-                );
-                try_consume_context::<QueriesStorage<Q>>().unwrap()
-            }
-        };
-
-        let mut map = storage.storage.write();
-        let query_data = map
-            .entry(query.clone())
-            .or_insert_with(|| QueryData {
-                state: Rc::new(RefCell::new(QueryStateData::Pending)),
-                reactive_contexts: Rc::new(RefCell::new(FxHashSet::default())),
-                interval_task: Rc::default(),
-                clean_task: Rc::default(),
-            })
-            .clone();
+        let storage = Self::current().storage;
+        let query_data = storage.get_or_insert(&query);
 
         // Run the query if the value is stale
-        if query_data.state.borrow().is_stale(&query) {
-            // Set to Loading
-            let res = mem::replace(&mut *query_data.state.borrow_mut(), QueryStateData::Pending)
-                .into_loading();
-            *query_data.state.borrow_mut() = res;
-            for reactive_context in query_data.reactive_contexts.borrow().iter() {
-                reactive_context.notify();
-            }
-
-            // Run
-            let res = query.query.run(&query.keys).await;
-
-            // Set to Settled
-            *query_data.state.borrow_mut() = QueryStateData::Settled {
-                res,
-                settlement_instant: Instant::now(),
-            };
-            for reactive_context in query_data.reactive_contexts.borrow().iter() {
-                reactive_context.notify();
-            }
+        if query_data.is_stale(&query) {
+            query_data.run(&query);
+            query_data.in_flight.settled().await;
         }
 
         // Spawn clean up task if there no more reactive contexts
-        if query_data.reactive_contexts.borrow().is_empty() {
-            *query_data.clean_task.borrow_mut() = Some(spawn_forever(async move {
-                // Wait as long as the stale time is configured
-                Timer::after(query.clean_time).await;
-
-                // Finally clear the query
-                let mut storage = storage.storage.write_unchecked();
-                storage.remove(&query);
-            }));
+        if !query_data.entry.has_subscribers() {
+            storage.schedule_clean(query, &query_data);
         }
 
         QueryReader {
@@ -330,68 +393,110 @@ impl<Q: QueryCapability> QueriesStorage<Q> {
         let storage = consume_context::<QueriesStorage<Q>>();
 
         // Get all the queries
-        let matching_queries = storage
-            .storage
-            .read()
-            .clone()
-            .into_iter()
-            .collect::<Vec<_>>();
-        let matching_queries = matching_queries
-            .iter()
-            .map(|(q, d)| (q, d))
-            .collect::<Vec<_>>();
+        let matching_queries = storage.storage.matching(|_| true);
 
         // Invalidate the queries
-        Self::run_queries(&matching_queries).await
+        Self::invalidate_queries(&matching_queries).await
     }
 
     pub async fn invalidate_matching(matching_keys: Q::Keys) {
         let storage = consume_context::<QueriesStorage<Q>>();
 
         // Get those queries that match
-        let mut matching_queries = Vec::new();
-        for (query, data) in storage.storage.read().iter() {
-            if query.query.matches(&matching_keys) {
-                matching_queries.push((query.clone(), data.clone()));
-            }
-        }
-        let matching_queries = matching_queries
-            .iter()
-            .map(|(q, d)| (q, d))
-            .collect::<Vec<_>>();
+        let matching_queries = storage
+            .storage
+            .matching(|query| query.query.matches(&matching_keys));
 
         // Invalidate the queries
-        Self::run_queries(&matching_queries).await
+        Self::invalidate_queries(&matching_queries).await
     }
 
-    async fn run_queries(queries: &[(&Query<Q>, &QueryData<Q>)]) {
-        let tasks = FuturesUnordered::new();
+    /// Update the values of the queries with the given keys without running them, e.g. optimistically
+    /// from [MutationCapability::on_mutate](crate::mutation::MutationCapability::on_mutate) before the mutation is confirmed.
+    ///
+    /// Only the queries that have a value are updated. Their in-flight runs are cancelled so they don't overwrite the update.
+    /// The returned [QuerySnapshot] can be used to restore their previous values.
+    pub fn update_data(keys: &Q::Keys, mut updater: impl FnMut(&mut Q::Ok)) -> QuerySnapshot<Q>
+    where
+        Q::Ok: Clone,
+    {
+        let Some(storage) = try_consume_context::<QueriesStorage<Q>>() else {
+            return QuerySnapshot {
+                entries: Vec::new(),
+            };
+        };
 
-        for (query, query_data) in queries {
-            // Set to Loading
-            let res = mem::replace(&mut *query_data.state.borrow_mut(), QueryStateData::Pending)
-                .into_loading();
-            *query_data.state.borrow_mut() = res;
-            for reactive_context in query_data.reactive_contexts.borrow().iter() {
-                reactive_context.notify();
+        let mut entries = Vec::new();
+        for (query, query_data) in storage.storage.matching(|query| &query.keys == keys) {
+            // Queries without a value yet keep loading as there is nothing to update
+            if query_data.state.borrow().ok().is_none() {
+                continue;
             }
 
-            tasks.push(Box::pin(async move {
-                // Run
-                let res = query.query.run(&query.keys).await;
+            query_data.cancel();
+            let mut state = query_data.state.borrow_mut();
+            let settlement_instant = state.settlement_instant();
+            let Some(value) = state.ok_mut() else {
+                // The cancelled run had not settled before, so it runs again
+                drop(state);
+                query_data.run(&query);
+                continue;
+            };
+            entries.push(QuerySnapshotEntry {
+                query_data: query_data.clone(),
+                previous_value: value.clone(),
+                settlement_instant,
+            });
+            updater(value);
+            drop(state);
+            query_data.entry.notify();
+        }
 
-                // Set to settled
-                *query_data.state.borrow_mut() = QueryStateData::Settled {
-                    res,
-                    settlement_instant: Instant::now(),
-                };
-                for reactive_context in query_data.reactive_contexts.borrow().iter() {
-                    reactive_context.notify();
-                }
-            }));
+        QuerySnapshot { entries }
+    }
+
+    async fn invalidate_queries(queries: &[(Query<Q>, QueryData<Q>)]) {
+        for (query, query_data) in queries {
+            query_data.invalidate(query);
         }
 
-        tasks.count().await;
+        join_all(
+            queries
+                .iter()
+                .map(|(_, query_data)| query_data.in_flight.settled()),
+        )
+        .await;
+    }
+}
+
+/// Previous values of the queries updated with [QueriesStorage::update_data].
+pub struct QuerySnapshot<Q: QueryCapability> {
+    entries: Vec<QuerySnapshotEntry<Q>>,
+}
+
+struct QuerySnapshotEntry<Q: QueryCapability> {
+    query_data: QueryData<Q>,
+    previous_value: Q::Ok,
+    /// Settlement of the value when the snapshot was taken.
+    settlement_instant: Option<Instant>,
+}
+
+impl<Q: QueryCapability> QuerySnapshot<Q> {
+    /// Restore the previous values of the queries.
+    ///
+    /// Queries that have settled again since the snapshot was taken are left untouched, as their value is newer.
+    pub fn rollback(self) {
+        for entry in self.entries {
+            let mut state = entry.query_data.state.borrow_mut();
+            if state.settlement_instant() != entry.settlement_instant {
+                continue;
+            }
+            if let Some(value) = state.ok_mut() {
+                *value = entry.previous_value;
+            }
+            drop(state);
+            entry.query_data.entry.notify();
+        }
     }
 }
 
@@ -401,6 +506,7 @@ pub struct GetQuery<Q: QueryCapability> {
 
     stale_time: Duration,
     clean_time: Duration,
+    retry: RetryPolicy,
 }
 
 impl<Q: QueryCapability> GetQuery<Q> {
@@ -410,6 +516,7 @@ impl<Q: QueryCapability> GetQuery<Q> {
             keys,
             stale_time: Duration::ZERO,
             clean_time: Duration::ZERO,
+            retry: RetryPolicy::default(),
         }
     }
     /// For how long is the data considered stale. If a query subscriber is mounted and the data is stale, it will re run the query.
@@ -425,6 +532,11 @@ impl<Q: QueryCapability> GetQuery<Q> {
     pub fn clean_time(self, clean_time: Duration) -> Self {
         Self { clean_time, ..self }
     }
+
+    /// How failed runs are retried, see [Query::retry].
+    pub fn retry(self, retry: RetryPolicy) -> Self {
+        Self { retry, ..self }
+    }
 }
 
 impl<Q: QueryCapability> From<GetQuery<Q>> for Query<Q> {
@@ -438,19 +550,21 @@ impl<Q: QueryCapability> From<GetQuery<Q>> for Query<Q> {
             stale_time: value.stale_time,
             clean_time: value.clean_time,
             interval_time: Duration::MAX,
+            retry: value.retry,
         }
     }
 }
 #[derive(PartialEq, Clone)]
 pub struct Query<Q: QueryCapability> {
-    query: Q,
-    keys: Q::Keys,
+    pub(crate) query: Q,
+    pub(crate) keys: Q::Keys,
 
     enabled: bool,
 
     stale_time: Duration,
-    clean_time: Duration,
+    pub(crate) clean_time: Duration,
     interval_time: Duration,
+    retry: RetryPolicy,
 }
 
 impl<Q: QueryCapability> Eq for Query<Q> {}
@@ -466,6 +580,17 @@ impl<Q: QueryCapability> Hash for Query<Q> {
 
         // Intentionally left out as intervals can vary from one query subscriber to another
         // self.interval_time.hash(state);
+        // self.retry.hash(state);
+    }
+}
+
+impl<Q: QueryCapability> CacheKey for Query<Q> {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn clean_time(&self) -> Duration {
+        self.clean_time
     }
 }
 
@@ -478,6 +603,7 @@ impl<Q: QueryCapability> Query<Q> {
             stale_time: Duration::ZERO,
             clean_time: Duration::from_secs(5 * 60),
             interval_time: Duration::MAX,
+            retry: RetryPolicy::default(),
         }
     }
 
@@ -514,6 +640,93 @@ impl<Q: QueryCapability> Query<Q> {
             ..self
         }
     }
+
+    /// How failed runs are retried before settling with the error, only errors accepted by
+    /// [QueryCapability::should_retry] are retried.
+    ///
+    /// Defaults to [RetryPolicy::default], meaning it never retries.
+    pub fn retry(self, retry: RetryPolicy) -> Self {
+        Self { retry, ..self }
+    }
+
+    /// Run the query, retrying it according to its [RetryPolicy].
+    async fn run_with_retries(&self) -> Result<Q::Ok, Q::Err> {
+        self.retry
+            .run(
+                || self.query.run(&self.keys),
+                |err| self.query.should_retry(err),
+            )
+            .await
+    }
+}
+
+/// How the failed runs of a [Query] are retried, waiting an exponentially longer delay between each retry.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    retries: u32,
+    delay: Duration,
+    factor: f64,
+    max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl RetryPolicy {
+    /// Retry up to the given amount of times, waiting `1s`, `2s`, `4s` and so on between them, up to `30s`.
+    pub fn new(retries: u32) -> Self {
+        Self {
+            retries,
+            delay: Duration::from_secs(1),
+            factor: 2.,
+            max_delay: Duration::from_secs(30),
+        }
+    }
+
+    /// Delay before the first retry.
+    pub fn delay(self, delay: Duration) -> Self {
+        Self { delay, ..self }
+    }
+
+    /// By how much the delay is multiplied after each retry, `1.` for a constant delay.
+    pub fn factor(self, factor: f64) -> Self {
+        Self { factor, ..self }
+    }
+
+    /// Max delay between retries.
+    pub fn max_delay(self, max_delay: Duration) -> Self {
+        Self { max_delay, ..self }
+    }
+
+    /// Run the given future, retrying it while the errors are accepted by `should_retry`.
+    pub(crate) async fn run<T, E, F: Future<Output = Result<T, E>>>(
+        &self,
+        mut run: impl FnMut() -> F,
+        should_retry: impl Fn(&E) -> bool,
+    ) -> Result<T, E> {
+        let mut attempt = 0;
+        loop {
+            let res = run().await;
+            match &res {
+                Err(err) if attempt < self.retries && should_retry(err) => {
+                    Timer::after(self.retry_delay(attempt)).await;
+                    attempt += 1;
+                }
+                _ => return res,
+            }
+        }
+    }
+
+    /// Delay before the retry after the given failed attempt, starting at `0`.
+    pub fn retry_delay(&self, attempt: u32) -> Duration {
+        let delay = self.delay.as_secs_f64() * self.factor.powi(attempt as i32);
+        Duration::try_from_secs_f64(delay)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
 }
 
 pub struct QueryReader<Q: QueryCapability> {
@@ -555,13 +768,7 @@ impl<Q: QueryCapability> UseQuery<Q> {
     /// If you want a **non-subscribing** method have a look at [UseQuery::peek].
     pub fn read(&self) -> QueryReader<Q> {
         let storage = consume_context::<QueriesStorage<Q>>();
-        let map = storage.storage.peek();
-        let query_data = map.get(&self.query.peek()).cloned().unwrap();
-
-        // Subscribe if possible
-        if let Some(mut reactive_context) = ReactiveContext::try_current() {
-            reactive_context.subscribe(&query_data.reactive_contexts);
-        }
+        let query_data = storage.storage.read(&self.query.peek());
 
         QueryReader {
             state: query_data.state,
@@ -574,8 +781,7 @@ impl<Q: QueryCapability> UseQuery<Q> {
     /// If you want a **subscribing** method have a look at [UseQuery::read].
     pub fn peek(&self) -> QueryReader<Q> {
         let storage = consume_context::<QueriesStorage<Q>>();
-        let map = storage.storage.peek();
-        let query_data = map.get(&self.query.peek()).cloned().unwrap();
+        let query_data = storage.storage.peek(&self.query.peek());
 
         QueryReader {
             state: query_data.state,
@@ -589,14 +795,13 @@ impl<Q: QueryCapability> UseQuery<Q> {
         let storage = consume_context::<QueriesStorage<Q>>();
 
         let query = self.query.peek().clone();
-        let map = storage.storage.peek();
-        let query_data = map.get(&query).cloned().unwrap();
+        let query_data = storage.storage.peek(&query);
 
-        // Run the query
-        QueriesStorage::run_queries(&[(&query, &query_data)]).await;
+        // Invalidate the query
+        QueriesStorage::invalidate_queries(&[(query, query_data.clone())]).await;
 
         QueryReader {
-            state: query_data.state.clone(),
+            state: query_data.state,
         }
     }
 
@@ -607,14 +812,12 @@ impl<Q: QueryCapability> UseQuery<Q> {
         let storage = consume_context::<QueriesStorage<Q>>();
 
         let query = self.query.peek().clone();
-        let map = storage.storage.peek();
-        let query_data = map.get(&query).cloned().unwrap();
+        let query_data = storage.storage.peek(&query);
 
-        // Run the query
-        spawn(async move { QueriesStorage::run_queries(&[(&query, &query_data)]).await });
+        // Invalidate the query
+        query_data.invalidate(&query);
     }
 }
-
 /// Queries are used to get data asynchronously (e.g external resources such as HTTP APIs), which can later be cached or refreshed.
 ///
 /// Important concepts:
@@ -642,57 +845,21 @@ impl<Q: QueryCapability> UseQuery<Q> {
 /// By default it never refreshes automatically.
 ///
 /// See [Query::interval_time].
+///
+/// ### Retries
+/// How many times and how often is a failed query retried before settling with the error.
+/// By default it is never retried.
+///
+/// See [Query::retry] and [QueryCapability::should_retry].
+///
+/// ### Deduplication and cancellation
+/// Only one run of the same query can be in flight at a time, so subscribers requesting it concurrently share the same run.
+/// Invalidations instead cancel the in-flight run and start a fresh one, so they never settle with a result requested before them.
+/// Runs are cancelled once all the subscribers of the query have been unmounted.
 pub fn use_query<Q: QueryCapability>(query: Query<Q>) -> UseQuery<Q> {
-    let mut storage = match try_consume_context::<QueriesStorage<Q>>() {
-        Some(storage) => storage,
-        None => {
-            provide_context_for_scope_id(QueriesStorage::<Q>::new_in_root(), Some(ScopeId::ROOT));
-            try_consume_context::<QueriesStorage<Q>>().unwrap()
-        }
-    };
-
-    let mut make_query = |query: &Query<Q>, mut prev_query: Option<Query<Q>>| {
-        let query_data = storage.insert_or_get_query(query.clone());
+    let storage = QueriesStorage::<Q>::current();
 
-        // Update the query tasks if there has been a change in the query
-        if let Some(prev_query) = prev_query.take() {
-            storage.update_tasks(prev_query);
-        }
-
-        // Immediately run the query if enabled and the value is stale
-        if query.enabled && query_data.state.borrow().is_stale(query) {
-            let query = query.clone();
-            spawn(async move {
-                QueriesStorage::run_queries(&[(&query, &query_data)]).await;
-            });
-        }
-    };
-
-    let mut current_query = use_hook(|| {
-        make_query(&query, None);
-        State::create(query.clone())
-    });
-
-    if *current_query.read() != query {
-        let prev = mem::replace(&mut *current_query.write(), query.clone());
-        make_query(&query, Some(prev));
+    UseQuery {
+        query: use_cache_entry(storage.storage, query),
     }
-
-    // Update the query tasks when the scope is dropped
-    use_drop({
-        move || {
-            storage.update_tasks(current_query.peek().clone());
-        }
-    });
-
-    let query = UseQuery {
-        query: current_query,
-    };
-
-    // Used to consider this use_query call as a subscriber without rerunning the component
-    use_side_effect(move || {
-        let _ = query.read();
-    });
-
-    query
 }
@@ -0,0 +1,131 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+};
+
+use freya_query::prelude::*;
+use freya_testing::prelude::*;
+
+#[derive(Clone, PartialEq, Hash, Eq)]
+struct GetNumbers;
+
+impl InfiniteQueryCapability for GetNumbers {
+    type Page = Vec<usize>;
+    type Err = ();
+    type Keys = ();
+    type Cursor = usize;
+
+    fn initial_cursor(&self, _keys: &Self::Keys) -> Self::Cursor {
+        0
+    }
+
+    async fn run(
+        &self,
+        _keys: &Self::Keys,
+        cursor: &Self::Cursor,
+    ) -> Result<Self::Page, Self::Err> {
+        Ok((cursor * 3..(cursor + 1) * 3).collect())
+    }
+
+    fn next_cursor(&self, page: &InfinitePage<Self>) -> Option<Self::Cursor> {
+        (page.cursor < 2).then_some(page.cursor + 1)
+    }
+}
+
+#[test]
+fn infinite_query_pages() {
+    let numbers = Rc::new(RefCell::new(Vec::new()));
+
+    let mut test = launch_test({
+        let numbers = numbers.clone();
+        move || {
+            let query = use_infinite_query(InfiniteQuery::new((), GetNumbers));
+            let state = query.read();
+            let state = state.state();
+
+            // Fetch all the pages one by one
+            if !state.is_loading() && state.has_next_page() {
+                query.fetch_next_page();
+            }
+            *numbers.borrow_mut() = state.pages_data().flatten().copied().collect::<Vec<_>>();
+
+            rect()
+        }
+    });
+    test.sync_and_update();
+
+    test.poll(
+        std::time::Duration::from_millis(10),
+        std::time::Duration::from_millis(200),
+    );
+
+    assert_eq!(*numbers.borrow(), (0..9).collect::<Vec<_>>());
+}
+
+#[derive(Clone, PartialEq, Hash, Eq)]
+struct FlakyGetNumbers(Captured<Rc<RefCell<usize>>>);
+
+impl InfiniteQueryCapability for FlakyGetNumbers {
+    type Page = Vec<usize>;
+    type Err = usize;
+    type Keys = ();
+    type Cursor = usize;
+
+    fn initial_cursor(&self, _keys: &Self::Keys) -> Self::Cursor {
+        0
+    }
+
+    async fn run(
+        &self,
+        _keys: &Self::Keys,
+        cursor: &Self::Cursor,
+    ) -> Result<Self::Page, Self::Err> {
+        // Every page fails on its first attempt
+        let mut attempts = self.0.borrow_mut();
+        *attempts += 1;
+        if *attempts % 2 == 1 {
+            return Err(*attempts);
+        }
+        Ok((cursor * 3..(cursor + 1) * 3).collect())
+    }
+
+    fn next_cursor(&self, page: &InfinitePage<Self>) -> Option<Self::Cursor> {
+        (page.cursor < 1).then_some(page.cursor + 1)
+    }
+}
+
+#[test]
+fn infinite_query_retry() {
+    let numbers = Rc::new(RefCell::new(Vec::new()));
+    let attempts = Rc::new(RefCell::new(0));
+
+    let mut test = launch_test({
+        let numbers = numbers.clone();
+        let attempts = attempts.clone();
+        move || {
+            // Mounted twice to make sure both subscribers share the same fetches
+            let query = InfiniteQuery::new((), FlakyGetNumbers(Captured(attempts.clone())))
+                .retry(RetryPolicy::new(1).delay(std::time::Duration::from_millis(10)));
+            let numbers_query = use_infinite_query(query.clone());
+            use_infinite_query(query);
+            let state = numbers_query.read();
+            let state = state.state();
+
+            if !state.is_loading() && state.has_next_page() {
+                numbers_query.fetch_next_page();
+            }
+            *numbers.borrow_mut() = state.pages_data().flatten().copied().collect::<Vec<_>>();
+
+            rect()
+        }
+    });
+    test.sync_and_update();
+
+    test.poll(
+        std::time::Duration::from_millis(10),
+        std::time::Duration::from_millis(300),
+    );
+
+    assert_eq!(*numbers.borrow(), (0..6).collect::<Vec<_>>());
+    assert_eq!(*attempts.borrow(), 4);
+}
@@ -94,3 +94,182 @@ fn mutation_basic() {
             .contains("John")
     );
 }
+
+#[derive(Clone, PartialEq, Hash, Eq)]
+struct TrySetUserName;
+
+impl MutationCapability for TrySetUserName {
+    type Ok = ();
+    type Err = ();
+    type Keys = (usize, String);
+
+    async fn run(&self, _keys: &Self::Keys) -> Result<Self::Ok, Self::Err> {
+        async_io::Timer::after(std::time::Duration::from_millis(50)).await;
+        Err(())
+    }
+
+    async fn on_mutate(&self, (user_id, name): &Self::Keys) -> QuerySnapshots {
+        QuerySnapshots::default().with(QueriesStorage::<GetUserName>::update_data(
+            user_id,
+            |user_name| *user_name = name.clone(),
+        ))
+    }
+}
+
+#[test]
+fn mutation_optimistic_rollback() {
+    let names = Rc::new(RefCell::new(Vec::new()));
+
+    let mut test = launch_test({
+        let names = names.clone();
+        move || {
+            let client = use_hook(|| Captured(Rc::new(RefCell::new(String::from("Marc")))));
+            let user = use_query(Query::new(0usize, GetUserName(client)));
+            let mutation = use_mutation(Mutation::new(TrySetUserName));
+
+            let name = user.read().state().ok().cloned();
+            if let Some(name) = name {
+                if names.borrow().is_empty() {
+                    mutation.mutate((0usize, "John".to_string()));
+                }
+                names.borrow_mut().push(name);
+            }
+
+            rect()
+        }
+    });
+    test.sync_and_update();
+
+    test.poll(
+        std::time::Duration::from_millis(10),
+        std::time::Duration::from_millis(200),
+    );
+
+    // Optimistically updated and then rolled back once the mutation failed
+    let names = names.borrow();
+    assert!(names.iter().any(|name| name == "John"));
+    assert_eq!(names.last().map(String::as_str), Some("Marc"));
+}
+
+#[derive(Clone, PartialEq, Hash, Eq)]
+struct RefreshAndFailSetUserName(Captured<Rc<RefCell<String>>>);
+
+impl MutationCapability for RefreshAndFailSetUserName {
+    type Ok = ();
+    type Err = ();
+    type Keys = (usize, String);
+
+    async fn run(&self, (user_id, _name): &Self::Keys) -> Result<Self::Ok, Self::Err> {
+        // Someone else renamed the user in the meantime
+        *self.0.borrow_mut() = "Anna".to_string();
+        QueriesStorage::<GetUserName>::invalidate_matching(*user_id).await;
+        Err(())
+    }
+
+    async fn on_mutate(&self, (user_id, name): &Self::Keys) -> QuerySnapshots {
+        QuerySnapshots::default().with(QueriesStorage::<GetUserName>::update_data(
+            user_id,
+            |user_name| *user_name = name.clone(),
+        ))
+    }
+}
+
+#[test]
+fn mutation_rollback_after_settled() {
+    let names = Rc::new(RefCell::new(Vec::new()));
+
+    let mut test = launch_test({
+        let names = names.clone();
+        move || {
+            let client = use_hook(|| Captured(Rc::new(RefCell::new(String::from("Marc")))));
+            let user = use_query(Query::new(0usize, GetUserName(client.clone())));
+            let mutation = use_mutation(Mutation::new(RefreshAndFailSetUserName(client)));
+
+            let name = user.read().state().ok().cloned();
+            if let Some(name) = name {
+                if names.borrow().is_empty() {
+                    mutation.mutate((0usize, "John".to_string()));
+                }
+                names.borrow_mut().push(name);
+            }
+
+            rect()
+        }
+    });
+    test.sync_and_update();
+
+    test.poll(
+        std::time::Duration::from_millis(10),
+        std::time::Duration::from_millis(200),
+    );
+
+    // The query settled again before the mutation failed, so the stale snapshot is not restored
+    let names = names.borrow();
+    assert!(names.iter().any(|name| name == "John"));
+    assert_eq!(names.last().map(String::as_str), Some("Anna"));
+}
+
+#[derive(Clone, PartialEq, Hash, Eq)]
+struct SlowGetUserName;
+
+impl QueryCapability for SlowGetUserName {
+    type Ok = String;
+    type Err = ();
+    type Keys = usize;
+
+    async fn run(&self, _user_id: &Self::Keys) -> Result<Self::Ok, Self::Err> {
+        async_io::Timer::after(std::time::Duration::from_millis(50)).await;
+        Ok("Marc".to_string())
+    }
+}
+
+#[derive(Clone, PartialEq, Hash, Eq)]
+struct OptimisticSetSlowUserName;
+
+impl MutationCapability for OptimisticSetSlowUserName {
+    type Ok = ();
+    type Err = ();
+    type Keys = (usize, String);
+
+    async fn run(&self, _keys: &Self::Keys) -> Result<Self::Ok, Self::Err> {
+        Ok(())
+    }
+
+    async fn on_mutate(&self, (user_id, name): &Self::Keys) -> QuerySnapshots {
+        QuerySnapshots::default().with(QueriesStorage::<SlowGetUserName>::update_data(
+            user_id,
+            |user_name| *user_name = name.clone(),
+        ))
+    }
+}
+
+#[test]
+fn mutation_update_data_during_first_load() {
+    let names = Rc::new(RefCell::new(Vec::new()));
+
+    let mut test = launch_test({
+        let names = names.clone();
+        move || {
+            let user = use_query(Query::new(0usize, SlowGetUserName));
+            let mutation = use_mutation(Mutation::new(OptimisticSetSlowUserName));
+
+            // Updated while the first run is still in flight
+            use_after_side_effect(move || {
+                mutation.mutate((0usize, "John".to_string()));
+            });
+
+            names.borrow_mut().push(user.read().state().ok().cloned());
+
+            rect()
+        }
+    });
+    test.sync_and_update();
+
+    test.poll(
+        std::time::Duration::from_millis(10),
+        std::time::Duration::from_millis(200),
+    );
+
+    // There was no value to update so the first run settles as usual
+    assert_eq!(names.borrow().last(), Some(&Some("Marc".to_string())));
+}
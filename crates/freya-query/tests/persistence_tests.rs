@@ -0,0 +1,100 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+    time::Duration,
+};
+
+use freya_query::prelude::*;
+use freya_testing::prelude::*;
+
+#[derive(Clone, Default)]
+struct MemoryPersister(Rc<RefCell<HashMap<String, PersistedQuery>>>);
+
+impl QueryPersister for MemoryPersister {
+    fn load(&self, key: &str) -> Option<PersistedQuery> {
+        self.0.borrow().get(key).cloned()
+    }
+
+    fn store(&self, key: &str, query: PersistedQuery) {
+        self.0.borrow_mut().insert(key.to_string(), query);
+    }
+
+    fn remove(&self, key: &str) {
+        self.0.borrow_mut().remove(key);
+    }
+}
+
+#[derive(Clone, PartialEq, Hash, Eq)]
+struct GetUserName(Captured<Rc<RefCell<String>>>);
+
+impl QueryCapability for GetUserName {
+    type Ok = String;
+    type Err = ();
+    type Keys = usize;
+
+    fn run(
+        &self,
+        _user_id: &Self::Keys,
+    ) -> impl core::future::Future<Output = Result<Self::Ok, Self::Err>> {
+        let client = self.0.clone();
+        async move { Ok(client.borrow().clone()) }
+    }
+
+    fn persistence(&self) -> Option<QueryPersistence<Self>> {
+        Some(QueryPersistence::new(
+            "user-name",
+            |user_id: &usize| Some(user_id.to_string()),
+            |name: &String| Some(name.clone()),
+            |data| Some(data.to_string()),
+        ))
+    }
+}
+
+fn launch(
+    persister: MemoryPersister,
+    client: Rc<RefCell<String>>,
+    stale_time: Duration,
+) -> (TestingRunner, Rc<RefCell<Option<String>>>) {
+    let name = Rc::new(RefCell::new(None));
+    let test = launch_test({
+        let name = name.clone();
+        move || {
+            use_init_query_persister(|| persister.clone());
+            let user = use_query(
+                Query::new(0usize, GetUserName(Captured(client.clone()))).stale_time(stale_time),
+            );
+            *name.borrow_mut() = user.read().state().ok().cloned();
+            rect()
+        }
+    });
+    (test, name)
+}
+
+#[test]
+fn query_persistence() {
+    let persister = MemoryPersister::default();
+    let client = Rc::new(RefCell::new("Marc".to_string()));
+
+    let (mut test, name) = launch(persister.clone(), client.clone(), Duration::ZERO);
+    test.sync_and_update();
+    test.poll(Duration::from_millis(10), Duration::from_millis(100));
+    assert_eq!(name.borrow().as_deref(), Some("Marc"));
+    assert_eq!(persister.load("user-name:0").unwrap().data, "Marc");
+
+    // Fresh persisted results are rehydrated without rerunning the query
+    *client.borrow_mut() = "Alfredo".to_string();
+    let (mut test, name) = launch(persister.clone(), client.clone(), Duration::MAX);
+    test.sync_and_update();
+    assert_eq!(name.borrow().as_deref(), Some("Marc"));
+    test.poll(Duration::from_millis(10), Duration::from_millis(100));
+    assert_eq!(name.borrow().as_deref(), Some("Marc"));
+
+    // Stale persisted results are shown while the query reruns
+    let (mut test, name) = launch(persister.clone(), client, Duration::ZERO);
+    test.sync_and_update();
+    assert_eq!(name.borrow().as_deref(), Some("Marc"));
+    test.poll(Duration::from_millis(10), Duration::from_millis(100));
+    assert_eq!(name.borrow().as_deref(), Some("Alfredo"));
+    assert_eq!(persister.load("user-name:0").unwrap().data, "Alfredo");
+}
@@ -59,3 +59,185 @@ fn query_basic() {
             .contains("Settled")
     );
 }
+
+#[derive(Clone, PartialEq, Hash, Eq)]
+struct FlakyGetUserName(Captured<std::rc::Rc<std::cell::Cell<usize>>>);
+
+impl QueryCapability for FlakyGetUserName {
+    type Ok = String;
+    type Err = usize;
+    type Keys = usize;
+
+    fn run(
+        &self,
+        _user_id: &Self::Keys,
+    ) -> impl core::future::Future<Output = Result<Self::Ok, Self::Err>> {
+        let attempts = self.0.clone();
+        async move {
+            attempts.set(attempts.get() + 1);
+            match attempts.get() {
+                3 => Ok("Marc".to_string()),
+                attempt => Err(attempt),
+            }
+        }
+    }
+
+    fn should_retry(&self, error: &Self::Err) -> bool {
+        *error < 3
+    }
+}
+
+#[test]
+fn query_retry() {
+    let attempts = std::rc::Rc::new(std::cell::Cell::new(0));
+    let mut test = launch_test({
+        let attempts = attempts.clone();
+        move || {
+            // Mounted twice to make sure both subscribers share the same run
+            let query = Query::new(0usize, FlakyGetUserName(Captured(attempts.clone())))
+                .retry(RetryPolicy::new(5).delay(std::time::Duration::from_millis(10)));
+            let user = use_query(query.clone());
+            use_query(query);
+            rect().child(label().text(format!("{:?}", user.read().state())))
+        }
+    });
+    test.sync_and_update();
+
+    // Wait for the query to settle
+    test.poll(
+        std::time::Duration::from_millis(10),
+        std::time::Duration::from_millis(300),
+    );
+
+    let label = test
+        .find(|node, element| Label::try_downcast(element).map(|_| node))
+        .unwrap();
+
+    assert!(
+        Label::try_downcast(&*label.element())
+            .unwrap()
+            .text
+            .contains("Marc")
+    );
+    assert_eq!(attempts.get(), 3);
+}
+
+#[derive(Clone, PartialEq, Hash, Eq)]
+struct SlowCounter(Captured<std::rc::Rc<std::cell::Cell<usize>>>);
+
+impl QueryCapability for SlowCounter {
+    type Ok = usize;
+    type Err = ();
+    type Keys = usize;
+
+    fn run(
+        &self,
+        _keys: &Self::Keys,
+    ) -> impl core::future::Future<Output = Result<Self::Ok, Self::Err>> {
+        let runs = self.0.clone();
+        async move {
+            runs.set(runs.get() + 1);
+            let run = runs.get();
+            async_io::Timer::after(std::time::Duration::from_millis(50)).await;
+            Ok(run)
+        }
+    }
+}
+
+#[test]
+fn query_invalidate_during_flight() {
+    let runs = std::rc::Rc::new(std::cell::Cell::new(0));
+    let mut test = launch_test({
+        let runs = runs.clone();
+        move || {
+            let counter = use_query(Query::new(0usize, SlowCounter(Captured(runs.clone()))));
+
+            // Invalidated while the first run is still in flight
+            use_after_side_effect(move || {
+                counter.invalidate();
+            });
+
+            rect().child(label().text(format!("{:?}", counter.read().state().ok())))
+        }
+    });
+    test.sync_and_update();
+
+    test.poll(
+        std::time::Duration::from_millis(10),
+        std::time::Duration::from_millis(300),
+    );
+
+    let label = test
+        .find(|node, element| Label::try_downcast(element).map(|_| node))
+        .unwrap();
+
+    // The first run was cancelled instead of being joined
+    assert_eq!(
+        Label::try_downcast(&*label.element())
+            .unwrap()
+            .text
+            .as_ref(),
+        "Some(2)"
+    );
+    assert_eq!(runs.get(), 2);
+}
+
+#[derive(Clone, PartialEq, Hash, Eq)]
+struct UnfinishedCounter(Captured<std::rc::Rc<std::cell::Cell<usize>>>);
+
+impl QueryCapability for UnfinishedCounter {
+    type Ok = ();
+    type Err = ();
+    type Keys = usize;
+
+    fn run(
+        &self,
+        _keys: &Self::Keys,
+    ) -> impl core::future::Future<Output = Result<Self::Ok, Self::Err>> {
+        let finished_runs = self.0.clone();
+        async move {
+            async_io::Timer::after(std::time::Duration::from_millis(50)).await;
+            finished_runs.set(finished_runs.get() + 1);
+            Ok(())
+        }
+    }
+}
+
+#[test]
+fn query_cancel_on_unmount() {
+    #[derive(PartialEq)]
+    struct Subscriber(std::rc::Rc<std::cell::Cell<usize>>);
+
+    impl Component for Subscriber {
+        fn render(&self) -> impl IntoElement {
+            use_query(Query::new(
+                0usize,
+                UnfinishedCounter(Captured(self.0.clone())),
+            ));
+            rect()
+        }
+    }
+
+    let finished_runs = std::rc::Rc::new(std::cell::Cell::new(0));
+    let mut test = launch_test({
+        let finished_runs = finished_runs.clone();
+        move || {
+            let mut show = use_state(|| true);
+            rect()
+                .expanded()
+                .on_mouse_up(move |_| show.set(false))
+                .maybe_child(show().then(|| Subscriber(finished_runs.clone())))
+        }
+    });
+    test.sync_and_update();
+
+    // Unmount the only subscriber while its run is in flight
+    test.click_cursor((15., 15.));
+
+    test.poll(
+        std::time::Duration::from_millis(10),
+        std::time::Duration::from_millis(200),
+    );
+
+    assert_eq!(finished_runs.get(), 0);
+}
@@ -0,0 +1,175 @@
+//! Record the user interactions of a Freya app into an [InteractionScript],
+//! so they can be replayed later in tests with `TestingRunner::replay_file` from `freya-testing`.
+//!
+//! ```rust,ignore
+//! launch(
+//!     LaunchConfig::new()
+//!         .with_plugin(InteractionRecorderPlugin::new("tests/scripts/login.json"))
+//!         .with_window(WindowConfig::new(app)),
+//! )
+//! ```
+//!
+//! Toggle the recording with Ctrl+Shift+R (Cmd+Shift+R on macOS), the script is saved when the recording stops
+//! or when the window gets closed.
+
+use std::{
+    fs,
+    path::PathBuf,
+    time::Instant,
+};
+
+pub use freya_core::integration::{
+    InteractionScript,
+    ScriptedEvent,
+};
+use freya_core::integration::{
+    KeyboardEventName,
+    PlatformEvent,
+};
+use freya_winit::{
+    plugins::{
+        FreyaPlugin,
+        Key,
+        Modifiers,
+        PluginEvent,
+        PluginHandle,
+    },
+    reexports::winit::window::{
+        Window,
+        WindowId,
+    },
+};
+use torin::prelude::Size2D;
+
+/// Plugin that records the [PlatformEvent]s of a window, with their timing, into an [InteractionScript]
+/// saved as JSON.
+pub struct InteractionRecorderPlugin {
+    path: PathBuf,
+    record_on_launch: bool,
+    recording: Option<Recording>,
+}
+
+struct Recording {
+    window_id: WindowId,
+    last_event: Instant,
+    script: InteractionScript,
+}
+
+impl InteractionRecorderPlugin {
+    /// Save the recorded scripts to `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            record_on_launch: false,
+            recording: None,
+        }
+    }
+
+    /// Start recording the first window as soon as it is created instead of waiting for the shortcut.
+    pub fn with_recording(mut self, recording: bool) -> Self {
+        self.record_on_launch = recording;
+        self
+    }
+
+    fn start(&mut self, window: &Window) {
+        let size = window.inner_size().to_logical::<f32>(window.scale_factor());
+        self.recording = Some(Recording {
+            window_id: window.id(),
+            last_event: Instant::now(),
+            script: InteractionScript::new(Size2D::new(size.width, size.height)),
+        });
+        tracing::info!("Started recording interactions");
+    }
+
+    fn stop(&mut self) {
+        let Some(recording) = self.recording.take() else {
+            return;
+        };
+
+        let result = serde_json::to_string_pretty(&recording.script)
+            .map_err(|err| err.to_string())
+            .and_then(|json| {
+                if let Some(parent) = self.path.parent() {
+                    fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+                }
+                fs::write(&self.path, json).map_err(|err| err.to_string())
+            });
+
+        match result {
+            Ok(()) => tracing::info!(
+                "Saved {} recorded interactions to {}",
+                recording.script.events.len(),
+                self.path.display()
+            ),
+            Err(err) => tracing::error!(
+                "Failed to save the recorded interactions to {}: {err}",
+                self.path.display()
+            ),
+        }
+    }
+}
+
+/// Whether the event is the keyboard shortcut that toggles the recording.
+fn is_toggle_shortcut(event: &PlatformEvent) -> bool {
+    let toggle_modifier = if cfg!(target_os = "macos") {
+        Modifiers::META | Modifiers::SHIFT
+    } else {
+        Modifiers::CONTROL | Modifiers::SHIFT
+    };
+    matches!(event, PlatformEvent::Keyboard { key: Key::Character(c), modifiers, .. } if c.eq_ignore_ascii_case("r") && *modifiers == toggle_modifier)
+}
+
+impl FreyaPlugin for InteractionRecorderPlugin {
+    fn plugin_id(&self) -> &'static str {
+        "freya-interaction-recorder"
+    }
+
+    fn on_event(&mut self, event: &mut PluginEvent, _handle: PluginHandle) {
+        match event {
+            PluginEvent::WindowCreated { window, .. } if self.record_on_launch => {
+                self.record_on_launch = false;
+                self.start(window);
+            }
+            PluginEvent::WindowClosed { window, .. } => {
+                if self
+                    .recording
+                    .as_ref()
+                    .is_some_and(|recording| recording.window_id == window.id())
+                {
+                    self.stop();
+                }
+            }
+            PluginEvent::PlatformEvent { window, event } => {
+                // The shortcut itself is never recorded
+                if is_toggle_shortcut(event) {
+                    if matches!(
+                        event,
+                        PlatformEvent::Keyboard {
+                            name: KeyboardEventName::KeyDown,
+                            ..
+                        }
+                    ) {
+                        if self.recording.is_some() {
+                            self.stop();
+                        } else {
+                            self.start(window);
+                        }
+                    }
+                    return;
+                }
+
+                if let Some(recording) = &mut self.recording
+                    && recording.window_id == window.id()
+                {
+                    let now = Instant::now();
+                    recording.script.push(
+                        now.duration_since(recording.last_event),
+                        (*event).clone().scaled(1. / window.scale_factor()),
+                    );
+                    recording.last_event = now;
+                }
+            }
+            _ => {}
+        }
+    }
+}
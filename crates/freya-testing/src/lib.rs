@@ -32,6 +32,52 @@
 //! ```
 //!
 //! For a runnable example see `examples/testing_events.rs` in the repository.
+//!
+//! # Snapshots
+//!
+//! Catch visual regressions by comparing the rendered app against reference images stored next to the tests:
+//!
+//! ```rust,ignore
+//! test.assert_snapshot("tests/snapshots/button.png");
+//!
+//! // Allow small differences
+//! test.assert_snapshot_with(
+//!     "tests/snapshots/chart.png",
+//!     SnapshotOptions::default().threshold(0.2).max_diff_pixels(10),
+//! );
+//! ```
+//!
+//! Run the tests with `FREYA_UPDATE_SNAPSHOTS=1` to update the reference images after an intended change.
+//!
+//! # Replaying interactions
+//!
+//! Long interaction flows can be recorded in a real window with the `freya-recorder` plugin
+//! and replayed with the same timing, following the timestamps of the script instead of waiting in real time:
+//!
+//! ```rust,ignore
+//! let mut test = TestingRunner::new(app, (800., 600.).into(), |_| {}, 1.).0;
+//! test.replay_file("tests/scripts/checkout.json");
+//! assert!(test.find_by_text("Order placed").is_some());
+//!
+//! // Advance the time in smaller steps between the events, e.g to catch intermediate animation frames
+//! test.replay_with(&script, ReplayOptions::default().step(Duration::from_millis(4)));
+//! ```
+//!
+//! # Accessibility queries
+//!
+//! Prefer querying nodes the way users perceive them, by their accessibility role, name, text or focus,
+//! rather than by their element structure:
+//!
+//! ```rust,ignore
+//! let save = test.find_by_role_and_name(AccessibilityRole::Button, "Save").unwrap();
+//! assert!(!save.is_disabled());
+//!
+//! test.press_key(Key::Named(NamedKey::Tab));
+//! assert!(save.is_focused());
+//!
+//! // Assert the keyboard navigation order
+//! assert_eq!(test.tab_order_snapshot(), ["Button \"Save\"", "Switch"]);
+//! ```
 
 use std::{
     borrow::Cow,
@@ -39,7 +85,10 @@ use std::{
     collections::HashMap,
     fs::File,
     io::Write,
-    path::PathBuf,
+    path::{
+        Path,
+        PathBuf,
+    },
     rc::Rc,
     time::{
         Duration,
@@ -51,33 +100,34 @@ use freya_clipboard::copypasta::{
     ClipboardContext,
     ClipboardProvider,
 };
-use freya_components::{
-    cache::AssetCacher,
-    integration::integration,
-};
 use freya_core::{
     integration::*,
     prelude::*,
 };
 use freya_engine::prelude::{
     EncodedImageFormat,
-    FontCollection,
-    FontMgr,
     SkData,
-    TypefaceFontProvider,
     raster_n32_premul,
 };
+use freya_headless::OffscreenRunner;
 use ragnarok::{
     CursorPoint,
-    EventsExecutorRunner,
     EventsMeasurerRunner,
-    NodesState,
 };
 use torin::prelude::{
     LayoutNode,
     Size2D,
 };
 
+mod replay;
+mod snapshot;
+
+pub use replay::ReplayOptions;
+pub use snapshot::{
+    SnapshotOptions,
+    UPDATE_SNAPSHOTS_ENV,
+};
+
 pub mod prelude {
     pub use freya_core::{
         events::platform::*,
@@ -86,6 +136,8 @@ pub mod prelude {
 
     pub use crate::{
         DocRunner,
+        ReplayOptions,
+        SnapshotOptions,
         TestingRunner,
         launch_doc,
         launch_test,
@@ -147,26 +199,8 @@ pub fn launch_test(app: impl Into<AppComponent>) -> TestingRunner {
 }
 
 pub struct TestingRunner {
-    nodes_state: NodesState<NodeId>,
-    runner: Runner,
-    tree: Rc<RefCell<Tree>>,
-    size: Size2D,
-
-    accessibility: AccessibilityTree,
-
-    events_receiver: futures_channel::mpsc::UnboundedReceiver<EventsChunk>,
-    events_sender: futures_channel::mpsc::UnboundedSender<EventsChunk>,
-
-    font_manager: FontMgr,
-    font_collection: FontCollection,
-
-    platform: Platform,
-
-    animation_clock: AnimationClock,
-    ticker_sender: RenderingTickerSender,
-
-    default_fonts: Vec<Cow<'static, str>>,
-    scale_factor: f64,
+    offscreen_runner: OffscreenRunner,
+    events_recognizer: EventsRecognizer,
 }
 
 impl TestingRunner {
@@ -176,195 +210,55 @@ impl TestingRunner {
         hook: impl FnOnce(&mut Runner) -> T,
         scale_factor: f64,
     ) -> (Self, T) {
-        let (events_sender, events_receiver) = futures_channel::mpsc::unbounded();
-        let app = app.into();
-        let mut runner = Runner::new(move || integration(app.clone()).into_element());
-
-        runner.provide_root_context(ScreenReader::new);
-
-        let (mut ticker_sender, ticker) = RenderingTicker::new();
-        ticker_sender.set_overflow(true);
-        runner.provide_root_context(|| ticker);
-
-        let animation_clock = runner.provide_root_context(AnimationClock::new);
-
-        runner.provide_root_context(AssetCacher::create);
-
-        let tree = Tree::default();
-        let tree = Rc::new(RefCell::new(tree));
-
-        let platform = runner.provide_root_context({
-            let tree = tree.clone();
-            || Platform {
-                focused_accessibility_id: State::create(ACCESSIBILITY_ROOT_ID),
-                focused_accessibility_node: State::create(accesskit::Node::new(
-                    accesskit::Role::Window,
-                )),
-                root_size: State::create(size),
-                navigation_mode: State::create(NavigationMode::NotKeyboard),
-                preferred_theme: State::create(PreferredTheme::Light),
-                sender: Rc::new(move |user_event| {
-                    match user_event {
-                        UserEvent::RequestRedraw => {
-                            // Nothing
-                        }
-                        UserEvent::FocusAccessibilityNode(strategy) => {
-                            tree.borrow_mut().accessibility_diff.request_focus(strategy);
-                        }
-                        UserEvent::SetCursorIcon(_) => {
-                            // Nothing
-                        }
-                        UserEvent::Erased(_) => {
-                            // Nothing
-                        }
-                    }
-                }),
-            }
-        });
-
-        runner.provide_root_context(|| {
-            let clipboard: Option<Box<dyn ClipboardProvider>> = ClipboardContext::new()
+        let clipboard = || {
+            ClipboardContext::new()
                 .ok()
-                .map(|c| Box::new(c) as Box<dyn ClipboardProvider>);
-
-            State::create(clipboard)
-        });
-
-        runner.provide_root_context(|| tree.borrow().accessibility_generator.clone());
-
-        let hook_result = hook(&mut runner);
-
-        let mut font_collection = FontCollection::new();
-        let def_mgr = FontMgr::default();
-        let provider = TypefaceFontProvider::new();
-        let font_manager: FontMgr = provider.into();
-        font_collection.set_default_font_manager(def_mgr, None);
-        font_collection.set_dynamic_font_manager(font_manager.clone());
-        font_collection.paragraph_cache_mut().turn_on(false);
-
-        runner.provide_root_context(|| font_collection.clone());
-
-        let nodes_state = NodesState::default();
-        let accessibility = AccessibilityTree::default();
-
-        let mut runner = Self {
-            runner,
-            tree,
-            size,
-
-            accessibility,
-            platform,
-
-            nodes_state,
-            events_receiver,
-            events_sender,
-
-            font_manager,
-            font_collection,
-
-            animation_clock,
-            ticker_sender,
-
-            default_fonts: default_fonts(),
-            scale_factor,
+                .map(|c| Box::new(c) as Box<dyn ClipboardProvider>)
         };
+        let (offscreen_runner, hook_result) =
+            OffscreenRunner::new(app, size, scale_factor, clipboard, hook);
 
-        runner.sync_and_update();
+        let runner = Self {
+            offscreen_runner,
+            events_recognizer: EventsRecognizer::default(),
+        };
 
         (runner, hook_result)
     }
 
     pub fn set_fonts(&mut self, fonts: HashMap<&str, &[u8]>) {
-        let mut provider = TypefaceFontProvider::new();
-        for (font_name, font_data) in fonts {
-            let ft_type = self
-                .font_collection
-                .fallback_manager()
-                .unwrap()
-                .new_from_data(font_data, None)
-                .unwrap_or_else(|| panic!("Failed to load font {font_name}."));
-            provider.register_typeface(ft_type, Some(font_name));
-        }
-        let font_manager: FontMgr = provider.into();
-        self.font_manager = font_manager.clone();
-        self.font_collection.set_dynamic_font_manager(font_manager);
+        self.offscreen_runner
+            .set_fonts(fonts)
+            .unwrap_or_else(|err| panic!("{err}."));
     }
 
     pub fn set_default_fonts(&mut self, fonts: &[Cow<'static, str>]) {
-        self.default_fonts.clear();
-        self.default_fonts.extend_from_slice(fonts);
-        self.tree.borrow_mut().layout.reset();
-        self.tree.borrow_mut().text_cache.reset();
-        self.tree.borrow_mut().measure_layout(
-            self.size,
-            &mut self.font_collection,
-            &self.font_manager,
-            &self.events_sender,
-            self.scale_factor,
-            &self.default_fonts,
-        );
-        self.tree.borrow_mut().accessibility_diff.clear();
-        self.accessibility.focused_id = ACCESSIBILITY_ROOT_ID;
-        self.accessibility.init(&mut self.tree.borrow_mut());
+        self.offscreen_runner.default_fonts.clear();
+        self.offscreen_runner.default_fonts.extend_from_slice(fonts);
+        self.offscreen_runner.remeasure();
+        let offscreen_runner = &mut self.offscreen_runner;
+        offscreen_runner
+            .tree
+            .borrow_mut()
+            .accessibility_diff
+            .clear();
+        offscreen_runner.accessibility.focused_id = ACCESSIBILITY_ROOT_ID;
+        offscreen_runner
+            .accessibility
+            .init(&mut offscreen_runner.tree.borrow_mut());
         self.sync_and_update();
     }
 
     pub async fn handle_events(&mut self) {
-        self.runner.handle_events().await
+        self.offscreen_runner.runner.handle_events().await
     }
 
     pub fn handle_events_immediately(&mut self) {
-        self.runner.handle_events_immediately()
+        self.offscreen_runner.runner.handle_events_immediately()
     }
 
     pub fn sync_and_update(&mut self) {
-        while let Ok(events_chunk) = self.events_receiver.try_recv() {
-            match events_chunk {
-                EventsChunk::Processed(processed_events) => {
-                    let events_executor_adapter = EventsExecutorAdapter {
-                        runner: &mut self.runner,
-                    };
-                    events_executor_adapter.run(&mut self.nodes_state, processed_events);
-                }
-                EventsChunk::Batch(events) => {
-                    for event in events {
-                        self.runner.handle_event(
-                            event.node_id,
-                            event.name,
-                            event.data,
-                            event.bubbles,
-                        );
-                    }
-                }
-            }
-        }
-
-        let mutations = self.runner.sync_and_update();
-        self.runner.run_in(|| {
-            self.tree.borrow_mut().apply_mutations(mutations);
-        });
-        self.tree.borrow_mut().measure_layout(
-            self.size,
-            &mut self.font_collection,
-            &self.font_manager,
-            &self.events_sender,
-            self.scale_factor,
-            &self.default_fonts,
-        );
-
-        let accessibility_update = self
-            .accessibility
-            .process_updates(&mut self.tree.borrow_mut(), &self.events_sender);
-
-        self.platform
-            .focused_accessibility_id
-            .set_if_modified(accessibility_update.focus);
-        let node_id = self.accessibility.focused_node_id().unwrap();
-        let tree = self.tree.borrow();
-        let layout_node = tree.layout.get(&node_id).unwrap();
-        self.platform
-            .focused_accessibility_node
-            .set_if_modified(AccessibilityTree::create_node(node_id, layout_node, &tree));
+        self.offscreen_runner.sync_and_update();
     }
 
     /// Poll async tasks and events every `step` time for a total time of `duration`.
@@ -372,10 +266,14 @@ impl TestingRunner {
     pub fn poll(&mut self, step: Duration, duration: Duration) {
         let started = Instant::now();
         while started.elapsed() < duration {
+            self.send_due_events();
             self.handle_events_immediately();
             self.sync_and_update();
             std::thread::sleep(step);
-            self.ticker_sender.broadcast_blocking(()).unwrap();
+            self.offscreen_runner
+                .ticker_sender
+                .broadcast_blocking(())
+                .unwrap();
         }
     }
 
@@ -383,24 +281,45 @@ impl TestingRunner {
     /// This is useful for animations for instance.
     pub fn poll_n(&mut self, step: Duration, times: u32) {
         for _ in 0..times {
+            self.send_due_events();
             self.handle_events_immediately();
             self.sync_and_update();
             std::thread::sleep(step);
-            self.ticker_sender.broadcast_blocking(()).unwrap();
+            self.offscreen_runner
+                .ticker_sender
+                .broadcast_blocking(())
+                .unwrap();
         }
     }
 
     pub fn send_event(&mut self, platform_event: PlatformEvent) {
+        let mut platform_events = vec![platform_event];
+        let now = self.offscreen_runner.animation_clock.now();
+        self.events_recognizer.recognize(&mut platform_events, now);
+        self.measure_events(platform_events);
+    }
+
+    /// Send the events that became due with the pass of time, e.g long presses.
+    fn send_due_events(&mut self) {
+        let now = self.offscreen_runner.animation_clock.now();
+        let platform_events = self.events_recognizer.poll(now);
+        if !platform_events.is_empty() {
+            self.measure_events(platform_events);
+        }
+    }
+
+    fn measure_events(&mut self, mut platform_events: Vec<PlatformEvent>) {
         let mut events_measurer_adapter = EventsMeasurerAdapter {
-            tree: &mut self.tree.borrow_mut(),
-            scale_factor: self.scale_factor,
+            tree: &mut self.offscreen_runner.tree.borrow_mut(),
+            scale_factor: self.offscreen_runner.scale_factor,
         };
         let processed_events = events_measurer_adapter.run(
-            &mut vec![platform_event],
-            &mut self.nodes_state,
-            self.accessibility.focused_node_id(),
+            &mut platform_events,
+            &mut self.offscreen_runner.nodes_state,
+            self.offscreen_runner.accessibility.focused_node_id(),
         );
-        self.events_sender
+        self.offscreen_runner
+            .events_sender
             .unbounded_send(EventsChunk::Processed(processed_events))
             .unwrap();
     }
@@ -482,23 +401,67 @@ impl TestingRunner {
         self.sync_and_update();
     }
 
+    /// Replay the events of an [InteractionScript] with the timing it was recorded with.
+    ///
+    /// The time is virtual during the replay, the [AnimationClock] and the recognition of timed events
+    /// such as double clicks or long presses follow the timestamps of the script instead of the real time,
+    /// so the replay is deterministic and does not take as long as it was recorded.
+    /// Timers of async tasks still run in real time.
+    ///
+    /// Locations are scaled by the scale factor of the runner, which should have the same size as the script.
+    pub fn replay(&mut self, script: &InteractionScript) {
+        self.replay_with(script, ReplayOptions::default())
+    }
+
+    /// Like [TestingRunner::replay] but with custom [ReplayOptions].
+    pub fn replay_with(&mut self, script: &InteractionScript, options: ReplayOptions) {
+        let animation_clock = self.offscreen_runner.animation_clock.clone();
+        let mut now = animation_clock.now();
+        animation_clock.set_manual_time(Some(now));
+
+        for ScriptedEvent { delay, event } in &script.events {
+            let due = now + *delay;
+            loop {
+                self.send_due_events();
+                self.handle_events_immediately();
+                self.sync_and_update();
+                if now >= due {
+                    break;
+                }
+                now = (now + options.step).min(due);
+                animation_clock.set_manual_time(Some(now));
+                self.offscreen_runner
+                    .ticker_sender
+                    .broadcast_blocking(())
+                    .unwrap();
+            }
+
+            self.send_event(event.clone().scaled(self.offscreen_runner.scale_factor));
+            self.sync_and_update();
+        }
+
+        animation_clock.set_manual_time(None);
+    }
+
+    /// Replay an [InteractionScript] saved as JSON, e.g recorded with the `freya-recorder` plugin.
+    /// The `path` is relative to the package root when running `cargo test`.
+    pub fn replay_file(&mut self, path: impl AsRef<Path>) {
+        let script = replay::load_script(path.as_ref());
+        self.replay(&script)
+    }
+
     pub fn animation_clock(&mut self) -> &mut AnimationClock {
-        &mut self.animation_clock
+        &mut self.offscreen_runner.animation_clock
     }
 
     pub fn render(&mut self) -> SkData {
-        let mut surface = raster_n32_premul((self.size.width as i32, self.size.height as i32))
-            .expect("Failed to create the surface.");
-
-        let render_pipeline = RenderPipeline {
-            font_collection: &mut self.font_collection,
-            font_manager: &self.font_manager,
-            tree: &self.tree.borrow(),
-            canvas: surface.canvas(),
-            scale_factor: self.scale_factor,
-            background: Color::WHITE,
-        };
-        render_pipeline.render();
+        let mut surface = raster_n32_premul((
+            self.offscreen_runner.size.width as i32,
+            self.offscreen_runner.size.height as i32,
+        ))
+        .expect("Failed to create the surface.");
+
+        self.offscreen_runner.render(surface.canvas(), Color::WHITE);
 
         let image = surface.image_snapshot();
         let mut context = surface.direct_context();
@@ -519,23 +482,36 @@ impl TestingRunner {
             .expect("Failed to save the snapshot file.");
     }
 
+    /// Render the app and compare it against the reference PNG at `path`, which is relative to the package root
+    /// when running `cargo test`, e.g `tests/snapshots/button.png`.
+    ///
+    /// The reference is created if it does not exist yet (except when the `CI` environment variable is set),
+    /// and overwritten if the [UPDATE_SNAPSHOTS_ENV] environment variable is set.
+    ///
+    /// Panics if the snapshot differs, saving the rendered image and a diff image next to the reference.
+    pub fn assert_snapshot(&mut self, path: impl AsRef<Path>) {
+        self.assert_snapshot_with(path, SnapshotOptions::default())
+    }
+
+    /// Like [TestingRunner::assert_snapshot] but with custom [SnapshotOptions].
+    pub fn assert_snapshot_with(&mut self, path: impl AsRef<Path>, options: SnapshotOptions) {
+        let snapshot = self.render();
+        snapshot::assert_snapshot(&snapshot, path.as_ref(), &options);
+    }
+
     pub fn find<T>(
         &self,
         matcher: impl Fn(TestingNode, &dyn ElementExt) -> Option<T>,
     ) -> Option<T> {
         let mut matched = None;
         {
-            let tree = self.tree.borrow();
+            let tree = self.offscreen_runner.tree.borrow();
             tree.traverse_depth(|id| {
                 if matched.is_some() {
                     return;
                 }
                 let element = tree.elements.get(&id).unwrap();
-                let node = TestingNode {
-                    tree: self.tree.clone(),
-                    id,
-                };
-                matched = matcher(node, element.as_ref());
+                matched = matcher(self.node(id), element.as_ref());
             });
         }
 
@@ -548,14 +524,10 @@ impl TestingRunner {
     ) -> Vec<T> {
         let mut matched = Vec::new();
         {
-            let tree = self.tree.borrow();
+            let tree = self.offscreen_runner.tree.borrow();
             tree.traverse_depth(|id| {
                 let element = tree.elements.get(&id).unwrap();
-                let node = TestingNode {
-                    tree: self.tree.clone(),
-                    id,
-                };
-                if let Some(result) = matcher(node, element.as_ref()) {
+                if let Some(result) = matcher(self.node(id), element.as_ref()) {
                     matched.push(result);
                 }
             });
@@ -563,10 +535,101 @@ impl TestingRunner {
 
         matched
     }
+
+    fn node(&self, id: NodeId) -> TestingNode {
+        TestingNode {
+            tree: self.offscreen_runner.tree.clone(),
+            focused_id: self.offscreen_runner.platform.focused_accessibility_id,
+            id,
+        }
+    }
+
+    /// Find the first node whose accessibility node matches the given predicate.
+    pub fn find_by_accessibility(
+        &self,
+        matcher: impl Fn(&accesskit::Node) -> bool,
+    ) -> Option<TestingNode> {
+        self.find(|node, _| matcher(&node.accessibility_node()).then_some(node))
+    }
+
+    /// Find all the nodes whose accessibility node matches the given predicate.
+    pub fn find_all_by_accessibility(
+        &self,
+        matcher: impl Fn(&accesskit::Node) -> bool,
+    ) -> Vec<TestingNode> {
+        self.find_many(|node, _| matcher(&node.accessibility_node()).then_some(node))
+    }
+
+    /// Find the first node with the given accessibility role, e.g [AccessibilityRole::Button].
+    pub fn find_by_role(&self, role: AccessibilityRole) -> Option<TestingNode> {
+        self.find_by_accessibility(|node| node.role() == role)
+    }
+
+    /// Find all the nodes with the given accessibility role.
+    pub fn find_all_by_role(&self, role: AccessibilityRole) -> Vec<TestingNode> {
+        self.find_all_by_accessibility(|node| node.role() == role)
+    }
+
+    /// Find the first node with the given accessibility role and name, see [TestingNode::name].
+    pub fn find_by_role_and_name(
+        &self,
+        role: AccessibilityRole,
+        name: &str,
+    ) -> Option<TestingNode> {
+        self.find_by_accessibility(|node| node.role() == role && node.label() == Some(name))
+    }
+
+    /// Find the first node with the given accessibility name, see [TestingNode::name].
+    pub fn find_by_name(&self, name: &str) -> Option<TestingNode> {
+        self.find_by_accessibility(|node| node.label() == Some(name))
+    }
+
+    /// Find the first `label` or `paragraph` with the given text.
+    pub fn find_by_text(&self, text: &str) -> Option<TestingNode> {
+        self.find(|node, _| (node.text().as_deref() == Some(text)).then_some(node))
+    }
+
+    /// Find the node that is currently focused, if any.
+    pub fn find_focused(&self) -> Option<TestingNode> {
+        if self.offscreen_runner.accessibility.focused_id == ACCESSIBILITY_ROOT_ID {
+            return None;
+        }
+        self.offscreen_runner
+            .accessibility
+            .focused_node_id()
+            .map(|node_id| self.node(node_id))
+    }
+
+    /// Get the nodes that are focused when pressing `Tab`, in order.
+    pub fn tab_order(&self) -> Vec<TestingNode> {
+        let tree = self.offscreen_runner.tree.borrow();
+        AccessibilityTree::navigable_nodes(&tree)
+            .into_iter()
+            .filter_map(|a11y_id| self.offscreen_runner.accessibility.map.get(&a11y_id))
+            .map(|node_id| self.node(*node_id))
+            .collect()
+    }
+
+    /// Describe the nodes that are focused when pressing `Tab` by their role and name, in order.
+    /// Useful to assert that the keyboard navigation of an app does not change unexpectedly.
+    ///
+    /// ```rust,ignore
+    /// assert_eq!(test.tab_order_snapshot(), ["Button \"Cancel\"", "Button \"Save\"", "Switch"]);
+    /// ```
+    pub fn tab_order_snapshot(&self) -> Vec<String> {
+        self.tab_order()
+            .into_iter()
+            .map(|node| match node.name() {
+                Some(name) => format!("{:?} {name:?}", node.role()),
+                None => format!("{:?}", node.role()),
+            })
+            .collect()
+    }
 }
 
 pub struct TestingNode {
     tree: Rc<RefCell<Tree>>,
+    focused_id: State<AccessibilityId>,
     id: NodeId,
 }
 
@@ -589,6 +652,7 @@ impl TestingNode {
             .map(|child_id| Self {
                 id: child_id,
                 tree: self.tree.clone(),
+                focused_id: self.focused_id,
             })
             .collect()
     }
@@ -614,4 +678,63 @@ impl TestingNode {
             .cloned()
             .expect("Element does not exist.")
     }
+
+    pub fn accessibility_id(&self) -> AccessibilityId {
+        self.tree
+            .borrow()
+            .accessibility_state
+            .get(&self.id)
+            .expect("Element does not exist.")
+            .a11y_id
+    }
+
+    /// The accessibility node as seen by assistive technologies.
+    pub fn accessibility_node(&self) -> accesskit::Node {
+        let tree = self.tree.borrow();
+        let layout_node = tree.layout.get(&self.id).expect("Element does not exist.");
+        AccessibilityTree::create_node(self.id, layout_node, &tree)
+    }
+
+    pub fn role(&self) -> AccessibilityRole {
+        self.accessibility_node().role()
+    }
+
+    /// The accessible name, either set with `a11y_alt` or taken from the text of its `label` or `paragraph` children.
+    pub fn name(&self) -> Option<String> {
+        self.accessibility_node().label().map(str::to_string)
+    }
+
+    /// The text of this node if it is a `label` or a `paragraph`.
+    pub fn text(&self) -> Option<String> {
+        let element = self.element();
+        if let Some(label) = Label::try_downcast(element.as_ref()) {
+            Some(label.text.to_string())
+        } else {
+            Paragraph::try_downcast(element.as_ref()).map(|paragraph| {
+                paragraph
+                    .spans
+                    .iter()
+                    .map(|span| span.text.to_string())
+                    .collect()
+            })
+        }
+    }
+
+    pub fn is_focused(&self) -> bool {
+        *self.focused_id.peek() == self.accessibility_id()
+    }
+
+    pub fn is_focusable(&self) -> bool {
+        self.tree
+            .borrow()
+            .accessibility_state
+            .get(&self.id)
+            .expect("Element does not exist.")
+            .a11y_focusable
+            .is_enabled()
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.accessibility_node().is_disabled()
+    }
 }
@@ -0,0 +1,121 @@
+use std::{
+    fs,
+    path::Path,
+    time::Duration,
+};
+
+use freya_core::integration::InteractionScript;
+
+/// How an [InteractionScript] is replayed,
+/// see [TestingRunner::replay_with](crate::TestingRunner::replay_with).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ReplayOptions {
+    pub(crate) step: Duration,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            step: Duration::from_millis(16),
+        }
+    }
+}
+
+impl ReplayOptions {
+    /// How much the virtual time advances between each poll of async tasks, events and animations
+    /// while waiting for the next event. Defaults to `16ms`, and it is never less than `1ms`.
+    pub fn step(self, step: Duration) -> Self {
+        Self {
+            step: step.max(Duration::from_millis(1)),
+        }
+    }
+}
+
+/// Load an [InteractionScript] saved as JSON, e.g by `freya-recorder`.
+pub(crate) fn load_script(path: &Path) -> InteractionScript {
+    let script = fs::read_to_string(path).unwrap_or_else(|err| {
+        panic!(
+            "Failed to read the interaction script {}: {err}",
+            path.display()
+        )
+    });
+    serde_json::from_str(&script).unwrap_or_else(|err| {
+        panic!(
+            "Failed to parse the interaction script {}: {err}",
+            path.display()
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use freya_core::{
+        integration::{
+            KeyboardEventName,
+            MouseButton,
+            MouseEventName,
+            PlatformEvent,
+        },
+        prelude::{
+            Code,
+            Key,
+            Modifiers,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn script_round_trip() {
+        let mut script = InteractionScript::new((300., 200.).into());
+        script.push(
+            Duration::from_millis(120),
+            PlatformEvent::Mouse {
+                name: MouseEventName::MouseDown,
+                cursor: (15., 30.).into(),
+                button: Some(MouseButton::Left),
+            },
+        );
+        script.push(
+            Duration::from_millis(300),
+            PlatformEvent::Keyboard {
+                name: KeyboardEventName::KeyDown,
+                key: Key::Character("a".to_string()),
+                code: Code::KeyA,
+                modifiers: Modifiers::SHIFT,
+            },
+        );
+
+        let json = serde_json::to_string(&script).unwrap();
+        assert_eq!(
+            serde_json::from_str::<InteractionScript>(&json).unwrap(),
+            script
+        );
+        assert_eq!(script.duration(), Duration::from_millis(420));
+    }
+
+    #[test]
+    fn zero_step() {
+        assert_eq!(
+            ReplayOptions::default().step(Duration::ZERO).step,
+            Duration::from_millis(1)
+        );
+    }
+
+    #[test]
+    fn scaled_events() {
+        let event = PlatformEvent::Mouse {
+            name: MouseEventName::MouseMove,
+            cursor: (15., 30.).into(),
+            button: None,
+        };
+        assert_eq!(
+            event.scaled(2.),
+            PlatformEvent::Mouse {
+                name: MouseEventName::MouseMove,
+                cursor: (30., 60.).into(),
+                button: None,
+            }
+        );
+    }
+}
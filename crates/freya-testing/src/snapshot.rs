@@ -0,0 +1,315 @@
+use std::{
+    fs,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use image::{
+    Rgba,
+    RgbaImage,
+};
+
+/// Environment variable that, when set, makes snapshot assertions overwrite the reference images instead of comparing them.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "FREYA_UPDATE_SNAPSHOTS";
+
+/// How tolerant is the comparison of a snapshot against its reference image,
+/// see [TestingRunner::assert_snapshot_with](crate::TestingRunner::assert_snapshot_with).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SnapshotOptions {
+    threshold: f32,
+    anti_aliasing: bool,
+    max_diff_pixels: usize,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 0.1,
+            anti_aliasing: true,
+            max_diff_pixels: 0,
+        }
+    }
+}
+
+impl SnapshotOptions {
+    /// How different can the color of two pixels be while still considered equal,
+    /// from `0.` (exactly equal) to `1.` (any color). Defaults to `0.1`.
+    pub fn threshold(self, threshold: f32) -> Self {
+        Self { threshold, ..self }
+    }
+
+    /// Ignore differing pixels that look like anti-aliasing, meaning they are on the edge between a darker and a brighter
+    /// flat region, as these usually change with subpixel layout changes. Enabled by default.
+    pub fn anti_aliasing(self, anti_aliasing: bool) -> Self {
+        Self {
+            anti_aliasing,
+            ..self
+        }
+    }
+
+    /// How many differing pixels are allowed. Defaults to `0`.
+    pub fn max_diff_pixels(self, max_diff_pixels: usize) -> Self {
+        Self {
+            max_diff_pixels,
+            ..self
+        }
+    }
+}
+
+/// Result of comparing two images.
+pub(crate) struct SnapshotDiff {
+    pub diff_pixels: usize,
+    pub anti_aliased_pixels: usize,
+    pub image: RgbaImage,
+}
+
+fn is_similar(a: &Rgba<u8>, b: &Rgba<u8>, threshold: f32) -> bool {
+    a.0.iter()
+        .zip(b.0.iter())
+        .all(|(a, b)| a.abs_diff(*b) as f32 / 255. <= threshold)
+}
+
+/// Perceived brightness of a pixel.
+fn brightness(pixel: &Rgba<u8>) -> f32 {
+    let [r, g, b, _] = pixel.0;
+    0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32
+}
+
+/// Neighbour pixels of the given one, without going outside of the image.
+fn neighbours(image: &RgbaImage, x: u32, y: u32) -> impl Iterator<Item = (u32, u32)> {
+    let (width, height) = image.dimensions();
+    (x.saturating_sub(1)..=(x + 1).min(width - 1))
+        .flat_map(move |nx| (y.saturating_sub(1)..=(y + 1).min(height - 1)).map(move |ny| (nx, ny)))
+        .filter(move |&point| point != (x, y))
+}
+
+/// Check if the given pixel of `image` sits in a flat region, meaning more than 2 of its neighbours have the same color.
+fn has_many_siblings(image: &RgbaImage, x: u32, y: u32) -> bool {
+    let pixel = image.get_pixel(x, y);
+    neighbours(image, x, y)
+        .filter(|&(nx, ny)| image.get_pixel(nx, ny) == pixel)
+        .count()
+        > 2
+}
+
+/// Check if the given pixel of `image` looks like anti-aliasing, in the way of `pixelmatch`:
+/// its brightness lies between the ones of its darkest and brightest neighbours,
+/// and one of these sits in a flat region of both images.
+fn is_anti_aliased(image: &RgbaImage, other: &RgbaImage, x: u32, y: u32) -> bool {
+    let pixel = image.get_pixel(x, y);
+    let center = brightness(pixel);
+
+    let mut equal = 0;
+    let mut darkest: Option<(f32, (u32, u32))> = None;
+    let mut brightest: Option<(f32, (u32, u32))> = None;
+    for (nx, ny) in neighbours(image, x, y) {
+        let neighbour = image.get_pixel(nx, ny);
+        if neighbour == pixel {
+            equal += 1;
+            // Too many equal neighbours means this is part of a shape, not an edge
+            if equal > 2 {
+                return false;
+            }
+            continue;
+        }
+        let delta = brightness(neighbour) - center;
+        if delta < 0. && darkest.is_none_or(|(min, _)| delta < min) {
+            darkest = Some((delta, (nx, ny)));
+        } else if delta > 0. && brightest.is_none_or(|(max, _)| delta > max) {
+            brightest = Some((delta, (nx, ny)));
+        }
+    }
+
+    // Anti-aliased pixels are between a darker and a brighter neighbour
+    let (Some((_, darkest)), Some((_, brightest))) = (darkest, brightest) else {
+        return false;
+    };
+
+    [darkest, brightest]
+        .into_iter()
+        .any(|(nx, ny)| has_many_siblings(image, nx, ny) && has_many_siblings(other, nx, ny))
+}
+
+/// Compare two images of the same size, producing an image where the differing pixels are red,
+/// the anti-aliased pixels are yellow and the rest is a faded version of the `expected` image.
+pub(crate) fn diff_images(
+    expected: &RgbaImage,
+    actual: &RgbaImage,
+    options: &SnapshotOptions,
+) -> SnapshotDiff {
+    let mut diff_pixels = 0;
+    let mut anti_aliased_pixels = 0;
+    let image = RgbaImage::from_fn(expected.width(), expected.height(), |x, y| {
+        let expected_pixel = expected.get_pixel(x, y);
+        let actual_pixel = actual.get_pixel(x, y);
+
+        if is_similar(expected_pixel, actual_pixel, options.threshold) {
+            let luma = brightness(expected_pixel) as u8;
+            let faded = 255 - (255 - luma) / 4;
+            return Rgba([faded, faded, faded, 255]);
+        }
+
+        let anti_aliased = options.anti_aliasing
+            && (is_anti_aliased(expected, actual, x, y) || is_anti_aliased(actual, expected, x, y));
+
+        if anti_aliased {
+            anti_aliased_pixels += 1;
+            Rgba([255, 200, 0, 255])
+        } else {
+            diff_pixels += 1;
+            Rgba([255, 0, 0, 255])
+        }
+    });
+
+    SnapshotDiff {
+        diff_pixels,
+        anti_aliased_pixels,
+        image,
+    }
+}
+
+/// Path next to the snapshot with the given suffix, e.g `button.png` -> `button.diff.png`.
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{stem}.{suffix}.png"))
+}
+
+/// Compare the `snapshot` PNG against the reference PNG at `path`, panicking if they differ.
+pub(crate) fn assert_snapshot(snapshot: &[u8], path: &Path, options: &SnapshotOptions) {
+    let actual_path = sibling_path(path, "actual");
+    let diff_path = sibling_path(path, "diff");
+
+    let write_reference = || {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("Failed to create the snapshots directory.");
+        }
+        fs::write(path, snapshot).expect("Failed to save the snapshot.");
+        fs::remove_file(&actual_path).ok();
+        fs::remove_file(&diff_path).ok();
+    };
+
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+        write_reference();
+        return;
+    }
+
+    let Ok(expected) = fs::read(path) else {
+        // Missing references are only created locally, in CI they must be committed
+        if std::env::var_os("CI").is_some() {
+            panic!(
+                "Snapshot {} does not exist, run the tests with {UPDATE_SNAPSHOTS_ENV}=1 to create it.",
+                path.display()
+            );
+        }
+        write_reference();
+        return;
+    };
+
+    let expected = image::load_from_memory(&expected)
+        .expect("Failed to decode the reference snapshot.")
+        .to_rgba8();
+    let actual = image::load_from_memory(snapshot)
+        .expect("Failed to decode the snapshot.")
+        .to_rgba8();
+
+    if expected.dimensions() != actual.dimensions() {
+        fs::write(&actual_path, snapshot).expect("Failed to save the snapshot.");
+        panic!(
+            "Snapshot {} has a size of {:?} but the reference is {:?}, see {}.",
+            path.display(),
+            actual.dimensions(),
+            expected.dimensions(),
+            actual_path.display()
+        );
+    }
+
+    let diff = diff_images(&expected, &actual, options);
+    if diff.diff_pixels > options.max_diff_pixels {
+        fs::write(&actual_path, snapshot).expect("Failed to save the snapshot.");
+        diff.image
+            .save(&diff_path)
+            .expect("Failed to save the snapshot diff.");
+        panic!(
+            "Snapshot {} differs from the reference in {} pixels (max {}, ignoring {} anti-aliased), see {} and {}. Run the tests with {UPDATE_SNAPSHOTS_ENV}=1 to update it.",
+            path.display(),
+            diff.diff_pixels,
+            options.max_diff_pixels,
+            diff.anti_aliased_pixels,
+            actual_path.display(),
+            diff_path.display()
+        );
+    }
+
+    fs::remove_file(&actual_path).ok();
+    fs::remove_file(&diff_path).ok();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+    const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+    /// White image with a black vertical line at the given column.
+    fn line(x: u32) -> RgbaImage {
+        RgbaImage::from_fn(5, 5, |px, _| if px == x { BLACK } else { WHITE })
+    }
+
+    #[test]
+    fn equal_images() {
+        let diff = diff_images(&line(2), &line(2), &SnapshotOptions::default());
+        assert_eq!(diff.diff_pixels, 0);
+        assert_eq!(diff.anti_aliased_pixels, 0);
+    }
+
+    #[test]
+    fn threshold() {
+        let actual = RgbaImage::from_pixel(5, 5, Rgba([240, 240, 240, 255]));
+        let expected = RgbaImage::from_pixel(5, 5, WHITE);
+
+        let diff = diff_images(&expected, &actual, &SnapshotOptions::default());
+        assert_eq!(diff.diff_pixels, 0);
+
+        let diff = diff_images(
+            &expected,
+            &actual,
+            &SnapshotOptions::default().threshold(0.),
+        );
+        assert_eq!(diff.diff_pixels, 25);
+    }
+
+    #[test]
+    fn anti_aliasing() {
+        // A softer edge between two flat regions is anti-aliasing
+        let expected = RgbaImage::from_fn(5, 5, |x, _| if x < 2 { WHITE } else { BLACK });
+        let mut actual = expected.clone();
+        for y in 0..5 {
+            actual.put_pixel(2, y, Rgba([128, 128, 128, 255]));
+        }
+        let diff = diff_images(&expected, &actual, &SnapshotOptions::default());
+        assert_eq!(diff.diff_pixels, 0);
+        assert_eq!(diff.anti_aliased_pixels, 5);
+
+        let diff = diff_images(
+            &expected,
+            &actual,
+            &SnapshotOptions::default().anti_aliasing(false),
+        );
+        assert_eq!(diff.diff_pixels, 5);
+
+        // But a line moved by one pixel is not
+        let diff = diff_images(&line(2), &line(3), &SnapshotOptions::default());
+        assert_eq!(diff.diff_pixels, 10);
+        assert_eq!(diff.anti_aliased_pixels, 0);
+
+        // Nor a color that is nowhere around
+        let mut actual = line(2);
+        actual.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+        let diff = diff_images(&line(2), &actual, &SnapshotOptions::default());
+        assert_eq!(diff.diff_pixels, 1);
+        assert_eq!(diff.image.get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+    }
+}
@@ -1,6 +1,10 @@
 use std::{
     ffi::CString,
     num::NonZeroU32,
+    panic::{
+        self,
+        AssertUnwindSafe,
+    },
 };
 
 use freya_engine::prelude::{
@@ -21,6 +25,7 @@ use gl::{
 };
 use glutin::{
     config::{
+        Config,
         ConfigTemplateBuilder,
         GlConfig,
     },
@@ -57,6 +62,21 @@ use winit::{
     },
 };
 
+/// Pick the config with the fewest samples, preferring the ones with transparency if requested.
+fn pick_config(configs: impl Iterator<Item = Config>, transparent: bool) -> Option<Config> {
+    configs.reduce(|accum, config| {
+        let transparency_check = transparent
+            && config.supports_transparency().unwrap_or(false)
+            && !accum.supports_transparency().unwrap_or(false);
+
+        if transparency_check || config.num_samples() < accum.num_samples() {
+            config
+        } else {
+            accum
+        }
+    })
+}
+
 /// Graphics driver using OpenGL.
 pub struct OpenGLDriver {
     pub(crate) gr_context: DirectContext,
@@ -78,34 +98,24 @@ impl OpenGLDriver {
     pub fn new(
         event_loop: &ActiveEventLoop,
         window_attributes: WindowAttributes,
-    ) -> (Self, Window) {
+    ) -> Result<(Self, Window), Box<dyn std::error::Error>> {
         let transparent = window_attributes.transparent;
         let template = ConfigTemplateBuilder::new()
             .with_alpha_size(8)
             .with_transparency(window_attributes.transparent);
 
         let display_builder = DisplayBuilder::new().with_window_attributes(Some(window_attributes));
-        let (window, gl_config) = display_builder
-            .build(event_loop, template, |configs| {
-                configs
-                    .reduce(|accum, config| {
-                        let transparency_check = transparent
-                            && config.supports_transparency().unwrap_or(false)
-                            && !accum.supports_transparency().unwrap_or(false);
-
-                        if transparency_check || config.num_samples() < accum.num_samples() {
-                            config
-                        } else {
-                            accum
-                        }
-                    })
-                    .unwrap()
+        // glutin needs a config back from the picker, so running out of them can only be reported by unwinding
+        let built = panic::catch_unwind(AssertUnwindSafe(|| {
+            display_builder.build(event_loop, template, |configs| {
+                pick_config(configs, transparent).expect("No OpenGL config is available")
             })
-            .unwrap();
+        }));
+        let (window, gl_config) = built.map_err(|_| "No OpenGL config is available")??;
 
-        let window = window.expect("Could not create window with OpenGL context");
+        let window = window.ok_or("Could not create window with OpenGL context")?;
 
-        let window_handle = window.window_handle().unwrap();
+        let window_handle = window.window_handle()?;
 
         let context_attributes = ContextAttributesBuilder::new()
             .with_profile(GlProfile::Core)
@@ -120,12 +130,11 @@ impl OpenGLDriver {
             gl_config
                 .display()
                 .create_context(&gl_config, &context_attributes)
-                .unwrap_or_else(|_| {
+                .or_else(|_| {
                     gl_config
                         .display()
                         .create_context(&gl_config, &fallback_context_attributes)
-                        .expect("failed to create context")
-                })
+                })?
         };
 
         let size = window.inner_size();
@@ -139,13 +148,10 @@ impl OpenGLDriver {
         let gl_surface = unsafe {
             gl_config
                 .display()
-                .create_window_surface(&gl_config, &attrs)
-                .expect("Could not create gl window surface")
+                .create_window_surface(&gl_config, &attrs)?
         };
 
-        let gl_context = not_current_gl_context
-            .make_current(&gl_surface)
-            .expect("Could not make GL context current when setting up skia renderer");
+        let gl_context = not_current_gl_context.make_current(&gl_surface)?;
 
         // Try setting vsync.
         gl_surface
@@ -165,14 +171,14 @@ impl OpenGLDriver {
                 .display()
                 .get_proc_address(CString::new(name).unwrap().as_c_str())
         })
-        .expect("Could not create interface");
+        .ok_or("Could not create interface")?;
 
         let fb_info = {
             let mut fboid: GLint = 0;
             unsafe { GetIntegerv(FRAMEBUFFER_BINDING, &mut fboid) };
 
             FramebufferInfo {
-                fboid: fboid.try_into().unwrap(),
+                fboid: fboid.try_into()?,
                 format: Format::RGBA8.into(),
                 ..Default::default()
             }
@@ -182,7 +188,7 @@ impl OpenGLDriver {
         let stencil_size = gl_config.stencil_size() as usize;
 
         let mut gr_context =
-            direct_contexts::make_gl(interface, None).expect("Could not create direct context");
+            direct_contexts::make_gl(interface, None).ok_or("Could not create direct context")?;
 
         let render_target = backend_render_targets::make_gl(
             (size.width as i32, size.height as i32),
@@ -198,7 +204,7 @@ impl OpenGLDriver {
             None,
             None,
         )
-        .expect("Could not create skia surface");
+        .ok_or("Could not create skia surface")?;
 
         let driver = OpenGLDriver {
             gl_context,
@@ -210,7 +216,7 @@ impl OpenGLDriver {
             surface,
         };
 
-        (driver, window)
+        Ok((driver, window))
     }
 
     pub fn present(&mut self, window: &Window, render: impl FnOnce(&mut SkiaSurface)) {
@@ -2,6 +2,7 @@
 mod gl;
 #[cfg(target_os = "macos")]
 mod metal;
+mod software;
 #[cfg(any(target_os = "linux", target_os = "windows"))]
 mod vulkan;
 
@@ -23,28 +24,31 @@ pub enum GraphicsDriver {
     Metal(metal::MetalDriver),
     #[cfg(any(target_os = "linux", target_os = "windows"))]
     Vulkan(vulkan::VulkanDriver),
+    Software(software::SoftwareDriver),
 }
 
 impl GraphicsDriver {
-    #[allow(clippy::needless_return)]
     pub fn new(
         event_loop: &ActiveEventLoop,
         window_attributes: WindowAttributes,
     ) -> (Self, Window) {
+        let renderer = std::env::var("FREYA_RENDERER").unwrap_or_default();
+        let force_software = renderer.eq_ignore_ascii_case("software");
+
         // Metal (macOS)
+        // Set FREYA_RENDERER=software to force software rendering.
         #[cfg(target_os = "macos")]
-        {
+        if !force_software {
             let (driver, window) = metal::MetalDriver::new(event_loop, window_attributes);
 
             return (Self::Metal(driver), window);
         }
 
-        // Vulkan by default with OpenGL as fallback.
-        // Set FREYA_RENDERER=opengl to force OpenGL.
+        // Vulkan by default with OpenGL and then software rendering as fallbacks.
+        // Set FREYA_RENDERER=opengl to force OpenGL or FREYA_RENDERER=software to force software rendering.
         #[cfg(not(target_os = "macos"))]
-        {
-            let force_opengl =
-                std::env::var("FREYA_RENDERER").is_ok_and(|v| v.eq_ignore_ascii_case("opengl"));
+        if !force_software {
+            let force_opengl = renderer.eq_ignore_ascii_case("opengl");
 
             if !force_opengl {
                 let vk_attrs = window_attributes.clone();
@@ -58,10 +62,21 @@ impl GraphicsDriver {
                 }
             }
 
-            let (driver, window) = gl::OpenGLDriver::new(event_loop, window_attributes);
-
-            return (Self::OpenGl(driver), window);
+            let gl_attrs = window_attributes.clone();
+            match gl::OpenGLDriver::new(event_loop, gl_attrs) {
+                Ok((driver, window)) => return (Self::OpenGl(driver), window),
+                Err(err) => {
+                    tracing::warn!(
+                        "OpenGL initialization failed, falling back to software rendering: {err}"
+                    );
+                }
+            }
         }
+
+        let (driver, window) = software::SoftwareDriver::new(event_loop, window_attributes)
+            .expect("Could not create window with software rendering");
+
+        (Self::Software(driver), window)
     }
 
     pub fn present(
@@ -77,6 +92,7 @@ impl GraphicsDriver {
             Self::Metal(mtl) => mtl.present(size, window, render),
             #[cfg(any(target_os = "linux", target_os = "windows"))]
             Self::Vulkan(vk) => vk.present(size, window, render),
+            Self::Software(sw) => sw.present(window, render),
         }
     }
 
@@ -89,6 +105,7 @@ impl GraphicsDriver {
             Self::Metal(_) => "Metal",
             #[cfg(any(target_os = "linux", target_os = "windows"))]
             Self::Vulkan(_) => "Vulkan",
+            Self::Software(_) => "Software",
         }
     }
 
@@ -100,6 +117,7 @@ impl GraphicsDriver {
             Self::Metal(mtl) => mtl.resize(size),
             #[cfg(any(target_os = "linux", target_os = "windows"))]
             Self::Vulkan(vk) => vk.resize(size),
+            Self::Software(sw) => sw.resize(size),
         }
     }
 }
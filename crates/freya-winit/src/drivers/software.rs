@@ -0,0 +1,121 @@
+use std::num::NonZeroU32;
+
+use freya_engine::prelude::{
+    AlphaType,
+    ColorType,
+    ImageInfo,
+    Surface as SkiaSurface,
+    raster_n32_premul,
+};
+use raw_window_handle::{
+    DisplayHandle,
+    HasDisplayHandle,
+    HasWindowHandle,
+    WindowHandle,
+};
+use winit::{
+    dpi::PhysicalSize,
+    event_loop::ActiveEventLoop,
+    window::{
+        Window,
+        WindowAttributes,
+    },
+};
+
+/// Graphics driver rendering with the CPU into a raster surface, which is then copied to the window framebuffer.
+///
+/// Useful on machines without a usable GPU, like VMs or remote sessions. Window transparency is not supported.
+pub struct SoftwareDriver {
+    surface: SkiaSurface,
+    framebuffer: softbuffer::Surface<DisplayHandle<'static>, WindowHandle<'static>>,
+    size: PhysicalSize<u32>,
+}
+
+impl SoftwareDriver {
+    pub fn new(
+        event_loop: &ActiveEventLoop,
+        window_attributes: WindowAttributes,
+    ) -> Result<(Self, Window), Box<dyn std::error::Error>> {
+        let window = event_loop.create_window(window_attributes)?;
+
+        // SAFETY: The window is dropped after the driver.
+        let (display_handle, window_handle) = unsafe {
+            (
+                DisplayHandle::borrow_raw(window.display_handle()?.as_raw()),
+                WindowHandle::borrow_raw(window.window_handle()?.as_raw()),
+            )
+        };
+
+        let context = softbuffer::Context::new(display_handle)?;
+        let framebuffer = softbuffer::Surface::new(&context, window_handle)?;
+
+        let size = window.inner_size();
+        let surface = raster_n32_premul((size.width.max(1) as i32, size.height.max(1) as i32))
+            .ok_or("Could not create skia raster surface")?;
+
+        let mut driver = SoftwareDriver {
+            surface,
+            framebuffer,
+            size,
+        };
+        driver.resize(size);
+
+        Ok((driver, window))
+    }
+
+    pub fn present(&mut self, window: &Window, render: impl FnOnce(&mut SkiaSurface)) {
+        render(&mut self.surface);
+
+        let Ok(mut buffer) = self.framebuffer.buffer_mut() else {
+            return;
+        };
+
+        // Framebuffer pixels are `0RGB` u32s, so on little endian their bytes are laid out as `BGRA`.
+        let image_info = ImageInfo::new(
+            (self.size.width as i32, self.size.height as i32),
+            ColorType::BGRA8888,
+            AlphaType::Opaque,
+            None,
+        );
+        // SAFETY: Any u32 slice is also a valid u8 slice with 4 times the length.
+        let pixels = unsafe {
+            std::slice::from_raw_parts_mut(buffer.as_mut_ptr().cast::<u8>(), buffer.len() * 4)
+        };
+        if !self
+            .surface
+            .read_pixels(&image_info, pixels, self.size.width as usize * 4, (0, 0))
+        {
+            return;
+        }
+
+        // The upper byte must be zero for the framebuffer
+        for pixel in buffer.iter_mut() {
+            *pixel &= 0x00FF_FFFF;
+        }
+
+        window.pre_present_notify();
+        buffer.present().ok();
+    }
+
+    /// Resize the framebuffer and the surface, keeping the previous ones if that fails.
+    pub fn resize(&mut self, size: PhysicalSize<u32>) {
+        let width = size.width.max(1);
+        let height = size.height.max(1);
+
+        if let Err(err) = self.framebuffer.resize(
+            NonZeroU32::new(width).unwrap(),
+            NonZeroU32::new(height).unwrap(),
+        ) {
+            tracing::error!("Could not resize the software framebuffer: {err}");
+            return;
+        }
+
+        let Some(surface) = raster_n32_premul((width as i32, height as i32)) else {
+            tracing::error!("Could not create skia raster surface of {width}x{height}");
+            return;
+        };
+
+        self.surface = surface;
+        self.size = PhysicalSize::new(width, height);
+    }
+}
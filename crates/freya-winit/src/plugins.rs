@@ -191,6 +191,12 @@ pub enum PluginEvent<'a> {
         modifiers: Modifiers,
         is_pressed: bool,
     },
+
+    /// A [PlatformEvent] was received from a window, before being processed.
+    PlatformEvent {
+        window: &'a Window,
+        event: &'a PlatformEvent,
+    },
 }
 
 /// Skeleton for Freya plugins.
@@ -3,6 +3,7 @@ use std::{
     fmt,
     pin::Pin,
     task::Waker,
+    time::Instant,
 };
 
 use accesskit_winit::WindowEvent as AccessibilityWindowEvent;
@@ -17,10 +18,7 @@ use futures_util::{
     StreamExt,
     select,
 };
-use ragnarok::{
-    EventsExecutorRunner,
-    EventsMeasurerRunner,
-};
+use ragnarok::EventsExecutorRunner;
 use rustc_hash::FxHashMap;
 use torin::prelude::{
     CursorPoint,
@@ -44,6 +42,7 @@ use winit::{
     },
     event_loop::{
         ActiveEventLoop,
+        ControlFlow,
         EventLoopProxy,
     },
     window::{
@@ -585,18 +584,11 @@ impl ApplicationHandler<NativeEvent> for WinitRenderer {
                             }
                         },
                         NativeWindowEventAction::PlatformEvent(platform_event) => {
-                            let mut events_measurer_adapter = EventsMeasurerAdapter {
-                                tree: &mut app.tree,
-                                scale_factor: app.window.scale_factor(),
-                            };
-                            let processed_events = events_measurer_adapter.run(
-                                &mut vec![platform_event],
-                                &mut app.nodes_state,
-                                app.accessibility.focused_node_id(),
+                            app.process_platform_events(
+                                vec![platform_event],
+                                &mut self.plugins,
+                                &self.proxy,
                             );
-                            app.events_sender
-                                .unbounded_send(EventsChunk::Processed(processed_events))
-                                .unwrap();
                         }
                     }
                 }
@@ -604,6 +596,25 @@ impl ApplicationHandler<NativeEvent> for WinitRenderer {
         }
     }
 
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        // Emit the events that became due while waiting, e.g long presses
+        for app in self.windows.values_mut() {
+            let platform_events = app.events_recognizer.poll(Instant::now());
+            if platform_events.is_empty() {
+                continue;
+            }
+            app.measure_platform_events(platform_events);
+        }
+
+        // Wake up once the next timed event is due
+        let deadline = self
+            .windows
+            .values()
+            .filter_map(|app| app.events_recognizer.next_deadline())
+            .min();
+        event_loop.set_control_flow(deadline.map_or(ControlFlow::Wait, ControlFlow::WaitUntil));
+    }
+
     fn window_event(
         &mut self,
         event_loop: &winit::event_loop::ActiveEventLoop,
@@ -876,23 +887,30 @@ impl ApplicationHandler<NativeEvent> for WinitRenderer {
                     } else {
                         MouseEventName::MouseUp
                     };
-                    let platform_event = PlatformEvent::Mouse {
+                    let platform_events = vec![PlatformEvent::Mouse {
                         name,
                         cursor: (app.position.x, app.position.y).into(),
                         button: Some(map_winit_mouse_button(button)),
-                    };
-                    let mut events_measurer_adapter = EventsMeasurerAdapter {
-                        tree: &mut app.tree,
-                        scale_factor: app.window.scale_factor(),
-                    };
-                    let processed_events = events_measurer_adapter.run(
-                        &mut vec![platform_event],
-                        &mut app.nodes_state,
-                        app.accessibility.focused_node_id(),
-                    );
-                    app.events_sender
-                        .unbounded_send(EventsChunk::Processed(processed_events))
-                        .unwrap();
+                    }];
+                    app.process_platform_events(platform_events, &mut self.plugins, &self.proxy);
+
+                    // The pointer was released outside the window so nothing is hovered anymore
+                    if state == ElementState::Released
+                        && !app.is_cursor_inside
+                        && app.nodes_state.captured_node().is_none()
+                    {
+                        app.position = CursorPoint::from((-1., -1.));
+                        let platform_event = PlatformEvent::Mouse {
+                            name: MouseEventName::MouseMove,
+                            cursor: app.position,
+                            button: None,
+                        };
+                        app.process_platform_events(
+                            vec![platform_event],
+                            &mut self.plugins,
+                            &self.proxy,
+                        );
+                    }
                 }
 
                 WindowEvent::KeyboardInput { event, .. } => {
@@ -921,24 +939,13 @@ impl ApplicationHandler<NativeEvent> for WinitRenderer {
                         PluginHandle::new(&self.proxy),
                     );
 
-                    let platform_event = PlatformEvent::Keyboard {
+                    let platform_events = vec![PlatformEvent::Keyboard {
                         name,
                         key,
                         code,
                         modifiers,
-                    };
-                    let mut events_measurer_adapter = EventsMeasurerAdapter {
-                        tree: &mut app.tree,
-                        scale_factor: app.window.scale_factor(),
-                    };
-                    let processed_events = events_measurer_adapter.run(
-                        &mut vec![platform_event],
-                        &mut app.nodes_state,
-                        app.accessibility.focused_node_id(),
-                    );
-                    app.events_sender
-                        .unbounded_send(EventsChunk::Processed(processed_events))
-                        .unwrap();
+                    }];
+                    app.process_platform_events(platform_events, &mut self.plugins, &self.proxy);
                 }
 
                 WindowEvent::MouseWheel { delta, phase, .. } => {
@@ -965,73 +972,56 @@ impl ApplicationHandler<NativeEvent> for WinitRenderer {
                             cursor: app.position,
                             source: WheelSource::Device,
                         };
-                        let mut events_measurer_adapter = EventsMeasurerAdapter {
-                            tree: &mut app.tree,
-                            scale_factor: app.window.scale_factor(),
-                        };
-                        let processed_events = events_measurer_adapter.run(
-                            &mut vec![platform_event],
-                            &mut app.nodes_state,
-                            app.accessibility.focused_node_id(),
+                        app.process_platform_events(
+                            vec![platform_event],
+                            &mut self.plugins,
+                            &self.proxy,
                         );
-                        app.events_sender
-                            .unbounded_send(EventsChunk::Processed(processed_events))
-                            .unwrap();
                     }
                 }
 
+                WindowEvent::CursorEntered { .. } => {
+                    app.is_cursor_inside = true;
+                }
                 WindowEvent::CursorLeft { .. } => {
-                    if app.mouse_state == ElementState::Released {
+                    app.is_cursor_inside = false;
+                    // Winit keeps reporting the pointer movement outside the window while it is pressed,
+                    // so a captured or pressed pointer keeps its position
+                    if app.mouse_state == ElementState::Released
+                        && app.nodes_state.captured_node().is_none()
+                    {
                         app.position = CursorPoint::from((-1., -1.));
                         let platform_event = PlatformEvent::Mouse {
                             name: MouseEventName::MouseMove,
                             cursor: app.position,
                             button: None,
                         };
-                        let mut events_measurer_adapter = EventsMeasurerAdapter {
-                            tree: &mut app.tree,
-                            scale_factor: app.window.scale_factor(),
-                        };
-                        let processed_events = events_measurer_adapter.run(
-                            &mut vec![platform_event],
-                            &mut app.nodes_state,
-                            app.accessibility.focused_node_id(),
+                        app.process_platform_events(
+                            vec![platform_event],
+                            &mut self.plugins,
+                            &self.proxy,
                         );
-                        app.events_sender
-                            .unbounded_send(EventsChunk::Processed(processed_events))
-                            .unwrap();
                     }
                 }
                 WindowEvent::CursorMoved { position, .. } => {
                     app.just_focused = false;
                     app.position = CursorPoint::from((position.x, position.y));
 
-                    let mut platform_event = vec![PlatformEvent::Mouse {
+                    let mut platform_events = vec![PlatformEvent::Mouse {
                         name: MouseEventName::MouseMove,
                         cursor: app.position,
                         button: None,
                     }];
 
                     for dropped_file_path in app.dropped_file_paths.drain(..) {
-                        platform_event.push(PlatformEvent::File {
+                        platform_events.push(PlatformEvent::File {
                             name: FileEventName::FileDrop,
                             file_path: Some(dropped_file_path),
                             cursor: app.position,
                         });
                     }
 
-                    let mut events_measurer_adapter = EventsMeasurerAdapter {
-                        tree: &mut app.tree,
-                        scale_factor: app.window.scale_factor(),
-                    };
-                    let processed_events = events_measurer_adapter.run(
-                        &mut platform_event,
-                        &mut app.nodes_state,
-                        app.accessibility.focused_node_id(),
-                    );
-                    app.events_sender
-                        .unbounded_send(EventsChunk::Processed(processed_events))
-                        .unwrap();
+                    app.process_platform_events(platform_events, &mut self.plugins, &self.proxy);
                 }
 
                 WindowEvent::Touch(Touch {
@@ -1050,26 +1040,66 @@ impl ApplicationHandler<NativeEvent> for WinitRenderer {
                         TouchPhase::Started => TouchEventName::TouchStart,
                     };
 
-                    let platform_event = PlatformEvent::Touch {
+                    let platform_events = vec![PlatformEvent::Touch {
                         name,
                         location: app.position,
                         finger_id: id,
                         phase: map_winit_touch_phase(phase),
                         force: force.map(map_winit_touch_force),
+                    }];
+                    app.process_platform_events(platform_events, &mut self.plugins, &self.proxy);
+                    app.position = CursorPoint::from((location.x, location.y));
+                }
+                WindowEvent::PinchGesture { delta, phase, .. } if delta.is_finite() => {
+                    // Trackpad magnification, positive deltas zoom in
+                    let platform_event = PlatformEvent::Gesture {
+                        name: GestureEventName::Pinch,
+                        location: app.position,
+                        phase: map_winit_touch_phase(phase),
+                        scale: 1. + delta,
+                        rotation: 0.,
+                        translation: CursorPoint::zero(),
+                        velocity: CursorPoint::zero(),
                     };
-                    let mut events_measurer_adapter = EventsMeasurerAdapter {
-                        tree: &mut app.tree,
-                        scale_factor: app.window.scale_factor(),
+                    app.process_platform_events(
+                        vec![platform_event],
+                        &mut self.plugins,
+                        &self.proxy,
+                    );
+                }
+                WindowEvent::RotationGesture { delta, phase, .. } => {
+                    // Trackpad rotation, winit deltas are counterclockwise
+                    let platform_event = PlatformEvent::Gesture {
+                        name: GestureEventName::Rotate,
+                        location: app.position,
+                        phase: map_winit_touch_phase(phase),
+                        scale: 1.,
+                        rotation: -delta as f64,
+                        translation: CursorPoint::zero(),
+                        velocity: CursorPoint::zero(),
                     };
-                    let processed_events = events_measurer_adapter.run(
-                        &mut vec![platform_event],
-                        &mut app.nodes_state,
-                        app.accessibility.focused_node_id(),
+                    app.process_platform_events(
+                        vec![platform_event],
+                        &mut self.plugins,
+                        &self.proxy,
+                    );
+                }
+                WindowEvent::PanGesture { delta, phase, .. } => {
+                    // Multi finger pan, only reported by some platforms
+                    let platform_event = PlatformEvent::Gesture {
+                        name: GestureEventName::Pan,
+                        location: app.position,
+                        phase: map_winit_touch_phase(phase),
+                        scale: 1.,
+                        rotation: 0.,
+                        translation: (delta.x as f64, delta.y as f64).into(),
+                        velocity: CursorPoint::zero(),
+                    };
+                    app.process_platform_events(
+                        vec![platform_event],
+                        &mut self.plugins,
+                        &self.proxy,
                     );
-                    app.events_sender
-                        .unbounded_send(EventsChunk::Processed(processed_events))
-                        .unwrap();
-                    app.position = CursorPoint::from((location.x, location.y));
                 }
                 WindowEvent::Ime(Ime::Commit(text)) => {
                     let platform_event = PlatformEvent::Keyboard {
@@ -1078,18 +1108,11 @@ impl ApplicationHandler<NativeEvent> for WinitRenderer {
                         code: keyboard_types::Code::Unidentified,
                         modifiers: winit_mappings::map_winit_modifiers(app.modifiers_state),
                     };
-                    let mut events_measurer_adapter = EventsMeasurerAdapter {
-                        tree: &mut app.tree,
-                        scale_factor: app.window.scale_factor(),
-                    };
-                    let processed_events = events_measurer_adapter.run(
-                        &mut vec![platform_event],
-                        &mut app.nodes_state,
-                        app.accessibility.focused_node_id(),
+                    app.process_platform_events(
+                        vec![platform_event],
+                        &mut self.plugins,
+                        &self.proxy,
                     );
-                    app.events_sender
-                        .unbounded_send(EventsChunk::Processed(processed_events))
-                        .unwrap();
                 }
                 WindowEvent::Ime(Ime::Preedit(text, pos)) => {
                     let platform_event = PlatformEvent::ImePreedit {
@@ -1097,18 +1120,11 @@ impl ApplicationHandler<NativeEvent> for WinitRenderer {
                         text,
                         cursor: pos,
                     };
-                    let mut events_measurer_adapter = EventsMeasurerAdapter {
-                        tree: &mut app.tree,
-                        scale_factor: app.window.scale_factor(),
-                    };
-                    let processed_events = events_measurer_adapter.run(
-                        &mut vec![platform_event],
-                        &mut app.nodes_state,
-                        app.accessibility.focused_node_id(),
+                    app.process_platform_events(
+                        vec![platform_event],
+                        &mut self.plugins,
+                        &self.proxy,
                     );
-                    app.events_sender
-                        .unbounded_send(EventsChunk::Processed(processed_events))
-                        .unwrap();
                 }
                 WindowEvent::DroppedFile(file_path) => {
                     app.dropped_file_paths.push(file_path);
@@ -1119,18 +1135,11 @@ impl ApplicationHandler<NativeEvent> for WinitRenderer {
                         file_path: Some(file_path),
                         cursor: app.position,
                     };
-                    let mut events_measurer_adapter = EventsMeasurerAdapter {
-                        tree: &mut app.tree,
-                        scale_factor: app.window.scale_factor(),
-                    };
-                    let processed_events = events_measurer_adapter.run(
-                        &mut vec![platform_event],
-                        &mut app.nodes_state,
-                        app.accessibility.focused_node_id(),
+                    app.process_platform_events(
+                        vec![platform_event],
+                        &mut self.plugins,
+                        &self.proxy,
                     );
-                    app.events_sender
-                        .unbounded_send(EventsChunk::Processed(processed_events))
-                        .unwrap();
                 }
                 WindowEvent::HoveredFileCancelled => {
                     let platform_event = PlatformEvent::File {
@@ -1138,18 +1147,11 @@ impl ApplicationHandler<NativeEvent> for WinitRenderer {
                         file_path: None,
                         cursor: app.position,
                     };
-                    let mut events_measurer_adapter = EventsMeasurerAdapter {
-                        tree: &mut app.tree,
-                        scale_factor: app.window.scale_factor(),
-                    };
-                    let processed_events = events_measurer_adapter.run(
-                        &mut vec![platform_event],
-                        &mut app.nodes_state,
-                        app.accessibility.focused_node_id(),
+                    app.process_platform_events(
+                        vec![platform_event],
+                        &mut self.plugins,
+                        &self.proxy,
                     );
-                    app.events_sender
-                        .unbounded_send(EventsChunk::Processed(processed_events))
-                        .unwrap();
                 }
                 _ => {}
             }
@@ -4,6 +4,7 @@ use std::{
     rc::Rc,
     sync::Arc,
     task::Waker,
+    time::Instant,
 };
 
 use accesskit_winit::Adapter;
@@ -27,7 +28,10 @@ use futures_util::task::{
     ArcWake,
     waker,
 };
-use ragnarok::NodesState;
+use ragnarok::{
+    EventsMeasurerRunner,
+    NodesState,
+};
 use raw_window_handle::HasDisplayHandle;
 #[cfg(target_os = "linux")]
 use raw_window_handle::RawDisplayHandle;
@@ -75,9 +79,11 @@ pub struct AppWindow {
     pub(crate) driver: GraphicsDriver,
     pub(crate) window: Window,
     pub(crate) nodes_state: NodesState<NodeId>,
+    pub(crate) events_recognizer: EventsRecognizer,
 
     pub(crate) position: CursorPoint,
     pub(crate) mouse_state: ElementState,
+    pub(crate) is_cursor_inside: bool,
     pub(crate) modifiers_state: ModifiersState,
     pub(crate) just_focused: bool,
 
@@ -287,8 +293,10 @@ impl AppWindow {
             driver,
             window,
             nodes_state,
+            events_recognizer: EventsRecognizer::default(),
 
             mouse_state: ElementState::Released,
+            is_cursor_inside: true,
             position: CursorPoint::default(),
             modifiers_state: ModifiersState::default(),
             just_focused: false,
@@ -325,4 +333,42 @@ impl AppWindow {
     pub fn window_mut(&mut self) -> &mut Window {
         &mut self.window
     }
+
+    /// Notify the plugins about the [PlatformEvent]s coming from the platform, recognize the events derived from them,
+    /// then measure them all and send the resulting events to the [Runner].
+    pub(crate) fn process_platform_events(
+        &mut self,
+        mut platform_events: Vec<PlatformEvent>,
+        plugins: &mut PluginsManager,
+        proxy: &EventLoopProxy<NativeEvent>,
+    ) {
+        for platform_event in &platform_events {
+            plugins.send(
+                PluginEvent::PlatformEvent {
+                    window: &self.window,
+                    event: platform_event,
+                },
+                PluginHandle::new(proxy),
+            );
+        }
+        self.events_recognizer
+            .recognize(&mut platform_events, Instant::now());
+        self.measure_platform_events(platform_events);
+    }
+
+    /// Measure the [PlatformEvent]s and send the resulting events to the [Runner].
+    pub(crate) fn measure_platform_events(&mut self, mut platform_events: Vec<PlatformEvent>) {
+        let mut events_measurer_adapter = EventsMeasurerAdapter {
+            tree: &mut self.tree,
+            scale_factor: self.window.scale_factor(),
+        };
+        let processed_events = events_measurer_adapter.run(
+            &mut platform_events,
+            &mut self.nodes_state,
+            self.accessibility.focused_node_id(),
+        );
+        self.events_sender
+            .unbounded_send(EventsChunk::Processed(processed_events))
+            .unwrap();
+    }
 }
@@ -7,8 +7,8 @@
 //!
 //! | Platform | Graphics Backend |
 //! |----------|-----------------|
-//! | Linux | Vulkan (preferred), OpenGL (fallback) |
-//! | Windows | Vulkan (preferred), OpenGL (fallback) |
+//! | Linux | Vulkan (preferred), OpenGL (fallback), Software (last resort) |
+//! | Windows | Vulkan (preferred), OpenGL (fallback), Software (last resort) |
 //! | macOS | Metal |
 //!
 //! ## Rendering Backends
@@ -30,3 +30,15 @@
 //! ### Metal (macOS)
 //!
 //! The best graphics backend for macOS.
+//!
+//! ### Software (all platforms)
+//!
+//! Renders with the CPU and copies the result to the window, so it works on machines without a usable GPU
+//! such as VMs, CI desktops or remote sessions. It is much slower than the GPU backends and does not support transparent windows.
+//!
+//! Used as the last resort on Linux and Windows when OpenGL fails to initialize.
+//! You can also force it by setting the `FREYA_RENDERER` environment variable:
+//!
+//! ```sh
+//! FREYA_RENDERER=software cargo run
+//! ```
@@ -53,6 +53,7 @@
 //! - [Material Design](material_design)
 //! - [Plotters](freya_plotters_backend)
 //! - [Testing](freya_testing)
+//! - [Headless Rendering](headless)
 //! - [WebView](webview)
 //! - [Terminal](terminal)
 //! - [Tokio Integration](self::_docs::tokio_integration)
@@ -73,11 +74,16 @@
 //! - `icons`: Reexport of [freya_icons] under [icons].
 //! - `radio`: Reexport [freya_radio] under [radio].
 //! - `query`: Reexport [freya_query] under [query].
+//! - `query-json-persister`: Enables persisting queries in a JSON file with `JsonFilePersister`.
 //! - `markdown`: Enables the [MarkdownViewer](components::MarkdownViewer) component.
 //! - `webview`: Reexport [freya_webview] under [webview].
 //! - `titlebar`: Enables the [TitlebarButton](components::TitlebarButton) component.
 //! - `terminal`: Reexport [freya_terminal] under [terminal].
 //! - `code-editor`: Reexport [freya_code_editor] under [code_editor].
+//! - `code-editor-lsp`: Enables the Language Server Protocol support of [code_editor].
+//! - `headless`: Reexport [freya_headless] under [headless].
+//! - `headless-pdf`: Enables exporting PDF documents with [headless].
+//! - `recorder`: Reexport [freya_recorder] under [recorder].
 //!
 //! ## Misc features
 //! - `devtools`: Enables devtools support.
@@ -129,6 +135,11 @@ pub mod prelude {
             CursorPoint,
             Size2D,
         },
+        grid::{
+            GridPosition,
+            GridSize,
+        },
+        layout_direction::LayoutDirection,
         position::Position,
         size::Size,
         visible_size::VisibleSize,
@@ -317,6 +328,20 @@ pub mod code_editor {
     pub use freya_code_editor::prelude::*;
 }
 
+/// Reexport `freya-headless` when the `headless` feature is enabled.
+#[cfg(feature = "headless")]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "headless")))]
+pub mod headless {
+    pub use freya_headless::*;
+}
+
+/// Reexport `freya-recorder` when the `recorder` feature is enabled.
+#[cfg(feature = "recorder")]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "recorder")))]
+pub mod recorder {
+    pub use freya_recorder::*;
+}
+
 #[cfg(feature = "performance")]
 #[cfg_attr(feature = "docs", doc(cfg(feature = "performance")))]
 pub mod performance {
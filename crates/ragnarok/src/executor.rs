@@ -4,6 +4,7 @@ use crate::{
     NodeKey,
     NodesState,
     NodesStatesUpdate,
+    PointerCapture,
     PotentialEvent,
     SourceEvent,
 };
@@ -44,6 +45,11 @@ where
     /// Call the event handler of the given [Self::Emmitable].
     fn emit_event(&mut self, event: Self::Emmitable) -> bool;
 
+    /// Take the [PointerCapture] requested by the last emitted event, if any.
+    fn take_pointer_capture(&mut self) -> Option<PointerCapture<Self::Key>> {
+        None
+    }
+
     // All events have been emitted
     fn emitted_events(&mut self) {}
 }
@@ -74,6 +80,10 @@ impl<T: EventsExecutor + private::Sealed> EventsExecutorRunner for T {
 
             let default_action_enabled = self.emit_event(emmitable_event.clone());
 
+            if let Some(pointer_capture) = self.take_pointer_capture() {
+                nodes_state.apply_pointer_capture(pointer_capture);
+            }
+
             if !default_action_enabled {
                 // Get the events that this event can cancel
                 let cancellable_events = emmitable_event.name().get_cancellable_events();
@@ -48,6 +48,63 @@ pub fn measure_source_global_events<
     }
 }
 
+/// Route the pointer movement and release source events to the Node capturing the pointer,
+/// independently of where the pointer is. Returns the routed source events.
+pub fn measure_captured_events<
+    Key: NodeKey,
+    Name: NameOfEvent,
+    Source: SourceEvent<Name = Name>,
+    Emmitable: EmmitableEvent<Key = Key, Name = Name>,
+>(
+    events_measurer: &impl EventsMeasurer<
+        Key = Key,
+        Name = Name,
+        Emmitable = Emmitable,
+        Source = Source,
+    >,
+    captured_node: Key,
+    source_events: &mut Vec<Source>,
+    emmitable_events: &mut Vec<Emmitable>,
+) -> Vec<Source> {
+    let Some(area) = events_measurer.try_area_of(&captured_node) else {
+        return Vec::new();
+    };
+
+    let (captured_source_events, source_events_left) = source_events
+        .drain(..)
+        .partition::<Vec<_>, _>(|source_event| {
+            let event_name = source_event.as_event_name();
+            source_event.try_location().is_some()
+                && (event_name.is_moved()
+                    || event_name
+                        .get_derived_events()
+                        .iter()
+                        .any(|event| event.is_released()))
+        });
+    *source_events = source_events_left;
+
+    for source_event in &captured_source_events {
+        for derived_event_name in source_event.as_event_name().get_derived_events() {
+            // Exclusive enter events are left out as the pointer is not really entering the Node,
+            // non-exclusive ones (e.g over) still report the movement
+            if derived_event_name.is_global() || derived_event_name.is_exclusive_enter() {
+                continue;
+            }
+
+            if events_measurer.is_listening_to(&captured_node, &derived_event_name) {
+                emmitable_events.push(events_measurer.new_emmitable_event(
+                    captured_node,
+                    derived_event_name,
+                    source_event.clone(),
+                    Some(area),
+                ));
+            }
+        }
+    }
+
+    captured_source_events
+}
+
 /// Measure what event listeners could potentially be triggered
 pub fn measure_potential_events<
     Key: NodeKey,
@@ -7,8 +7,10 @@ use crate::{
     NameOfEvent,
     NodeKey,
     NodesState,
+    PointerCapture,
     ProcessedEvents,
     SourceEvent,
+    measure_captured_events,
     measure_emmitable_events,
     measure_potential_events,
     measure_source_global_events,
@@ -57,6 +59,35 @@ impl<T: EventsMeasurer + private::Sealed> EventsMeasurerRunner for T {
         nodes_state: &mut NodesState<Self::Key>,
         focus_id: Option<Self::Key>,
     ) -> ProcessedEvents<Self::Key, Self::Name, Self::Emmitable, Self::Source> {
+        // Route the pointer events to the Node capturing the pointer, if any
+        let mut captured_emmitable_events = Vec::new();
+        let captured_source_events = if let Some(captured_node) = nodes_state.captured_node() {
+            let captured_source_events =
+                measure_captured_events::<Self::Key, Self::Name, Self::Source, Self::Emmitable>(
+                    self,
+                    captured_node,
+                    source_events,
+                    &mut captured_emmitable_events,
+                );
+
+            // Release the pointer once it is released, or if the capturing Node is gone
+            let is_node_gone = self.try_area_of(&captured_node).is_none();
+            let is_pointer_released = captured_source_events.iter().any(|source_event| {
+                source_event
+                    .as_event_name()
+                    .get_derived_events()
+                    .iter()
+                    .any(|event| event.is_released())
+            });
+            if is_node_gone || is_pointer_released {
+                nodes_state.apply_pointer_capture(PointerCapture::Release);
+            }
+
+            captured_source_events
+        } else {
+            Vec::new()
+        };
+
         // Get potential events that could be emitted based on the elements layout and viewports
         let potential_events = measure_potential_events::<
             Self::Key,
@@ -85,8 +116,14 @@ impl<T: EventsMeasurer + private::Sealed> EventsMeasurerRunner for T {
             source_events,
             &mut emmitable_events,
         );
+        measure_source_global_events::<Self::Key, Self::Name, Self::Source, Self::Emmitable>(
+            self,
+            &captured_source_events,
+            &mut emmitable_events,
+        );
         // Join all the emmitable events and sort them
         emmitable_events.extend(collateral_emmitable_events);
+        emmitable_events.extend(captured_emmitable_events);
         emmitable_events.sort_unstable();
 
         let mut flattened_potential_events = potential_events.into_values().flatten().collect_vec();
@@ -17,6 +17,7 @@ pub struct NodesState<Key: NodeKey> {
     pressed_nodes: FxHashSet<Key>,
     hovered_nodes: FxHashSet<Key>,
     entered_node: Option<Key>,
+    captured_node: Option<Key>,
 }
 
 impl<Key: NodeKey> Default for NodesState<Key> {
@@ -25,10 +26,20 @@ impl<Key: NodeKey> Default for NodesState<Key> {
             pressed_nodes: FxHashSet::default(),
             hovered_nodes: FxHashSet::default(),
             entered_node: None,
+            captured_node: None,
         }
     }
 }
 
+/// Change of the Node capturing the pointer, requested while emitting an event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointerCapture<Key: NodeKey> {
+    /// Route the pointer events to this Node until released.
+    Capture(Key),
+    /// Stop routing the pointer events to the capturing Node.
+    Release,
+}
+
 pub type PotentialEvents<Key, Name, Source> =
     FxHashMap<Name, Vec<PotentialEvent<Key, Name, Source>>>;
 
@@ -232,6 +243,29 @@ impl<Key: NodeKey> NodesState<Key> {
     pub fn is_pressed(&self, key: Key) -> bool {
         self.pressed_nodes.contains(&key)
     }
+
+    /// Apply the given [PointerCapture].
+    pub fn apply_pointer_capture(&mut self, pointer_capture: PointerCapture<Key>) {
+        match pointer_capture {
+            PointerCapture::Capture(key) => {
+                #[cfg(debug_assertions)]
+                tracing::info!("Captured pointer by {:?}", key);
+
+                self.captured_node = Some(key);
+            }
+            PointerCapture::Release => {
+                #[cfg(debug_assertions)]
+                tracing::info!("Released pointer from {:?}", self.captured_node);
+
+                self.captured_node = None;
+            }
+        }
+    }
+
+    /// Get the Node capturing the pointer, if any.
+    pub fn captured_node(&self) -> Option<Key> {
+        self.captured_node
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
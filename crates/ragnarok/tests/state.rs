@@ -15,6 +15,7 @@ use ragnarok::{
     EventsMeasurerRunner,
     NameOfEvent,
     NodesState,
+    PointerCapture,
     SourceEvent,
 };
 
@@ -260,6 +261,7 @@ impl EmmitableEvent for TestEmmitableEvent {
 struct TestExecutor {
     emitted: Vec<TestEmmitableEvent>,
     handler: fn(&TestEmmitableEvent) -> bool,
+    pointer_capture: Option<PointerCapture<usize>>,
 }
 
 impl TestExecutor {
@@ -267,6 +269,7 @@ impl TestExecutor {
         Self {
             emitted: Vec::default(),
             handler,
+            pointer_capture: None,
         }
     }
 
@@ -274,6 +277,15 @@ impl TestExecutor {
         Self {
             emitted: Vec::default(),
             handler: |_| true,
+            pointer_capture: None,
+        }
+    }
+
+    pub fn with_pointer_capture(pointer_capture: PointerCapture<usize>) -> Self {
+        Self {
+            emitted: Vec::default(),
+            handler: |_| true,
+            pointer_capture: Some(pointer_capture),
         }
     }
 }
@@ -292,6 +304,10 @@ impl EventsExecutor for TestExecutor {
         self.emitted.push(event);
         allowed
     }
+
+    fn take_pointer_capture(&mut self) -> Option<PointerCapture<Self::Key>> {
+        self.pointer_capture.take()
+    }
 }
 
 #[derive(Default)]
@@ -613,6 +629,80 @@ fn missed_up_event() {
     assert!(nodes_state.is_pressed(0));
 }
 
+#[test]
+fn pointer_capture() {
+    let mut test_measurer = TestMeasurer::default();
+    let mut nodes_state = NodesState::default();
+
+    test_measurer.add(0, None, 0, Area::new((0., 0.).into(), (100., 100.).into()));
+    test_measurer.listen_to(0, EventName::MouseDown);
+    test_measurer.listen_to(0, EventName::MouseMove);
+    test_measurer.listen_to(0, EventName::MouseEnter);
+    test_measurer.listen_to(0, EventName::MouseOver);
+    test_measurer.listen_to(0, EventName::MouseUp);
+
+    let processed_events = test_measurer.run(
+        &mut vec![TestSourceEvent::MouseDown {
+            cursor: (25., 25.).into(),
+        }],
+        &mut nodes_state,
+        None,
+    );
+    TestExecutor::with_pointer_capture(PointerCapture::Capture(0))
+        .run(&mut nodes_state, processed_events);
+    assert_eq!(nodes_state.captured_node(), Some(0));
+
+    // The pointer moves outside the Node but it still gets the movement, without entering it
+    let processed_events = test_measurer.run(
+        &mut vec![TestSourceEvent::MouseMove {
+            cursor: (600., 700.).into(),
+        }],
+        &mut nodes_state,
+        None,
+    );
+    assert_eq!(processed_events.emmitable_events.len(), 2);
+    for name in [EventName::MouseMove, EventName::MouseOver] {
+        assert!(
+            processed_events
+                .emmitable_events
+                .contains(&TestEmmitableEvent {
+                    key: 0,
+                    name,
+                    source: EventName::MouseMove
+                })
+        );
+    }
+    TestExecutor::without_handler().run(&mut nodes_state, processed_events);
+
+    // Releasing the pointer outside the Node still gets routed to it and releases the capture
+    let processed_events = test_measurer.run(
+        &mut vec![TestSourceEvent::MouseUp {
+            cursor: (600., 700.).into(),
+        }],
+        &mut nodes_state,
+        None,
+    );
+    assert_eq!(
+        processed_events.emmitable_events,
+        vec![TestEmmitableEvent {
+            key: 0,
+            name: EventName::MouseUp,
+            source: EventName::MouseUp
+        }]
+    );
+    TestExecutor::without_handler().run(&mut nodes_state, processed_events);
+    assert_eq!(nodes_state.captured_node(), None);
+
+    let processed_events = test_measurer.run(
+        &mut vec![TestSourceEvent::MouseMove {
+            cursor: (600., 700.).into(),
+        }],
+        &mut nodes_state,
+        None,
+    );
+    assert!(processed_events.emmitable_events.is_empty());
+}
+
 #[test]
 fn state_updates_without_listeners() {
     let mut test_measurer = TestMeasurer::default();
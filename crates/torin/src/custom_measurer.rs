@@ -22,6 +22,12 @@ pub trait LayoutMeasurer<Key: NodeKey> {
 
     fn should_measure_inner_children(&mut self, node_id: Key) -> bool;
 
+    /// Distance from the top of a measured Node to the baseline of its first line of text,
+    /// used to align it with [Alignment::Baseline](crate::alignment::Alignment::Baseline).
+    fn baseline(&mut self, _node_id: Key, _data: &Rc<dyn Any>) -> Option<f32> {
+        None
+    }
+
     fn notify_layout_references(
         &mut self,
         _node_id: Key,
@@ -1,4 +1,5 @@
 pub use euclid::Rect;
+use itertools::Itertools;
 use rustc_hash::FxHashMap;
 
 use crate::{
@@ -19,6 +20,7 @@ use crate::{
         AvailableAreaModel,
         Content,
         Direction,
+        GridSize,
         Inner,
         LayoutMetadata,
         Length,
@@ -72,7 +74,7 @@ where
 
             let translate = match node.position {
                 Position::Global(_) => false,
-                Position::Stacked(_) | Position::Absolute(_) => true,
+                Position::Stacked(_) | Position::Absolute(_) | Position::Sticky(_) => true,
             };
 
             if translate {
@@ -99,6 +101,135 @@ where
         }
     }
 
+    /// Mirror the cached children of the given Node horizontally inside the given area.
+    fn mirror_children(&mut self, node_id: Key, area: &Area) {
+        for child in self.tree_adapter.children_of(&node_id) {
+            self.mirror_node(child, area);
+        }
+    }
+
+    /// Mirror a cached Node horizontally inside the given area, along with its descendants.
+    /// Global Nodes are mirrored inside the root area instead.
+    pub(crate) fn mirror_node(&mut self, node_id: Key, area: &Area) {
+        let Some(node) = self.tree_adapter.get_node(&node_id) else {
+            return;
+        };
+
+        let area = match node.position {
+            Position::Global(_) => self.layout_metadata.root_area,
+            Position::Stacked(_) | Position::Absolute(_) | Position::Sticky(_) => *area,
+        };
+
+        let Some(layout_node) = self.layout.get_mut(&node_id) else {
+            return;
+        };
+
+        // Mirror the visible area and swap the margins around it
+        let visible_area = layout_node.visible_area();
+        let offset_x = area.min_x() + area.max_x() - visible_area.min_x() - visible_area.max_x();
+        layout_node.area.origin.x +=
+            offset_x + layout_node.margin.left() - layout_node.margin.right();
+        layout_node.inner_area.origin.x += offset_x;
+        layout_node.margin.mirror_horizontal();
+
+        if let Some(measurer) = self.measurer {
+            measurer.notify_layout_references(
+                node_id,
+                layout_node.area,
+                layout_node.visible_area(),
+                layout_node.inner_sizes,
+            );
+        }
+
+        self.recursive_translate(node_id, Length::new(offset_x), Length::default());
+    }
+
+    /// Pin the sticky Nodes inside the visible area of their closest scrollable ancestor,
+    /// without leaving the inner area of their parent.
+    pub(crate) fn stick_nodes(&mut self) {
+        let sticky_nodes = self
+            .layout
+            .sticky_nodes
+            .iter()
+            .copied()
+            .sorted_by_key(|node_id| self.tree_adapter.height(node_id))
+            .collect::<Vec<_>>();
+
+        for node_id in sticky_nodes {
+            let Some(Position::Sticky(sides)) = self
+                .tree_adapter
+                .get_node(&node_id)
+                .map(|node| node.position)
+            else {
+                self.layout.sticky_nodes.remove(&node_id);
+                continue;
+            };
+            let Some(parent_id) = self.tree_adapter.parent_of(&node_id) else {
+                continue;
+            };
+            let Some(parent_layout_node) = self.layout.get(&parent_id) else {
+                continue;
+            };
+
+            // Children might overflow the inner area of the parent, e.g. in scroll views
+            let mut bounds = parent_layout_node.inner_area.cast_unit();
+            bounds.size = bounds.size.max(parent_layout_node.inner_sizes);
+
+            // Use the root area when there is no scrollable ancestor
+            let mut viewport = self.layout_metadata.root_area;
+            let mut ancestor_id = Some(parent_id);
+            while let Some(id) = ancestor_id {
+                if self.tree_adapter.is_scrollable(&id) {
+                    if let Some(layout_node) = self.layout.get(&id) {
+                        viewport = layout_node.visible_area();
+                    }
+                    break;
+                }
+                ancestor_id = self.tree_adapter.parent_of(&id);
+            }
+
+            let Some(layout_node) = self.layout.get_mut(&node_id) else {
+                continue;
+            };
+            let natural_area = layout_node.natural_area();
+
+            let mut sticky_offset = Size2D::default();
+            if let Some(top) = sides.top {
+                sticky_offset.height = (viewport.min_y() + top - natural_area.min_y())
+                    .min(bounds.max_y() - natural_area.max_y())
+                    .max(0.);
+            }
+            if let Some(left) = sides.left {
+                sticky_offset.width = (viewport.min_x() + left - natural_area.min_x())
+                    .min(bounds.max_x() - natural_area.max_x())
+                    .max(0.);
+            }
+
+            let offset_x = sticky_offset.width - layout_node.sticky_offset.width;
+            let offset_y = sticky_offset.height - layout_node.sticky_offset.height;
+            if offset_x == 0. && offset_y == 0. {
+                continue;
+            }
+
+            layout_node.sticky_offset = sticky_offset;
+            layout_node.area.origin.x += offset_x;
+            layout_node.area.origin.y += offset_y;
+            layout_node.inner_area.origin.x += offset_x;
+            layout_node.inner_area.origin.y += offset_y;
+
+            if let Some(measurer) = self.measurer {
+                measurer.notify_layout_references(
+                    node_id,
+                    layout_node.area,
+                    layout_node.visible_area(),
+                    layout_node.inner_sizes,
+                );
+            }
+
+            self.recursive_translate(node_id, Length::new(offset_x), Length::new(offset_y));
+        }
+    }
+
     /// Measure a Node and all its children.
     #[allow(clippy::too_many_arguments, clippy::missing_panics_doc)]
     pub fn measure_node(
@@ -129,11 +260,17 @@ where
 
             layout_node.offset_x = node.offset_x;
             layout_node.offset_y = node.offset_y;
+            layout_node.inner_area.origin.x += offset_x.get();
+            layout_node.inner_area.origin.y += offset_y.get();
 
             let layout_node = layout_node.clone();
 
             self.recursive_translate(node_id, offset_x, offset_y);
 
+            if must_cache_children && phase == Phase::Final {
+                self.layout.stats.translated_nodes += 1;
+            }
+
             return (must_cache_children, layout_node);
         }
 
@@ -143,6 +280,10 @@ where
         let must_revalidate =
             parent_is_dirty || reason.is_some() || !self.layout.results.contains_key(&node_id);
         if must_revalidate {
+            if must_cache_children && phase == Phase::Final {
+                self.layout.stats.measured_nodes += 1;
+            }
+
             // Create the initial Node area size
             let mut area_size = Size2D::new(node.padding.horizontal(), node.padding.vertical());
 
@@ -170,6 +311,8 @@ where
                 phase,
             );
 
+            let mut baseline = None;
+
             // If available, run a custom layout measure function
             // This is useful when you use third-party libraries (e.g. rust-skia, cosmic-text) to measure text layouts
             let node_data = if let Some(measurer) = self.measurer {
@@ -239,6 +382,10 @@ where
                             );
                         }
 
+                        baseline = measurer
+                            .baseline(node_id, &node_data)
+                            .map(|baseline| node.margin.top() + baseline);
+
                         // Do not measure inner children
                         Some(node_data)
                     } else {
@@ -265,6 +412,24 @@ where
                 true
             };
 
+            // Derive the size of an axis from the other one if possible,
+            // when both are sized by the children it will be done once they are measured
+            let aspect_ratio_axis = Self::aspect_ratio_axis(node).filter(|_| {
+                !(node.width.inner_sized()
+                    && node.height.inner_sized()
+                    && measure_inner_children
+                    && phase_measure_inner_children)
+            });
+            if aspect_ratio_axis.is_some() {
+                self.apply_aspect_ratio(
+                    node,
+                    &mut area_size,
+                    &initial_parent_area,
+                    &available_parent_area,
+                    phase,
+                );
+            }
+
             // Compute the inner size of the Node, which is basically the size inside the margins and paddings
             let inner_size = {
                 let mut inner_size = area_size;
@@ -296,6 +461,14 @@ where
                         phase,
                     );
                 }
+
+                // Use the size derived from the aspect ratio
+                match aspect_ratio_axis {
+                    Some(AlignAxis::Width) => inner_size.width = area_size.width,
+                    Some(AlignAxis::Height) => inner_size.height = area_size.height,
+                    None => {}
+                }
+
                 inner_size
             };
 
@@ -362,6 +535,31 @@ where
                     );
                 }
 
+                // The size of the children might have changed the axis the aspect ratio derives from
+                if let Some(axis) = Self::aspect_ratio_axis(node) {
+                    let mut size = parent_area.size.cast_unit();
+                    self.apply_aspect_ratio(
+                        node,
+                        &mut size,
+                        &initial_parent_area,
+                        &available_parent_area,
+                        phase,
+                    );
+                    parent_area.size = size.cast_unit();
+                    match axis {
+                        AlignAxis::Width => {
+                            inner_area.size.width = parent_area.size.width
+                                - node.padding.horizontal()
+                                - node.margin.horizontal();
+                        }
+                        AlignAxis::Height => {
+                            inner_area.size.height = parent_area.size.height
+                                - node.padding.vertical()
+                                - node.margin.vertical();
+                        }
+                    }
+                }
+
                 area = parent_area.cast_unit();
 
                 // Recompute the origin for non-stacked inner-sized elements
@@ -385,6 +583,19 @@ where
                         self.recursive_translate(node_id, Length::new(diff_x), Length::new(diff_y));
                     }
                 }
+
+                // Mirror the children horizontally now that their positions and the final area are known
+                if node.layout_direction.is_rtl() && must_cache_children && phase == Phase::Final {
+                    let visible_area = area.without_gaps(&node.margin);
+                    self.mirror_children(node_id, &visible_area);
+                    inner_area.origin.x =
+                        visible_area.min_x() + visible_area.max_x() - inner_area.max_x();
+                }
+            }
+
+            // Sticky Nodes are pinned once the whole layout is measured
+            if node.position.is_sticky() && must_cache_children {
+                self.layout.sticky_nodes.insert(node_id);
             }
 
             let layout_node = LayoutNode {
@@ -393,6 +604,9 @@ where
                 offset_x: node.offset_x,
                 offset_y: node.offset_y,
                 inner_area,
+                layout_direction: node.layout_direction,
+                sticky_offset: Size2D::default(),
+                baseline,
                 data: node_data,
                 inner_sizes,
             };
@@ -415,6 +629,10 @@ where
 
             (must_cache_children, layout_node)
         } else {
+            if must_cache_children && phase == Phase::Final {
+                self.layout.stats.cached_nodes += 1;
+            }
+
             let layout_node = self
                 .layout
                 .get(&node_id)
@@ -449,6 +667,86 @@ where
         }
     }
 
+    /// Axis whose size is derived from the other one using the aspect ratio of a Node.
+    /// The aspect ratio is ignored when both axis have a size not decided by the children.
+    fn aspect_ratio_axis(node: &Node) -> Option<AlignAxis> {
+        node.aspect_ratio
+            .filter(|aspect_ratio| *aspect_ratio > 0.)?;
+
+        if !node.width.inner_sized() || node.height.inner_sized() {
+            node.height.inner_sized().then_some(AlignAxis::Height)
+        } else {
+            Some(AlignAxis::Width)
+        }
+    }
+
+    /// Derive the size of an axis from the other one using the aspect ratio of a Node,
+    /// while still respecting the minimum and maximum sizes of both axis.
+    fn apply_aspect_ratio(
+        &self,
+        node: &Node,
+        // Size of the Node, including its margins
+        size: &mut Size2D,
+        initial_parent_area: &AreaOf<Parent>,
+        available_parent_area: &AreaOf<Available>,
+        phase: Phase,
+    ) {
+        let (Some(axis), Some(aspect_ratio)) = (Self::aspect_ratio_axis(node), node.aspect_ratio)
+        else {
+            return;
+        };
+
+        let min_max_width = |width: f32| {
+            Size::Pixels(Length::new(width)).min_max(
+                width,
+                initial_parent_area.size.width,
+                available_parent_area.size.width,
+                node.margin.left(),
+                node.margin.horizontal(),
+                &node.minimum_width,
+                &node.maximum_width,
+                self.layout_metadata.root_area.width(),
+                phase,
+            )
+        };
+        let min_max_height = |height: f32| {
+            Size::Pixels(Length::new(height)).min_max(
+                height,
+                initial_parent_area.size.height,
+                available_parent_area.size.height,
+                node.margin.top(),
+                node.margin.vertical(),
+                &node.minimum_height,
+                &node.maximum_height,
+                self.layout_metadata.root_area.height(),
+                phase,
+            )
+        };
+
+        match axis {
+            AlignAxis::Height => {
+                let height = (size.width - node.margin.horizontal()) / aspect_ratio;
+                size.height = min_max_height(height);
+
+                // Adapt the width if the height was constrained
+                let constrained_height = size.height - node.margin.vertical();
+                if (constrained_height - height).abs() > f32::EPSILON {
+                    size.width = min_max_width(constrained_height * aspect_ratio);
+                }
+            }
+            AlignAxis::Width => {
+                let width = (size.height - node.margin.vertical()) * aspect_ratio;
+                size.width = min_max_width(width);
+
+                // Adapt the height if the width was constrained
+                let constrained_width = size.width - node.margin.horizontal();
+                if (constrained_width - width).abs() > f32::EPSILON {
+                    size.height = min_max_height(constrained_width / aspect_ratio);
+                }
+            }
+        }
+    }
+
     /// Measure the children layouts of a Node.
     #[allow(clippy::too_many_arguments)]
     pub fn measure_children(
@@ -465,12 +763,30 @@ where
         // Parent Node is dirty.
         parent_is_dirty: bool,
     ) {
+        if let Content::Grid { columns, rows } = &parent_node.content {
+            self.measure_grid_children(
+                parent_node_id,
+                parent_node,
+                columns,
+                rows,
+                parent_area,
+                inner_area,
+                available_area,
+                inner_sizes,
+                must_cache_children,
+                parent_is_dirty,
+            );
+            return;
+        }
+
         let children = self.tree_adapter.children_of(parent_node_id);
 
         let initial_area = *inner_area;
 
         let mut initial_phase_flex_grows = FxHashMap::default();
+        let mut initial_phase_flex_shrinks = FxHashMap::default();
         let mut initial_phase_sizes = FxHashMap::default();
+        let mut initial_phase_baselines = FxHashMap::default();
         let mut initial_phase_inner_sizes = Size2D::default();
 
         // Used to calculate the spacing and some alignments
@@ -504,11 +820,20 @@ where
             )
         };
 
+        // Children might override the cross alignment of the parent
+        let children_align_self = parent_node.content.allows_alignments()
+            && children.iter().any(|child_id| {
+                self.tree_adapter
+                    .get_node(child_id)
+                    .is_some_and(|child_data| child_data.align_self.is_some())
+            });
+
         let needs_initial_phase = parent_node.cross_alignment.is_not_start()
             || parent_node.main_alignment.is_not_start()
             || parent_node.content.is_fit()
             || parent_node.content.is_flex()
-            || parent_node.content.is_wrap();
+            || parent_node.content.is_wrap()
+            || children_align_self;
 
         let mut initial_phase_parent_area = *parent_area;
         let mut initial_phase_inner_area = *inner_area;
@@ -519,9 +844,12 @@ where
         if needs_initial_phase {
             //  Measure the children
             for child_id in &children {
-                let Some(child_data) = self.tree_adapter.get_node(child_id) else {
+                let Some(mut child_data) = self.tree_adapter.get_node(child_id) else {
                     continue;
                 };
+                child_data.layout_direction = child_data
+                    .layout_direction
+                    .resolve(parent_node.layout_direction);
 
                 // No need to consider this Node for a two-phasing
                 // measurements as it will float on its own.
@@ -551,7 +879,7 @@ where
                     &mut initial_phase_parent_area,
                     &mut initial_phase_inner_area,
                     &mut initial_phase_inner_sizes,
-                    &child_areas.area,
+                    &child_areas.natural_area(),
                     is_last_child,
                     Phase::Initial,
                 );
@@ -559,10 +887,25 @@ where
                 if parent_node.cross_alignment.is_not_start()
                     || parent_node.main_alignment.is_spaced()
                     || parent_node.content.is_wrap()
+                    || child_data.align_self.is_some()
                 {
                     initial_phase_sizes.insert(*child_id, child_areas.area.size);
                 }
 
+                let cross_alignment = child_data
+                    .align_self
+                    .as_ref()
+                    .unwrap_or(&parent_node.cross_alignment);
+                if cross_alignment.is_baseline()
+                    && parent_node.direction == Direction::Horizontal
+                    && parent_node.content.allows_alignments()
+                {
+                    // Children without text use their bottom edge
+                    let baseline = child_areas.baseline.unwrap_or(child_areas.area.height());
+                    initial_phase_baselines
+                        .insert(*child_id, (baseline, child_areas.area.height() - baseline));
+                }
+
                 if parent_node.content.is_flex() {
                     match parent_node.direction {
                         Direction::Vertical => {
@@ -576,10 +919,76 @@ where
                             }
                         }
                     }
+
+                    // Flex growing children already adapt to the available space
+                    let main_size = match parent_node.direction {
+                        Direction::Vertical if !child_data.height.is_flex() => {
+                            child_areas.area.height()
+                        }
+                        Direction::Horizontal if !child_data.width.is_flex() => {
+                            child_areas.area.width()
+                        }
+                        _ => 0.,
+                    };
+                    if child_data.flex_shrink.get() > 0. && main_size > 0. {
+                        // Children can't shrink past their minimum size
+                        let minimum_size = match parent_node.direction {
+                            Direction::Vertical => Size::Pixels(Length::new(0.)).min_max(
+                                0.,
+                                initial_area.height(),
+                                initial_area.height(),
+                                child_data.margin.top(),
+                                child_data.margin.vertical(),
+                                &child_data.minimum_height,
+                                &child_data.maximum_height,
+                                self.layout_metadata.root_area.height(),
+                                Phase::Initial,
+                            ),
+                            Direction::Horizontal => Size::Pixels(Length::new(0.)).min_max(
+                                0.,
+                                initial_area.width(),
+                                initial_area.width(),
+                                child_data.margin.left(),
+                                child_data.margin.horizontal(),
+                                &child_data.minimum_width,
+                                &child_data.maximum_width,
+                                self.layout_metadata.root_area.width(),
+                                Phase::Initial,
+                            ),
+                        };
+                        initial_phase_flex_shrinks.insert(
+                            *child_id,
+                            (
+                                child_data.flex_shrink,
+                                main_size,
+                                minimum_size.clamp(0., main_size),
+                            ),
+                        );
+                    }
                 }
             }
         }
 
+        // Height needed by the baseline aligned children, which might be more than the tallest of them
+        let max_baseline = initial_phase_baselines
+            .values()
+            .map(|(baseline, _)| *baseline)
+            .reduce(f32::max);
+        let baselines_height = max_baseline.map(|max_baseline| {
+            max_baseline
+                + initial_phase_baselines
+                    .values()
+                    .map(|(_, descent)| *descent)
+                    .fold(0., f32::max)
+        });
+        if let Some(baselines_height) = baselines_height
+            && parent_node.height.inner_sized()
+        {
+            initial_phase_parent_area.size.height = initial_phase_parent_area.size.height.max(
+                baselines_height + parent_node.padding.vertical() + parent_node.margin.vertical(),
+            );
+        }
+
         let flex_grows = initial_phase_flex_grows
             .values()
             .copied()
@@ -589,6 +998,76 @@ where
 
         let flex_axis = AlignAxis::new(&parent_node.direction, AlignmentDirection::Main);
 
+        if parent_node.content.is_flex() && must_cache_children {
+            if initial_phase_flex_shrinks.is_empty() {
+                self.layout.flex_shrinking_nodes.remove(parent_node_id);
+            } else {
+                self.layout.flex_shrinking_nodes.insert(*parent_node_id);
+            }
+        }
+
+        // Shrink the children proportionally to their flex shrink and size when they overflow
+        let mut flex_shrunk_sizes = FxHashMap::default();
+        if parent_node.content.is_flex() && !initial_phase_flex_shrinks.is_empty() {
+            let overflow = match flex_axis {
+                AlignAxis::Height => initial_phase_inner_sizes.height - available_area.height(),
+                AlignAxis::Width => initial_phase_inner_sizes.width - available_area.width(),
+            };
+
+            if overflow > 0. {
+                // Children clamped to their minimum size can't absorb their whole share of the overflow,
+                // so what's left is redistributed among the others until none of them gets clamped
+                let mut clamped_sizes = FxHashMap::default();
+                loop {
+                    let remaining_overflow = overflow
+                        - clamped_sizes
+                            .iter()
+                            .map(|(child_id, min_size)| {
+                                initial_phase_flex_shrinks[child_id].1 - min_size
+                            })
+                            .sum::<f32>();
+                    let unclamped = initial_phase_flex_shrinks
+                        .iter()
+                        .filter(|(child_id, _)| !clamped_sizes.contains_key(*child_id))
+                        .collect::<Vec<_>>();
+                    let flex_shrinks = unclamped
+                        .iter()
+                        .map(|(_, (flex_shrink, size, _))| flex_shrink.get() * size)
+                        .sum::<f32>();
+
+                    let mut any_clamped = false;
+                    flex_shrunk_sizes.clear();
+                    for (child_id, (flex_shrink, size, min_size)) in unclamped {
+                        let shrunk_size =
+                            size - remaining_overflow * flex_shrink.get() * size / flex_shrinks;
+                        if shrunk_size < *min_size {
+                            clamped_sizes.insert(*child_id, *min_size);
+                            any_clamped = true;
+                        } else {
+                            flex_shrunk_sizes.insert(*child_id, shrunk_size);
+                        }
+                    }
+
+                    if !any_clamped {
+                        break;
+                    }
+                }
+                flex_shrunk_sizes.extend(clamped_sizes);
+
+                let shrunk = flex_shrunk_sizes
+                    .iter()
+                    .map(|(child_id, shrunk_size)| {
+                        initial_phase_flex_shrinks[child_id].1 - shrunk_size
+                    })
+                    .sum::<f32>();
+
+                match flex_axis {
+                    AlignAxis::Height => initial_phase_inner_sizes.height -= shrunk,
+                    AlignAxis::Width => initial_phase_inner_sizes.width -= shrunk,
+                }
+            }
+        }
+
         let flex_available_width = available_area.width() - initial_phase_inner_sizes.width;
         let flex_available_height = available_area.height() - initial_phase_inner_sizes.height;
 
@@ -623,6 +1102,7 @@ where
                     &initial_phase_parent_area,
                     &mut initial_phase_inner_area,
                     parent_node,
+                    children_align_self,
                     AlignmentDirection::Main,
                 );
 
@@ -637,7 +1117,9 @@ where
                 );
             }
 
-            if (parent_node.cross_alignment.is_not_start() || parent_node.content.is_fit())
+            if (parent_node.cross_alignment.is_not_start()
+                || parent_node.content.is_fit()
+                || children_align_self)
                 && parent_node.content.allows_alignments()
             {
                 // Adjust the available and inner areas of the Cross axis
@@ -646,6 +1128,7 @@ where
                     &initial_phase_parent_area,
                     &mut initial_phase_inner_area,
                     parent_node,
+                    children_align_self,
                     AlignmentDirection::Cross,
                 );
             }
@@ -655,9 +1138,12 @@ where
 
         // Final phase: measure the children with all the axis and sizes adjusted
         for child_id in children {
-            let Some(child_data) = self.tree_adapter.get_node(&child_id) else {
+            let Some(mut child_data) = self.tree_adapter.get_node(&child_id) else {
                 continue;
             };
+            child_data.layout_direction = child_data
+                .layout_direction
+                .resolve(parent_node.layout_direction);
 
             let is_first_child = first_child == Some(child_id);
             let is_last_child = last_child == Some(child_id);
@@ -681,6 +1167,21 @@ where
                         }
                     }
                 }
+
+                if let Some(shrunk_size) = flex_shrunk_sizes.get(&child_id) {
+                    match flex_axis {
+                        AlignAxis::Height => {
+                            child_data.height = Size::Pixels(Length::new(
+                                shrunk_size - child_data.margin.vertical(),
+                            ));
+                        }
+                        AlignAxis::Width => {
+                            child_data.width = Size::Pixels(Length::new(
+                                shrunk_size - child_data.margin.horizontal(),
+                            ));
+                        }
+                    }
+                }
             }
 
             // Only the stacked children will be aligned
@@ -701,8 +1202,12 @@ where
                 );
             }
 
-            if parent_node.cross_alignment.is_not_start() && parent_node.content.allows_alignments()
-            {
+            let cross_alignment = child_data
+                .align_self
+                .as_ref()
+                .unwrap_or(&parent_node.cross_alignment);
+
+            if cross_alignment.is_not_start() && parent_node.content.allows_alignments() {
                 let initial_phase_size = initial_phase_sizes.get(&child_id);
 
                 if let Some(initial_phase_size) = initial_phase_size {
@@ -711,11 +1216,19 @@ where
                         &mut adapted_available_area,
                         &available_area.as_inner(),
                         *initial_phase_size,
-                        &parent_node.cross_alignment,
+                        cross_alignment,
                         parent_node.direction,
                         AlignmentDirection::Cross,
                     );
                 }
+
+                if let (Some(max_baseline), Some((baseline, _))) =
+                    (max_baseline, initial_phase_baselines.get(&child_id))
+                {
+                    // Align the baseline with the lowest one
+                    adapted_available_area.origin.y =
+                        available_area.min_y() + max_baseline - baseline;
+                }
             }
 
             if let Content::Wrap { wrap_spacing } = parent_node.content {
@@ -755,7 +1268,7 @@ where
                     parent_area,
                     inner_area,
                     inner_sizes,
-                    &child_areas.area,
+                    &child_areas.natural_area(),
                     is_last_child,
                     Phase::Final,
                 );
@@ -767,6 +1280,267 @@ where
                 self.layout.cache_node(child_id, child_areas);
             }
         }
+
+        if let Some(baselines_height) = baselines_height {
+            inner_sizes.height = inner_sizes.height.max(baselines_height);
+
+            // Keep the biggest height
+            if parent_node.height.inner_sized() {
+                parent_area.size.height = parent_area.size.height.max(
+                    baselines_height
+                        + parent_node.padding.vertical()
+                        + parent_node.margin.vertical(),
+                );
+                // Keep the inner area in sync
+                inner_area.size.height = parent_area.size.height
+                    - parent_node.padding.vertical()
+                    - parent_node.margin.vertical();
+            }
+        }
+    }
+
+    /// Measure the children layouts of a Node with a [Content::Grid].
+    #[allow(clippy::too_many_arguments)]
+    fn measure_grid_children(
+        &mut self,
+        parent_node_id: &Key,
+        parent_node: &Node,
+        columns: &[GridSize],
+        rows: &[GridSize],
+        parent_area: &mut AreaOf<Parent>,
+        inner_area: &mut AreaOf<Inner>,
+        available_area: &mut AreaOf<Available>,
+        // Accumulated sizes in both axis in the Node
+        inner_sizes: &mut Size2D,
+        // Whether to cache the measurements of this Node's children
+        must_cache_children: bool,
+        // Parent Node is dirty.
+        parent_is_dirty: bool,
+    ) {
+        let initial_area = *inner_area;
+        let spacing = parent_node.spacing.get();
+
+        let children = self
+            .tree_adapter
+            .children_of(parent_node_id)
+            .into_iter()
+            .filter_map(|child_id| {
+                let mut child_data = self.tree_adapter.get_node(&child_id)?;
+                child_data.layout_direction = child_data
+                    .layout_direction
+                    .resolve(parent_node.layout_direction);
+                Some((child_id, child_data))
+            })
+            .collect::<Vec<_>>();
+
+        // Children placed outside of the declared tracks get implicit auto tracks
+        let (columns_len, rows_len) = children
+            .iter()
+            .filter(|(_, child_data)| child_data.position.is_stacked())
+            .fold(
+                (columns.len(), rows.len()),
+                |(columns_len, rows_len), (_, child_data)| {
+                    (
+                        columns_len.max(child_data.grid_position.column_end()),
+                        rows_len.max(child_data.grid_position.row_end()),
+                    )
+                },
+            );
+        let mut columns = columns.to_vec();
+        columns.resize(columns_len, GridSize::Inner);
+        let mut rows = rows.to_vec();
+        rows.resize(rows_len, GridSize::Inner);
+
+        // Initial phase: Measure the children that sit on a single auto column
+        let mut columns_contents = vec![0f32; columns_len];
+        for (child_id, child_data) in &children {
+            let grid_position = child_data.grid_position;
+            if !child_data.position.is_stacked()
+                || grid_position.column_span > 1
+                || columns[grid_position.column] != GridSize::Inner
+            {
+                continue;
+            }
+
+            let (_, mut child_areas) = self.measure_node(
+                *child_id,
+                child_data,
+                initial_area.as_parent(),
+                *available_area,
+                false,
+                parent_is_dirty,
+                Phase::Initial,
+            );
+            child_areas.area.adjust_size(child_data);
+
+            let content = &mut columns_contents[grid_position.column];
+            *content = content.max(child_areas.area.width());
+        }
+        let columns_sizes =
+            Self::grid_tracks_sizes(&columns, &columns_contents, available_area.width(), spacing);
+
+        // Initial phase: Measure the children that sit on a single auto row, now that their width is known
+        let mut rows_contents = vec![0f32; rows_len];
+        for (child_id, child_data) in &children {
+            let grid_position = child_data.grid_position;
+            if !child_data.position.is_stacked()
+                || grid_position.row_span > 1
+                || rows[grid_position.row] != GridSize::Inner
+            {
+                continue;
+            }
+
+            let mut cell_available_area = *available_area;
+            cell_available_area.size.width = Self::grid_span_size(
+                &columns_sizes,
+                grid_position.column,
+                grid_position.column_end(),
+                spacing,
+            );
+
+            let (_, mut child_areas) = self.measure_node(
+                *child_id,
+                child_data,
+                initial_area.as_parent(),
+                cell_available_area,
+                false,
+                parent_is_dirty,
+                Phase::Initial,
+            );
+            child_areas.area.adjust_size(child_data);
+
+            let content = &mut rows_contents[grid_position.row];
+            *content = content.max(child_areas.area.height());
+        }
+        let rows_sizes =
+            Self::grid_tracks_sizes(&rows, &rows_contents, available_area.height(), spacing);
+
+        // Final phase: measure the children inside their cells
+        for (child_id, child_data) in children {
+            let (child_parent_area, child_available_area) = if child_data.position.is_stacked() {
+                let grid_position = child_data.grid_position;
+                let cell_area = AreaOf::<Available>::new(
+                    (
+                        available_area.min_x()
+                            + Self::grid_span_size(
+                                &columns_sizes,
+                                0,
+                                grid_position.column,
+                                spacing,
+                            )
+                            + if grid_position.column > 0 {
+                                spacing
+                            } else {
+                                0.
+                            },
+                        available_area.min_y()
+                            + Self::grid_span_size(&rows_sizes, 0, grid_position.row, spacing)
+                            + if grid_position.row > 0 { spacing } else { 0. },
+                    )
+                        .into(),
+                    (
+                        Self::grid_span_size(
+                            &columns_sizes,
+                            grid_position.column,
+                            grid_position.column_end(),
+                            spacing,
+                        ),
+                        Self::grid_span_size(
+                            &rows_sizes,
+                            grid_position.row,
+                            grid_position.row_end(),
+                            spacing,
+                        ),
+                    )
+                        .into(),
+                );
+                (cell_area.as_parent(), cell_area)
+            } else {
+                (initial_area.as_parent(), *available_area)
+            };
+
+            let (child_revalidated, mut child_areas) = self.measure_node(
+                child_id,
+                &child_data,
+                child_parent_area,
+                child_available_area,
+                must_cache_children,
+                parent_is_dirty,
+                Phase::Final,
+            );
+
+            // Adjust the size of the area if needed
+            child_areas.area.adjust_size(&child_data);
+
+            // Cache the child layout if it was mutated and children must be cached
+            if child_revalidated && must_cache_children {
+                self.layout.cache_node(child_id, child_areas);
+            }
+        }
+
+        inner_sizes.width = Self::grid_span_size(&columns_sizes, 0, columns_len, spacing);
+        inner_sizes.height = Self::grid_span_size(&rows_sizes, 0, rows_len, spacing);
+
+        if parent_node.width.inner_sized() {
+            parent_area.size.width = inner_sizes.width
+                + parent_node.padding.horizontal()
+                + parent_node.margin.horizontal();
+            inner_area.size.width = inner_sizes.width;
+        }
+
+        if parent_node.height.inner_sized() {
+            parent_area.size.height =
+                inner_sizes.height + parent_node.padding.vertical() + parent_node.margin.vertical();
+            inner_area.size.height = inner_sizes.height;
+        }
+    }
+
+    /// Resolve the sizes of some grid tracks given the size of their contents and the available space.
+    fn grid_tracks_sizes(
+        tracks: &[GridSize],
+        contents: &[f32],
+        available_size: f32,
+        spacing: f32,
+    ) -> Vec<f32> {
+        let mut sizes = tracks
+            .iter()
+            .zip(contents)
+            .map(|(track, content)| match track {
+                GridSize::Inner => *content,
+                GridSize::Pixels(px) => px.get(),
+                GridSize::Flex(_) => 0.,
+            })
+            .collect::<Vec<_>>();
+
+        let flex_grows = tracks
+            .iter()
+            .filter_map(|track| match track {
+                GridSize::Flex(flex) => Some(flex.get()),
+                _ => None,
+            })
+            .sum::<f32>();
+
+        if flex_grows > 0. {
+            let gaps = spacing * tracks.len().saturating_sub(1) as f32;
+            let flex_available_size = (available_size - gaps - sizes.iter().sum::<f32>()).max(0.);
+
+            for (size, track) in sizes.iter_mut().zip(tracks) {
+                if let GridSize::Flex(flex) = track {
+                    *size = flex_available_size * flex.get() / flex_grows;
+                }
+            }
+        }
+
+        sizes
+    }
+
+    /// Size of the tracks in the range `start..end`, including the spacing between them.
+    fn grid_span_size(sizes: &[f32], start: usize, end: usize, spacing: f32) -> f32 {
+        let end = end.min(sizes.len());
+        if start >= end {
+            return 0.;
+        }
+        sizes[start..end].iter().sum::<f32>() + spacing * (end - start - 1) as f32
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -1001,6 +1775,8 @@ where
         parent_area: &AreaOf<Parent>,
         inner_area: &mut AreaOf<Inner>,
         parent_node: &Node,
+        // Some children override the cross alignment of the parent
+        children_align_self: bool,
         alignment_direction: AlignmentDirection,
     ) {
         struct NodeData<'a> {
@@ -1019,10 +1795,14 @@ where
         let (is_vertical_not_start, is_horizontal_not_start) = match parent_node.direction {
             Direction::Vertical => (
                 parent_node.main_alignment.is_not_start(),
-                parent_node.cross_alignment.is_not_start() || parent_node.content.is_fit(),
+                parent_node.cross_alignment.is_not_start()
+                    || parent_node.content.is_fit()
+                    || children_align_self,
             ),
             Direction::Horizontal => (
-                parent_node.cross_alignment.is_not_start() || parent_node.content.is_fit(),
+                parent_node.cross_alignment.is_not_start()
+                    || parent_node.content.is_fit()
+                    || children_align_self,
                 parent_node.main_alignment.is_not_start(),
             ),
         };
@@ -5,6 +5,8 @@ use crate::{
     direction::Direction,
     gaps::Gaps,
     geometry::Length,
+    grid::GridPosition,
+    layout_direction::LayoutDirection,
     prelude::{
         Content,
         Position,
@@ -60,6 +62,21 @@ pub struct Node {
     pub has_layout_references: bool,
 
     pub spacing: Length,
+
+    /// Cell occupied inside a [`Content::Grid`] parent
+    pub grid_position: GridPosition,
+
+    /// Overrides the cross alignment of the parent for this Node
+    pub align_self: Option<Alignment>,
+
+    /// Shrink factor used to fit the children of a [`Content::Flex`] parent when they overflow
+    pub flex_shrink: Length,
+
+    /// Width / height ratio used to derive the size of an axis from the other one
+    pub aspect_ratio: Option<f32>,
+
+    /// Horizontal flow of the inner Nodes, inherited from the parent by default
+    pub layout_direction: LayoutDirection,
 }
 
 impl Scaled for Node {
@@ -76,6 +93,7 @@ impl Scaled for Node {
         self.offset_y *= scale_factor;
         self.position.scale(scale_factor);
         self.spacing *= scale_factor;
+        self.content.scale(scale_factor);
     }
 }
 
@@ -104,6 +122,11 @@ impl Node {
             && self.content == other.content
             && self.has_layout_references == other.has_layout_references
             && self.spacing == other.spacing
+            && self.grid_position == other.grid_position
+            && self.align_self == other.align_self
+            && self.flex_shrink == other.flex_shrink
+            && self.aspect_ratio == other.aspect_ratio
+            && self.layout_direction == other.layout_direction
     }
 
     pub fn inner_layout_eq(&self, other: &Self) -> bool {
@@ -287,6 +310,20 @@ impl Node {
         }
     }
 
+    /// Construct a new Node given a size and a grid position
+    pub fn from_size_and_grid_position(
+        width: Size,
+        height: Size,
+        grid_position: GridPosition,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            grid_position,
+            ..Default::default()
+        }
+    }
+
     /// Construct a new Node given a size and spacing
     pub fn from_size_and_direction_and_spacing(
         width: Size,
@@ -303,6 +340,30 @@ impl Node {
         }
     }
 
+    /// Construct a new Node given a size and an aspect ratio
+    pub fn from_size_and_aspect_ratio(width: Size, height: Size, aspect_ratio: f32) -> Self {
+        Self {
+            width,
+            height,
+            aspect_ratio: Some(aspect_ratio),
+            ..Default::default()
+        }
+    }
+
+    /// Construct a new Node given a size and a layout direction
+    pub fn from_size_and_layout_direction(
+        width: Size,
+        height: Size,
+        layout_direction: LayoutDirection,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            layout_direction,
+            ..Default::default()
+        }
+    }
+
     /// Has properties that depend on the inner Nodes?
     pub fn does_depend_on_inner(&self) -> bool {
         self.width.inner_sized() || self.height.inner_sized() || self.do_inner_depend_on_parent()
@@ -313,5 +374,6 @@ impl Node {
         self.cross_alignment.is_not_start()
             || self.main_alignment.is_not_start()
             || self.has_layout_references
+            || self.content.is_grid()
     }
 }
@@ -1,12 +1,16 @@
 #![allow(clippy::missing_panics_doc)]
 
-use std::collections::HashMap;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
 
 use crate::prelude::*;
 
 #[derive(Default)]
 pub struct TestingTree {
     mapper: HashMap<usize, (Option<usize>, Vec<usize>, u16, Node)>,
+    scrollables: HashSet<usize>,
 }
 
 impl TestingTree {
@@ -19,6 +23,10 @@ impl TestingTree {
         self.mapper.get_mut(&node_id).unwrap().3 = node;
     }
 
+    pub fn set_scrollable(&mut self, node_id: usize) {
+        self.scrollables.insert(node_id);
+    }
+
     pub fn remove(&mut self, node_id: usize) {
         let node = self.mapper.remove(&node_id).unwrap();
 
@@ -55,6 +63,10 @@ impl TreeAdapter<usize> for TestingTree {
     fn root_id(&self) -> usize {
         0
     }
+
+    fn is_scrollable(&self, node_id: &usize) -> bool {
+        self.scrollables.contains(node_id)
+    }
 }
 
 pub fn test_utils() -> (Torin<usize>, Option<NoopMeasurer>) {
@@ -4,7 +4,10 @@ use std::{
 };
 
 use itertools::Itertools;
-use rustc_hash::FxHashMap;
+use rustc_hash::{
+    FxHashMap,
+    FxHashSet,
+};
 
 use crate::{
     custom_measurer::LayoutMeasurer,
@@ -17,6 +20,7 @@ use crate::{
         AreaConverter,
         AreaModel,
         Gaps,
+        LayoutDirection,
         Length,
         Size2D,
     },
@@ -93,6 +97,7 @@ impl<Key: NodeKey> RootNodeCandidate<Key> {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DirtyReason {
     None,
@@ -102,6 +107,41 @@ pub enum DirtyReason {
     InnerLayout,
 }
 
+/// Statistics of the last layout measurement, useful to find out why and how much was relaid out.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutStats<Key: NodeKey> {
+    /// Node from where the measurement started, [None] if the measurement was skipped.
+    pub root: Option<Key>,
+    /// Nodes that were measured again.
+    pub measured_nodes: usize,
+    /// Nodes that reused their cached layout.
+    pub cached_nodes: usize,
+    /// Nodes whose descendants were translated instead of measured again.
+    pub translated_nodes: usize,
+    /// Nodes that were dirty and why.
+    pub dirty: FxHashMap<Key, DirtyReason>,
+}
+
+impl<Key: NodeKey> Default for LayoutStats<Key> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            measured_nodes: 0,
+            cached_nodes: 0,
+            translated_nodes: 0,
+            dirty: FxHashMap::default(),
+        }
+    }
+}
+
+impl<Key: NodeKey> LayoutStats<Key> {
+    /// Total of Nodes visited in the last measurement.
+    pub fn visited_nodes(&self) -> usize {
+        self.measured_nodes + self.cached_nodes + self.translated_nodes
+    }
+}
+
 pub struct Torin<Key: NodeKey> {
     /// Layout results of the registered Nodes
     pub results: FxHashMap<Key, LayoutNode>,
@@ -111,6 +151,15 @@ pub struct Torin<Key: NodeKey> {
 
     /// Best Root node candidate from where to start measuringg
     pub root_node_candidate: RootNodeCandidate<Key>,
+
+    /// Measured Nodes with a sticky position
+    pub sticky_nodes: FxHashSet<Key>,
+
+    /// Measured flex Nodes with flex shrinking children
+    pub flex_shrinking_nodes: FxHashSet<Key>,
+
+    /// Statistics of the last measurement
+    pub stats: LayoutStats<Key>,
 }
 
 impl<Key: NodeKey> Default for Torin<Key> {
@@ -126,6 +175,9 @@ impl<Key: NodeKey> Torin<Key> {
             results: HashMap::default(),
             dirty: FxHashMap::default(),
             root_node_candidate: RootNodeCandidate::None,
+            sticky_nodes: FxHashSet::default(),
+            flex_shrinking_nodes: FxHashSet::default(),
+            stats: LayoutStats::default(),
         }
     }
 
@@ -143,6 +195,14 @@ impl<Key: NodeKey> Torin<Key> {
         self.root_node_candidate = RootNodeCandidate::None;
         self.results.clear();
         self.dirty.clear();
+        self.sticky_nodes.clear();
+        self.flex_shrinking_nodes.clear();
+        self.stats = LayoutStats::default();
+    }
+
+    /// Read the statistics of the last measurement
+    pub fn stats(&self) -> &LayoutStats<Key> {
+        &self.stats
     }
 
     /// Read the HashSet of dirty nodes
@@ -154,6 +214,8 @@ impl<Key: NodeKey> Torin<Key> {
     pub fn raw_remove(&mut self, node_id: Key) {
         self.results.remove(&node_id);
         self.dirty.remove(&node_id);
+        self.sticky_nodes.remove(&node_id);
+        self.flex_shrinking_nodes.remove(&node_id);
         if let RootNodeCandidate::Valid(id) = self.root_node_candidate
             && id == node_id
         {
@@ -233,9 +295,28 @@ impl<Key: NodeKey> Torin<Key> {
             let parent = tree_adapter.get_node(&parent_id);
 
             if let Some(parent) = parent {
-                if parent.does_depend_on_inner() {
+                // Shrinking children of a flex Node are resized when any of their siblings changes
+                let has_flex_shrinking_children = parent.content.is_flex()
+                    && (self.flex_shrinking_nodes.contains(&parent_id)
+                        || tree_adapter
+                            .get_node(&node_id)
+                            .is_some_and(|node| node.flex_shrink.get() > 0.));
+
+                if parent.does_depend_on_inner() || has_flex_shrinking_children {
                     // Mark parent if it depends on it's inner children
                     self.check_dirty_dependants(parent_id, DirtyReason::None, tree_adapter, true);
+                } else if self
+                    .get(&parent_id)
+                    .is_some_and(|parent_layout| parent_layout.layout_direction.is_rtl())
+                {
+                    // Right-to-left Nodes mirror all their children at once,
+                    // but their own size does not depend on them
+                    self.invalidate(parent_id);
+                    self.root_node_candidate.propose_new_candidate(
+                        &parent_id,
+                        tree_adapter,
+                        &mut self.dirty,
+                    );
                 } else {
                     let parent_children = tree_adapter.children_of(&parent_id);
                     let multiple_children = parent_children.len() > 1;
@@ -301,6 +382,7 @@ impl<Key: NodeKey> Torin<Key> {
         // But no dirty nodes, we can simply skip the measurement
         // as this means no changes has been made to the layout
         if self.dirty.is_empty() && !self.results.is_empty() {
+            self.stats = LayoutStats::default();
             return;
         }
 
@@ -310,6 +392,10 @@ impl<Key: NodeKey> Torin<Key> {
         } else {
             suggested_root_id
         };
+        self.stats = LayoutStats {
+            root: Some(root_id),
+            ..LayoutStats::default()
+        };
         let root_parent_id = tree_adapter.parent_of(&root_id);
         let layout_node = root_parent_id
             .and_then(|root_parent_id| self.get(&root_parent_id).cloned())
@@ -320,9 +406,13 @@ impl<Key: NodeKey> Torin<Key> {
                 margin: Gaps::default(),
                 offset_x: Length::default(),
                 offset_y: Length::default(),
+                layout_direction: LayoutDirection::Ltr,
+                sticky_offset: Size2D::default(),
+                baseline: None,
                 data: None,
             });
-        let root = tree_adapter.get_node(&root_id).unwrap();
+        let mut root = tree_adapter.get_node(&root_id).unwrap();
+        root.layout_direction = root.layout_direction.resolve(layout_node.layout_direction);
 
         #[cfg(debug_assertions)]
         {
@@ -362,10 +452,18 @@ impl<Key: NodeKey> Torin<Key> {
             // Adjust the size of the area if needed
             root_layout_node.area.adjust_size(&root);
 
-            self.cache_node(root_id, root_layout_node);
+            measure_context.layout.cache_node(root_id, root_layout_node);
+
+            // Right-to-left parents mirror their children, so the root must be mirrored too
+            if root_parent_id.is_some() && layout_node.layout_direction.is_rtl() {
+                measure_context.mirror_node(root_id, &inner_area.cast_unit());
+            }
         }
 
-        self.dirty.clear();
+        // Pin the sticky Nodes now that everything else is in place
+        measure_context.stick_nodes();
+
+        self.stats.dirty = mem::take(&mut self.dirty);
         self.root_node_candidate = RootNodeCandidate::None;
     }
 
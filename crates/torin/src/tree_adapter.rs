@@ -13,6 +13,7 @@ use crate::{
         AreaOf,
         Gaps,
         Inner,
+        LayoutDirection,
         Length,
         Size2D,
     },
@@ -38,6 +39,15 @@ pub struct LayoutNode {
     pub offset_x: Length,
     pub offset_y: Length,
 
+    /// Resolved layout direction
+    pub layout_direction: LayoutDirection,
+
+    /// Offset applied to pin a sticky Node
+    pub sticky_offset: Size2D,
+
+    /// Distance from the top of the area to the baseline of its first line of text
+    pub baseline: Option<f32>,
+
     /// Associated data
     #[cfg_attr(feature = "serde", serde(skip_deserializing, skip_serializing))]
     pub data: Option<Rc<dyn Any>>,
@@ -58,6 +68,12 @@ impl LayoutNode {
     pub fn visible_area(&self) -> Area {
         self.area.without_gaps(&self.margin)
     }
+
+    // The area before being pinned, if it is sticky
+    pub fn natural_area(&self) -> Area {
+        self.area
+            .translate(-self.sticky_offset.to_vector().cast_unit())
+    }
 }
 
 pub trait NodeKey: Clone + PartialEq + Eq + std::hash::Hash + Copy + std::fmt::Debug {}
@@ -79,6 +95,11 @@ pub trait TreeAdapter<Key: NodeKey> {
     /// Get the children of a Node
     fn children_of(&mut self, node_id: &Key) -> Vec<Key>;
 
+    /// Whether the Node scrolls its content, sticky Nodes are pinned inside the closest one
+    fn is_scrollable(&self, _node_id: &Key) -> bool {
+        false
+    }
+
     /// Get the closest common parent Node of two Nodes
     fn closest_common_parent(
         &self,
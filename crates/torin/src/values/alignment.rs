@@ -14,6 +14,9 @@ pub enum Alignment {
     SpaceEvenly,
     /// Distribute children with equal space around them, half-size space at the edges.
     SpaceAround,
+    /// Align the first-line text baselines of the children, only applies to the cross axis of horizontal Nodes.
+    /// Children without text are aligned by their bottom edge.
+    Baseline,
 }
 
 impl Alignment {
@@ -47,6 +50,11 @@ impl Alignment {
         Alignment::SpaceAround
     }
 
+    /// Use a [`Baseline`](Alignment::Baseline) alignment.
+    pub fn baseline() -> Alignment {
+        Alignment::Baseline
+    }
+
     pub const fn is_not_start(&self) -> bool {
         !matches!(self, Self::Start)
     }
@@ -58,6 +66,10 @@ impl Alignment {
         )
     }
 
+    pub const fn is_baseline(&self) -> bool {
+        matches!(self, Self::Baseline)
+    }
+
     pub fn pretty(&self) -> String {
         match self {
             Self::Start => "start".to_string(),
@@ -66,6 +78,7 @@ impl Alignment {
             Self::SpaceBetween => "space-between".to_string(),
             Self::SpaceEvenly => "space-evenly".to_string(),
             Self::SpaceAround => "space-around".to_string(),
+            Self::Baseline => "baseline".to_string(),
         }
     }
 }
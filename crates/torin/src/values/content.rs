@@ -1,3 +1,8 @@
+use crate::{
+    grid::GridSize,
+    scaled::Scaled,
+};
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Clone, Debug, Default)]
 pub enum Content {
@@ -11,6 +16,13 @@ pub enum Content {
     /// Wrap children to the next line or column when they exceed the available space,
     /// with an optional gap between wrapped lines.
     Wrap { wrap_spacing: Option<f32> },
+    /// Place children in a two-dimensional grid of columns and rows.
+    /// Children choose their cell with [`GridPosition`](crate::grid::GridPosition)
+    /// and the node `spacing` is used as gap between tracks.
+    Grid {
+        columns: Vec<GridSize>,
+        rows: Vec<GridSize>,
+    },
 }
 
 impl Content {
@@ -41,6 +53,11 @@ impl Content {
         }
     }
 
+    /// Use a [`Grid`](Content::Grid) content with the given columns and rows.
+    pub fn grid(columns: Vec<GridSize>, rows: Vec<GridSize>) -> Content {
+        Content::Grid { columns, rows }
+    }
+
     pub fn is_fit(&self) -> bool {
         self == &Self::Fit
     }
@@ -53,6 +70,10 @@ impl Content {
         matches!(self, Self::Wrap { .. })
     }
 
+    pub fn is_grid(&self) -> bool {
+        matches!(self, Self::Grid { .. })
+    }
+
     pub fn allows_alignments(&self) -> bool {
         matches!(self, Self::Normal | Self::Flex | Self::Fit)
     }
@@ -65,6 +86,28 @@ impl Content {
             Self::Fit => "fit".to_owned(),
             Self::Flex => "flex".to_owned(),
             Self::Wrap { .. } => "wrap".to_owned(),
+            Self::Grid { columns, rows } => format!(
+                "grid({} / {})",
+                columns
+                    .iter()
+                    .map(GridSize::pretty)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                rows.iter()
+                    .map(GridSize::pretty)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+        }
+    }
+}
+
+impl Scaled for Content {
+    fn scale(&mut self, scale_factor: f32) {
+        if let Self::Grid { columns, rows } = self {
+            for track in columns.iter_mut().chain(rows.iter_mut()) {
+                track.scale(scale_factor);
+            }
         }
     }
 }
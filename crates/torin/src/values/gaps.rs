@@ -65,6 +65,11 @@ impl Gaps {
         self.fill_vertical(value);
     }
 
+    /// Swap the left and right gaps.
+    pub fn mirror_horizontal(&mut self) {
+        std::mem::swap(&mut self.left, &mut self.right);
+    }
+
     pub fn horizontal(&self) -> f32 {
         (self.right + self.left).get()
     }
@@ -0,0 +1,112 @@
+use crate::{
+    geometry::Length,
+    scaled::Scaled,
+};
+
+/// Size of a row or a column track in a [`Content::Grid`](crate::content::Content::Grid).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Clone, Debug, Default)]
+pub enum GridSize {
+    /// Sizes the track based on the biggest child that sits only on it. This is the default.
+    #[default]
+    Inner,
+
+    /// Fixed track size in pixels.
+    Pixels(Length),
+
+    /// Flex factor, shares the remaining space of the grid proportionally with other flex tracks.
+    Flex(Length),
+}
+
+impl GridSize {
+    /// Use an [`Inner`](GridSize::Inner) track size.
+    pub fn auto() -> GridSize {
+        GridSize::Inner
+    }
+
+    /// Use a [`Pixels`](GridSize::Pixels) track size.
+    pub fn px(px: impl Into<f32>) -> GridSize {
+        GridSize::Pixels(Length::new(px.into()))
+    }
+
+    /// Use a [`Flex`](GridSize::Flex) track size.
+    pub fn flex(flex: impl Into<f32>) -> GridSize {
+        GridSize::Flex(Length::new(flex.into()))
+    }
+
+    pub fn pretty(&self) -> String {
+        match self {
+            Self::Inner => "auto".to_string(),
+            Self::Pixels(s) => format!("{}", s.get()),
+            Self::Flex(f) => format!("flex({})", f.get()),
+        }
+    }
+}
+
+impl Scaled for GridSize {
+    fn scale(&mut self, scale_factor: f32) {
+        if let Self::Pixels(s) = self {
+            *s *= scale_factor;
+        }
+    }
+}
+
+/// Placement of a Node inside the tracks of its [`Content::Grid`](crate::content::Content::Grid) parent.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct GridPosition {
+    pub column: usize,
+    pub column_span: usize,
+    pub row: usize,
+    pub row_span: usize,
+}
+
+impl Default for GridPosition {
+    fn default() -> Self {
+        Self {
+            column: 0,
+            column_span: 1,
+            row: 0,
+            row_span: 1,
+        }
+    }
+}
+
+impl GridPosition {
+    pub fn new(column: usize, row: usize) -> Self {
+        Self {
+            column,
+            row,
+            ..Default::default()
+        }
+    }
+
+    #[must_use]
+    pub fn column_span(mut self, column_span: usize) -> Self {
+        self.column_span = column_span;
+        self
+    }
+
+    #[must_use]
+    pub fn row_span(mut self, row_span: usize) -> Self {
+        self.row_span = row_span;
+        self
+    }
+
+    /// Index of the last column occupied, exclusive.
+    pub fn column_end(&self) -> usize {
+        self.column + self.column_span.max(1)
+    }
+
+    /// Index of the last row occupied, exclusive.
+    pub fn row_end(&self) -> usize {
+        self.row + self.row_span.max(1)
+    }
+
+    pub fn pretty(&self) -> String {
+        format!(
+            "column {} (span {}), row {} (span {})",
+            self.column, self.column_span, self.row, self.row_span
+        )
+    }
+}
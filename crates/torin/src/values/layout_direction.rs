@@ -0,0 +1,53 @@
+/// Horizontal flow of a Node's children.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, Clone, Debug, Default, Copy, Hash)]
+pub enum LayoutDirection {
+    /// Use the direction of the parent Node, left-to-right for the root. This is the default.
+    #[default]
+    Inherit,
+    /// Left-to-right.
+    Ltr,
+    /// Right-to-left, mirrors the horizontal start and end, padding, margins and absolute positions.
+    Rtl,
+}
+
+impl LayoutDirection {
+    /// Use an [`Inherit`](LayoutDirection::Inherit) layout direction.
+    pub fn inherit() -> LayoutDirection {
+        LayoutDirection::Inherit
+    }
+
+    /// Use a [`Ltr`](LayoutDirection::Ltr) layout direction.
+    pub fn ltr() -> LayoutDirection {
+        LayoutDirection::Ltr
+    }
+
+    /// Use a [`Rtl`](LayoutDirection::Rtl) layout direction.
+    pub fn rtl() -> LayoutDirection {
+        LayoutDirection::Rtl
+    }
+
+    /// Resolve this direction against the already resolved direction of the parent Node.
+    #[must_use]
+    pub fn resolve(self, parent: LayoutDirection) -> LayoutDirection {
+        match self {
+            Self::Inherit => match parent {
+                Self::Inherit => Self::Ltr,
+                parent => parent,
+            },
+            direction => direction,
+        }
+    }
+
+    pub fn is_rtl(&self) -> bool {
+        *self == Self::Rtl
+    }
+
+    pub fn pretty(&self) -> String {
+        match self {
+            Self::Inherit => "inherit".to_string(),
+            Self::Ltr => "ltr".to_string(),
+            Self::Rtl => "rtl".to_string(),
+        }
+    }
+}
@@ -2,6 +2,8 @@ pub mod alignment;
 pub mod content;
 pub mod direction;
 pub mod gaps;
+pub mod grid;
+pub mod layout_direction;
 pub mod position;
 pub mod size;
 pub mod visible_size;
@@ -12,6 +14,8 @@ pub mod prelude {
         content::*,
         direction::*,
         gaps::*,
+        grid::*,
+        layout_direction::*,
         position::*,
         size::*,
         visible_size::*,
@@ -26,6 +26,10 @@ pub enum Position {
 
     Absolute(Box<PositionSides>),
     Global(Box<PositionSides>),
+
+    /// Stacked like [Position::Stacked], but pinned at the `top` and `left` distances
+    /// from the closest scrollable ancestor while its parent is in view.
+    Sticky(Box<PositionSides>),
 }
 
 impl Default for Position {
@@ -62,6 +66,15 @@ impl Position {
         }))
     }
 
+    pub fn new_sticky() -> Self {
+        Self::Sticky(Box::new(PositionSides {
+            top: None,
+            right: None,
+            bottom: None,
+            left: None,
+        }))
+    }
+
     #[must_use]
     pub fn top(mut self, value: f32) -> Self {
         self.position_mut().top = Some(value);
@@ -88,12 +101,16 @@ impl Position {
 
     fn position_mut(&mut self) -> &mut PositionSides {
         match self {
-            Self::Absolute(position) | Self::Global(position) | Self::Stacked(position) => position,
+            Self::Absolute(position)
+            | Self::Global(position)
+            | Self::Stacked(position)
+            | Self::Sticky(position) => position,
         }
     }
 
+    /// Whether it is stacked along with its siblings, which includes [Position::Sticky].
     pub fn is_stacked(&self) -> bool {
-        matches!(self, Self::Stacked { .. })
+        matches!(self, Self::Stacked { .. } | Self::Sticky { .. })
     }
 
     pub fn is_absolute(&self) -> bool {
@@ -104,6 +121,10 @@ impl Position {
         matches!(self, Self::Global { .. })
     }
 
+    pub fn is_sticky(&self) -> bool {
+        matches!(self, Self::Sticky { .. })
+    }
+
     pub(crate) fn get_origin(
         &self,
         available_parent_area: &AreaOf<Available>,
@@ -112,7 +133,7 @@ impl Position {
         root_area: &Area,
     ) -> Point2D {
         match self {
-            Self::Stacked(_) => available_parent_area.origin.cast_unit(),
+            Self::Stacked(_) | Self::Sticky(_) => available_parent_area.origin.cast_unit(),
             Self::Absolute(absolute_position) => {
                 let PositionSides {
                     top,
@@ -174,7 +195,7 @@ impl Position {
 impl Scaled for Position {
     fn scale(&mut self, scale_factor: f32) {
         match self {
-            Self::Absolute(position) | Self::Global(position) => {
+            Self::Absolute(position) | Self::Global(position) | Self::Sticky(position) => {
                 if let Some(top) = &mut position.top {
                     *top *= scale_factor;
                 }
@@ -197,6 +218,11 @@ impl Position {
     pub fn pretty(&self) -> String {
         match self {
             Self::Stacked(_) => "stacked".to_string(),
+            Self::Sticky(positions) => format!(
+                "sticky {}, {}",
+                positions.top.unwrap_or_default(),
+                positions.left.unwrap_or_default()
+            ),
             Self::Absolute(positions) | Self::Global(positions) => format!(
                 "{}, {}, {}, {}",
                 positions.top.unwrap_or_default(),
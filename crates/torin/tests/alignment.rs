@@ -663,3 +663,116 @@ pub fn alignment_with_absolute_child() {
         Rect::new(Point2D::new(450.0, 507.5), Size2D::new(100.0, 100.0)),
     );
 }
+
+#[test]
+pub fn align_self() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1, 2, 3],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(200.0)),
+            Size::Pixels(Length::new(200.0)),
+            Direction::Horizontal,
+        ),
+    );
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(50.0)),
+            Direction::Vertical,
+        ),
+    );
+    let mut centered = Node::from_size_and_direction(
+        Size::Pixels(Length::new(50.0)),
+        Size::Pixels(Length::new(50.0)),
+        Direction::Vertical,
+    );
+    centered.align_self = Some(Alignment::Center);
+    mocked_tree.add(2, Some(0), vec![], centered);
+    let mut end = Node::from_size_and_direction(
+        Size::Pixels(Length::new(50.0)),
+        Size::Pixels(Length::new(50.0)),
+        Direction::Vertical,
+    );
+    end.align_self = Some(Alignment::End);
+    mocked_tree.add(3, Some(0), vec![], end);
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(50.0, 50.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(50.0, 75.0), Size2D::new(50.0, 50.0)),
+    );
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(100.0, 150.0), Size2D::new(50.0, 50.0)),
+    );
+}
+
+#[test]
+pub fn align_self_overrides_parent() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1, 2],
+        Node::from_size_and_alignments_and_direction(
+            Size::Pixels(Length::new(200.0)),
+            Size::Pixels(Length::new(200.0)),
+            Alignment::Start,
+            Alignment::Center,
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(50.0)),
+            Direction::Vertical,
+        ),
+    );
+    let mut start = Node::from_size_and_direction(
+        Size::Pixels(Length::new(50.0)),
+        Size::Pixels(Length::new(50.0)),
+        Direction::Vertical,
+    );
+    start.align_self = Some(Alignment::Start);
+    mocked_tree.add(2, Some(0), vec![], start);
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(75.0, 0.0), Size2D::new(50.0, 50.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(0.0, 50.0), Size2D::new(50.0, 50.0)),
+    );
+}
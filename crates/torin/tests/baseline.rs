@@ -0,0 +1,166 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    rc::Rc,
+};
+
+use torin::{
+    prelude::*,
+    test_utils::*,
+};
+
+/// Measurer that reports a fixed baseline for some Nodes, like text would.
+struct BaselineMeasurer(HashMap<usize, f32>);
+
+impl LayoutMeasurer<usize> for BaselineMeasurer {
+    fn measure(
+        &mut self,
+        _node_id: usize,
+        _node: &Node,
+        size: &Size2D,
+    ) -> Option<(Size2D, Rc<dyn Any>)> {
+        Some((*size, Rc::new(())))
+    }
+
+    fn should_hook_measurement(&mut self, node_id: usize) -> bool {
+        self.0.contains_key(&node_id)
+    }
+
+    fn should_measure_inner_children(&mut self, node_id: usize) -> bool {
+        !self.0.contains_key(&node_id)
+    }
+
+    fn baseline(&mut self, node_id: usize, _data: &Rc<dyn Any>) -> Option<f32> {
+        self.0.get(&node_id).copied()
+    }
+}
+
+#[test]
+pub fn baseline_alignment() {
+    let (mut layout, _) = test_utils();
+    let mut measurer = Some(BaselineMeasurer(HashMap::from([(1, 16.), (2, 30.)])));
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1, 2, 3],
+        Node::from_size_and_alignments_and_direction(
+            Size::Inner,
+            Size::Inner,
+            Alignment::Start,
+            Alignment::Baseline,
+            Direction::Horizontal,
+        ),
+    );
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![],
+        Node::from_size_and_margin(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(20.0)),
+            Gaps::new(5.0, 0.0, 0.0, 0.0),
+        ),
+    );
+    mocked_tree.add(
+        2,
+        Some(0),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(40.0)),
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.add(
+        3,
+        Some(0),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(10.0)),
+            Direction::Vertical,
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&0).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(150.0, 40.0)),
+    );
+    assert_eq!(
+        layout.get(&1).unwrap().visible_area(),
+        Rect::new(Point2D::new(0.0, 14.0), Size2D::new(50.0, 20.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(50.0, 0.0), Size2D::new(50.0, 40.0)),
+    );
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(100.0, 20.0), Size2D::new(50.0, 10.0)),
+    );
+}
+
+#[test]
+pub fn baseline_alignment_grows_parent() {
+    let (mut layout, _) = test_utils();
+    let mut measurer = Some(BaselineMeasurer(HashMap::from([(1, 30.), (2, 5.)])));
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1, 2],
+        Node::from_size_and_alignments_and_direction(
+            Size::Inner,
+            Size::Inner,
+            Alignment::Start,
+            Alignment::Baseline,
+            Direction::Horizontal,
+        ),
+    );
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(40.0)),
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.add(
+        2,
+        Some(0),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(30.0)),
+            Direction::Vertical,
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&0).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(100.0, 55.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(50.0, 25.0), Size2D::new(50.0, 30.0)),
+    );
+}
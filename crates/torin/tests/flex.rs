@@ -495,3 +495,238 @@ pub fn flex_root_candidate_resolution() {
         Rect::new(Point2D::new(0.0, 150.0), Size2D::new(100.0, 50.0)),
     );
 }
+
+#[test]
+pub fn flex_shrink() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1, 2, 3],
+        Node::from_size_and_content(
+            Size::Pixels(Length::new(200.0)),
+            Size::Pixels(Length::new(100.0)),
+            Content::Flex,
+        ),
+    );
+    let mut first = Node::from_size_and_direction(
+        Size::Pixels(Length::new(100.0)),
+        Size::Pixels(Length::new(150.0)),
+        Direction::Vertical,
+    );
+    first.flex_shrink = Length::new(1.0);
+    mocked_tree.add(1, Some(0), vec![], first);
+    let mut second = Node::from_size_and_direction(
+        Size::Pixels(Length::new(100.0)),
+        Size::Pixels(Length::new(50.0)),
+        Direction::Vertical,
+    );
+    second.flex_shrink = Length::new(1.0);
+    mocked_tree.add(2, Some(0), vec![], second);
+    // Does not shrink
+    mocked_tree.add(
+        3,
+        Some(0),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(20.0)),
+            Direction::Vertical,
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    // 120 of overflow split by the shrink factors weighted by their sizes (150 and 50)
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(100.0, 60.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(0.0, 60.0), Size2D::new(100.0, 20.0)),
+    );
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(0.0, 80.0), Size2D::new(100.0, 20.0)),
+    );
+}
+
+#[test]
+pub fn flex_shrink_min_size() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1, 2, 3],
+        Node::from_size_and_content(
+            Size::Pixels(Length::new(200.0)),
+            Size::Pixels(Length::new(200.0)),
+            Content::Flex,
+        ),
+    );
+    let mut first = Node::from_size_and_direction(
+        Size::Pixels(Length::new(100.0)),
+        Size::Pixels(Length::new(150.0)),
+        Direction::Vertical,
+    );
+    first.flex_shrink = Length::new(1.0);
+    first.minimum_height = Size::Pixels(Length::new(120.0));
+    mocked_tree.add(1, Some(0), vec![], first);
+    let mut second = Node::from_size_and_direction(
+        Size::Pixels(Length::new(100.0)),
+        Size::Pixels(Length::new(100.0)),
+        Direction::Vertical,
+    );
+    second.flex_shrink = Length::new(1.0);
+    mocked_tree.add(2, Some(0), vec![], second);
+    mocked_tree.add(
+        3,
+        Some(0),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(20.0)),
+            Direction::Vertical,
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    // The first child can only absorb 30 of the 70 of overflow, the rest goes to the second one
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(100.0, 120.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(0.0, 120.0), Size2D::new(100.0, 60.0)),
+    );
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(0.0, 180.0), Size2D::new(100.0, 20.0)),
+    );
+}
+
+#[test]
+pub fn flex_shrink_without_overflow() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1],
+        Node::from_size_and_content(
+            Size::Pixels(Length::new(200.0)),
+            Size::Pixels(Length::new(200.0)),
+            Content::Flex,
+        ),
+    );
+    let mut child = Node::from_size_and_direction(
+        Size::Pixels(Length::new(100.0)),
+        Size::Pixels(Length::new(150.0)),
+        Direction::Vertical,
+    );
+    child.flex_shrink = Length::new(1.0);
+    mocked_tree.add(1, Some(0), vec![], child);
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(100.0, 150.0)),
+    );
+}
+
+#[test]
+pub fn flex_shrink_invalidation() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1, 2],
+        Node::from_size_and_content(
+            Size::Pixels(Length::new(200.0)),
+            Size::Pixels(Length::new(100.0)),
+            Content::Flex,
+        ),
+    );
+    let mut first = Node::from_size_and_direction(
+        Size::Pixels(Length::new(100.0)),
+        Size::Pixels(Length::new(100.0)),
+        Direction::Vertical,
+    );
+    first.flex_shrink = Length::new(1.0);
+    mocked_tree.add(1, Some(0), vec![], first);
+    mocked_tree.add(
+        2,
+        Some(0),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(20.0)),
+            Direction::Vertical,
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(100.0, 80.0)),
+    );
+
+    // Growing the last sibling shrinks the first one even more
+    mocked_tree.set_node(
+        2,
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(60.0)),
+            Direction::Vertical,
+        ),
+    );
+    layout.invalidate(2);
+    layout.find_best_root(&mut mocked_tree);
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(100.0, 40.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(0.0, 40.0), Size2D::new(100.0, 60.0)),
+    );
+}
@@ -0,0 +1,220 @@
+use euclid::Length;
+use torin::{
+    prelude::*,
+    test_utils::*,
+};
+
+#[test]
+pub fn grid_fixed_and_flex_tracks() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    let mut root = Node::from_size_and_content(
+        Size::Pixels(Length::new(300.0)),
+        Size::Pixels(Length::new(200.0)),
+        Content::grid(
+            vec![
+                GridSize::px(100.0),
+                GridSize::flex(1.0),
+                GridSize::flex(1.0),
+            ],
+            vec![GridSize::px(50.0), GridSize::flex(1.0)],
+        ),
+    );
+    root.spacing = Length::new(10.0);
+    mocked_tree.add(0, None, vec![1, 2, 3], root);
+
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![],
+        Node::from_size_and_grid_position(Size::Fill, Size::Fill, GridPosition::new(0, 0)),
+    );
+    mocked_tree.add(
+        2,
+        Some(0),
+        vec![],
+        Node::from_size_and_grid_position(
+            Size::Fill,
+            Size::Fill,
+            GridPosition::new(1, 0).column_span(2),
+        ),
+    );
+    mocked_tree.add(
+        3,
+        Some(0),
+        vec![],
+        Node::from_size_and_grid_position(
+            Size::Pixels(Length::new(20.0)),
+            Size::Fill,
+            GridPosition::new(2, 1),
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(100.0, 50.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(110.0, 0.0), Size2D::new(190.0, 50.0)),
+    );
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(210.0, 60.0), Size2D::new(20.0, 140.0)),
+    );
+}
+
+#[test]
+pub fn grid_auto_tracks() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1, 2, 3],
+        Node::from_size_and_content(
+            Size::Inner,
+            Size::Inner,
+            Content::grid(
+                vec![GridSize::auto(), GridSize::auto()],
+                vec![GridSize::auto()],
+            ),
+        ),
+    );
+
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![],
+        Node::from_size_and_grid_position(
+            Size::Pixels(Length::new(40.0)),
+            Size::Pixels(Length::new(30.0)),
+            GridPosition::new(0, 0),
+        ),
+    );
+    mocked_tree.add(
+        2,
+        Some(0),
+        vec![],
+        Node::from_size_and_grid_position(
+            Size::Pixels(Length::new(70.0)),
+            Size::Pixels(Length::new(10.0)),
+            GridPosition::new(1, 0),
+        ),
+    );
+    // Implicit row
+    mocked_tree.add(
+        3,
+        Some(0),
+        vec![],
+        Node::from_size_and_grid_position(
+            Size::Pixels(Length::new(60.0)),
+            Size::Pixels(Length::new(25.0)),
+            GridPosition::new(0, 1),
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&0).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(130.0, 55.0)),
+    );
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(40.0, 30.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(60.0, 0.0), Size2D::new(70.0, 10.0)),
+    );
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(0.0, 30.0), Size2D::new(60.0, 25.0)),
+    );
+}
+
+#[test]
+pub fn grid_relayout_sibling() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1, 2],
+        Node::from_size_and_content(
+            Size::Pixels(Length::new(300.0)),
+            Size::Pixels(Length::new(300.0)),
+            Content::grid(vec![GridSize::auto(), GridSize::flex(1.0)], vec![]),
+        ),
+    );
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![],
+        Node::from_size_and_grid_position(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(50.0)),
+            GridPosition::new(0, 0),
+        ),
+    );
+    mocked_tree.add(
+        2,
+        Some(0),
+        vec![],
+        Node::from_size_and_grid_position(
+            Size::Fill,
+            Size::Pixels(Length::new(50.0)),
+            GridPosition::new(1, 0),
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(50.0, 0.0), Size2D::new(250.0, 50.0)),
+    );
+
+    mocked_tree.set_node(
+        1,
+        Node::from_size_and_grid_position(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(50.0)),
+            GridPosition::new(0, 0),
+        ),
+    );
+    layout.invalidate(1);
+    layout.find_best_root(&mut mocked_tree);
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(100.0, 0.0), Size2D::new(200.0, 50.0)),
+    );
+}
@@ -0,0 +1,269 @@
+use euclid::Length;
+use torin::{
+    prelude::*,
+    test_utils::*,
+};
+
+#[test]
+pub fn rtl_horizontal() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    let mut root = Node::from_size_and_layout_direction(
+        Size::Pixels(Length::new(300.0)),
+        Size::Pixels(Length::new(100.0)),
+        LayoutDirection::Rtl,
+    );
+    root.direction = Direction::Horizontal;
+    root.padding = Gaps::new(0.0, 0.0, 0.0, 10.0);
+    mocked_tree.add(0, None, vec![1, 2], root);
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(50.0)),
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.add(
+        2,
+        Some(0),
+        vec![],
+        Node::from_size_and_margin(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(50.0)),
+            Gaps::new(0.0, 0.0, 0.0, 20.0),
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&0).unwrap().inner_area.cast_unit(),
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(290.0, 100.0)),
+    );
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(190.0, 0.0), Size2D::new(100.0, 50.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().visible_area(),
+        Rect::new(Point2D::new(120.0, 0.0), Size2D::new(50.0, 50.0)),
+    );
+}
+
+#[test]
+pub fn rtl_inherited() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1, 3],
+        Node::from_size_and_layout_direction(
+            Size::Pixels(Length::new(300.0)),
+            Size::Pixels(Length::new(300.0)),
+            LayoutDirection::Rtl,
+        ),
+    );
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![2],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(200.0)),
+            Size::Pixels(Length::new(100.0)),
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.add(
+        2,
+        Some(1),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(50.0)),
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.add(
+        3,
+        Some(0),
+        vec![4],
+        Node::from_size_and_layout_direction(
+            Size::Pixels(Length::new(200.0)),
+            Size::Pixels(Length::new(100.0)),
+            LayoutDirection::Ltr,
+        ),
+    );
+    mocked_tree.add(
+        4,
+        Some(3),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(50.0)),
+            Direction::Vertical,
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(100.0, 0.0), Size2D::new(200.0, 100.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(250.0, 0.0), Size2D::new(50.0, 50.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().layout_direction,
+        LayoutDirection::Rtl
+    );
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(100.0, 100.0), Size2D::new(200.0, 100.0)),
+    );
+    assert_eq!(
+        layout.get(&4).unwrap().area,
+        Rect::new(Point2D::new(100.0, 100.0), Size2D::new(50.0, 50.0)),
+    );
+}
+
+#[test]
+pub fn rtl_absolute_position() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1],
+        Node::from_size_and_layout_direction(
+            Size::Pixels(Length::new(300.0)),
+            Size::Pixels(Length::new(300.0)),
+            LayoutDirection::Rtl,
+        ),
+    );
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![],
+        Node::from_size_and_position(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(50.0)),
+            Position::new_absolute().top(10.0).left(20.0),
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(230.0, 10.0), Size2D::new(50.0, 50.0)),
+    );
+}
+
+#[test]
+pub fn rtl_relayout_sibling() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1],
+        Node::from_size_and_layout_direction(
+            Size::Pixels(Length::new(400.0)),
+            Size::Pixels(Length::new(100.0)),
+            LayoutDirection::Rtl,
+        ),
+    );
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![2, 3],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(300.0)),
+            Size::Pixels(Length::new(100.0)),
+            Direction::Horizontal,
+        ),
+    );
+    mocked_tree.add(
+        2,
+        Some(1),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(50.0)),
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.add(
+        3,
+        Some(1),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(50.0)),
+            Direction::Vertical,
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(300.0, 0.0), Size2D::new(50.0, 50.0)),
+    );
+
+    mocked_tree.set_node(
+        2,
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(50.0)),
+            Direction::Vertical,
+        ),
+    );
+    layout.invalidate(2);
+    layout.find_best_root(&mut mocked_tree);
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(300.0, 0.0), Size2D::new(100.0, 50.0)),
+    );
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(250.0, 0.0), Size2D::new(50.0, 50.0)),
+    );
+}
@@ -464,3 +464,127 @@ pub fn global_with_inner_sized_and_bottom() {
         Rect::new(Point2D::new(20.0, 460.0), Size2D::new(80.0, 30.0)),
     );
 }
+
+#[test]
+pub fn sticky() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(100.0)),
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.set_scrollable(0);
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![2, 3],
+        Node::from_size_and_offset(Size::Fill, Size::Fill, Length::new(0.), Length::new(-50.)),
+    );
+    mocked_tree.add(
+        2,
+        Some(1),
+        vec![4],
+        Node::from_size_and_position(
+            Size::Fill,
+            Size::Pixels(Length::new(20.0)),
+            Position::new_sticky().top(0.0),
+        ),
+    );
+    mocked_tree.add(
+        3,
+        Some(1),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Fill,
+            Size::Pixels(Length::new(200.0)),
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.add(
+        4,
+        Some(2),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(10.0)),
+            Size::Pixels(Length::new(10.0)),
+            Direction::Vertical,
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(100.0, 20.0)),
+    );
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(0.0, -30.0), Size2D::new(100.0, 200.0)),
+    );
+    assert_eq!(
+        layout.get(&4).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(10.0, 10.0)),
+    );
+
+    // Scroll until the parent is almost out of view
+    mocked_tree.set_node(
+        1,
+        Node::from_size_and_offset(Size::Fill, Size::Fill, Length::new(0.), Length::new(-300.)),
+    );
+    layout.invalidate_with_reason(1, DirtyReason::InnerLayout);
+    layout.find_best_root(&mut mocked_tree);
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(0.0, -100.0), Size2D::new(100.0, 20.0)),
+    );
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(0.0, -280.0), Size2D::new(100.0, 200.0)),
+    );
+    assert_eq!(
+        layout.get(&4).unwrap().area,
+        Rect::new(Point2D::new(0.0, -100.0), Size2D::new(10.0, 10.0)),
+    );
+
+    // Scroll back to the start
+    mocked_tree.set_node(
+        1,
+        Node::from_size_and_offset(Size::Fill, Size::Fill, Length::new(0.), Length::new(0.)),
+    );
+    layout.invalidate_with_reason(1, DirtyReason::InnerLayout);
+    layout.find_best_root(&mut mocked_tree);
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(100.0, 20.0)),
+    );
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(0.0, 20.0), Size2D::new(100.0, 200.0)),
+    );
+}
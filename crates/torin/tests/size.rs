@@ -943,3 +943,90 @@ pub fn size_fn() {
         Rect::new(Point2D::new(0.0, 0.0), Size2D::new(250.0, 1000.0)),
     );
 }
+
+#[test]
+pub fn aspect_ratio() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1, 2, 3],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(400.0)),
+            Size::Pixels(Length::new(400.0)),
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![],
+        Node::from_size_and_aspect_ratio(Size::Pixels(Length::new(160.0)), Size::Inner, 16. / 9.),
+    );
+    mocked_tree.add(
+        2,
+        Some(0),
+        vec![],
+        Node::from_size_and_aspect_ratio(Size::Inner, Size::Pixels(Length::new(50.0)), 2.),
+    );
+    let mut constrained = Node::from_size_and_aspect_ratio(Size::Fill, Size::Inner, 2.);
+    constrained.maximum_height = Size::Pixels(Length::new(100.0));
+    mocked_tree.add(3, Some(0), vec![], constrained);
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&1).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(160.0, 90.0)),
+    );
+    assert_eq!(
+        layout.get(&2).unwrap().area,
+        Rect::new(Point2D::new(0.0, 90.0), Size2D::new(100.0, 50.0)),
+    );
+    assert_eq!(
+        layout.get(&3).unwrap().area,
+        Rect::new(Point2D::new(0.0, 140.0), Size2D::new(200.0, 100.0)),
+    );
+}
+
+#[test]
+pub fn aspect_ratio_inner_sized() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1],
+        Node::from_size_and_aspect_ratio(Size::Inner, Size::Inner, 1.),
+    );
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(10.0)),
+            Direction::Vertical,
+        ),
+    );
+
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+
+    assert_eq!(
+        layout.get(&0).unwrap().area,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(100.0, 100.0)),
+    );
+}
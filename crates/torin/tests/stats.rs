@@ -0,0 +1,176 @@
+use rustc_hash::FxHashMap;
+use torin::{
+    prelude::*,
+    test_utils::*,
+};
+
+#[test]
+pub fn layout_stats() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    mocked_tree.add(
+        0,
+        None,
+        vec![1, 2],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(200.0)),
+            Size::Pixels(Length::new(200.0)),
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![3],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(100.0)),
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.add(
+        2,
+        Some(0),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(100.0)),
+            Direction::Vertical,
+        ),
+    );
+    mocked_tree.add(
+        3,
+        Some(1),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(50.0)),
+            Size::Pixels(Length::new(50.0)),
+            Direction::Vertical,
+        ),
+    );
+
+    layout.find_best_root(&mut mocked_tree);
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+    assert_eq!(layout.stats().root, Some(0));
+    assert_eq!(layout.stats().measured_nodes, 4);
+    assert_eq!(layout.stats().cached_nodes, 0);
+
+    mocked_tree.set_node(
+        2,
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(50.0)),
+            Direction::Vertical,
+        ),
+    );
+    layout.invalidate(2);
+    layout.find_best_root(&mut mocked_tree);
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+    assert_eq!(layout.stats().measured_nodes, 1);
+    assert_eq!(layout.stats().cached_nodes, 3);
+    assert_eq!(layout.stats().visited_nodes(), 4);
+    assert_eq!(
+        layout.stats().dirty,
+        FxHashMap::from_iter([(2, DirtyReason::None)])
+    );
+    assert!(layout.get_dirty_nodes().is_empty());
+
+    // Nothing changed so the measurement is skipped
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+    assert_eq!(layout.stats(), &LayoutStats::default());
+}
+
+#[test]
+pub fn layout_stats_initial_phase() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    // Centered children are measured in both the initial and final phases
+    let mut parent = Node::from_size_and_direction(
+        Size::Pixels(Length::new(200.0)),
+        Size::Pixels(Length::new(200.0)),
+        Direction::Vertical,
+    );
+    parent.main_alignment = Alignment::Center;
+    mocked_tree.add(0, None, vec![1, 2], parent);
+    for id in [1, 2] {
+        mocked_tree.add(
+            id,
+            Some(0),
+            vec![],
+            Node::from_size_and_direction(
+                Size::Pixels(Length::new(100.0)),
+                Size::Pixels(Length::new(50.0)),
+                Direction::Vertical,
+            ),
+        );
+    }
+
+    layout.find_best_root(&mut mocked_tree);
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+    assert_eq!(layout.stats().measured_nodes, 3);
+    assert_eq!(layout.stats().visited_nodes(), 3);
+}
+
+#[test]
+pub fn layout_stats_initial_phase_nested() {
+    let (mut layout, mut measurer) = test_utils();
+
+    let mut mocked_tree = TestingTree::default();
+    // The subtree of an inner sized child is measured during the initial phase of its centered parent too
+    let mut parent = Node::from_size_and_direction(
+        Size::Pixels(Length::new(200.0)),
+        Size::Pixels(Length::new(200.0)),
+        Direction::Vertical,
+    );
+    parent.main_alignment = Alignment::Center;
+    mocked_tree.add(0, None, vec![1], parent);
+    mocked_tree.add(
+        1,
+        Some(0),
+        vec![2],
+        Node::from_size_and_direction(Size::Inner, Size::Inner, Direction::Vertical),
+    );
+    mocked_tree.add(
+        2,
+        Some(1),
+        vec![],
+        Node::from_size_and_direction(
+            Size::Pixels(Length::new(100.0)),
+            Size::Pixels(Length::new(50.0)),
+            Direction::Vertical,
+        ),
+    );
+
+    layout.find_best_root(&mut mocked_tree);
+    layout.measure(
+        0,
+        Rect::new(Point2D::new(0.0, 0.0), Size2D::new(1000.0, 1000.0)),
+        &mut measurer,
+        &mut mocked_tree,
+    );
+    assert_eq!(layout.stats().measured_nodes, 3);
+    assert_eq!(layout.stats().visited_nodes(), 3);
+    assert_eq!(layout.get(&1).unwrap().area.size, Size2D::new(100.0, 50.0));
+}
@@ -0,0 +1,36 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+use freya::prelude::*;
+
+fn main() {
+    launch(LaunchConfig::new().with_window(WindowConfig::new(app)))
+}
+
+fn app() -> impl IntoElement {
+    ScrollView::new().children((0..5).map(|section| {
+        rect()
+            .width(Size::fill())
+            .child(
+                rect()
+                    .width(Size::fill())
+                    .padding(8.)
+                    .background((230, 230, 230))
+                    // Pin the header at the top of the scroll view while its section is visible
+                    .position(Position::new_sticky().top(0.))
+                    // Render it above the rest of the section
+                    .layer(1)
+                    .child(format!("Section {section}")),
+            )
+            .children((0..10).map(|item| {
+                rect()
+                    .width(Size::fill())
+                    .height(Size::px(40.))
+                    .padding(8.)
+                    .child(format!("Item {item}"))
+                    .into()
+            }))
+            .into()
+    }))
+}
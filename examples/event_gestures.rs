@@ -0,0 +1,37 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+use freya::prelude::*;
+
+fn main() {
+    launch(LaunchConfig::new().with_window(WindowConfig::new(app)))
+}
+
+fn app() -> impl IntoElement {
+    let mut scale = use_state(|| 1.);
+    let mut rotation = use_state(|| 0.);
+    let mut swipes = use_state(|| 0);
+
+    rect()
+        .expanded()
+        .center()
+        .on_pinch(move |e: Event<GestureEventData>| {
+            *scale.write() = (scale() * e.scale).clamp(0.25, 4.)
+        })
+        .on_rotate(move |e: Event<GestureEventData>| *rotation.write() += e.rotation)
+        .on_swipe(move |_| *swipes.write() += 1)
+        .child(
+            rect()
+                .width(Size::px(150.))
+                .height(Size::px(150.))
+                .corner_radius(8.)
+                .background((110, 160, 255))
+                .scale(scale() as f32)
+                .rotation(rotation() as f32),
+        )
+        .child(format!(
+            "Pinch or rotate with two fingers. Swipes: {}",
+            swipes()
+        ))
+}
@@ -0,0 +1,38 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+use freya::prelude::*;
+
+fn main() {
+    launch(LaunchConfig::new().with_window(WindowConfig::new(app)))
+}
+
+fn app() -> impl IntoElement {
+    let mut position = use_state(|| CursorPoint::new(50., 50.));
+    let mut grab_offset = use_state(|| None::<CursorPoint>);
+
+    rect().expanded().child(
+        rect()
+            .position(
+                Position::new_absolute()
+                    .left(position().x as f32)
+                    .top(position().y as f32),
+            )
+            .width(Size::px(100.))
+            .height(Size::px(100.))
+            .corner_radius(8.)
+            .background((110, 160, 255))
+            .on_pointer_down(move |e: Event<PointerEventData>| {
+                // Keep receiving the pointer movement even if it leaves the square or the window
+                e.capture_pointer();
+                grab_offset.set(Some(e.element_location()));
+            })
+            .on_mouse_move(move |e: Event<MouseEventData>| {
+                if let Some(grab_offset) = grab_offset() {
+                    position.set(e.global_location - grab_offset.to_vector());
+                }
+            })
+            .on_pointer_press(move |_| grab_offset.set(None)),
+    )
+}
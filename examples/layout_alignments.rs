@@ -79,4 +79,19 @@ fn app() -> impl IntoElement {
                 .main_align(Alignment::SpaceEvenly)
                 .children(cards()),
         )
+        .child(
+            rect()
+                .width(Size::fill())
+                .horizontal()
+                .spacing(8.)
+                .cross_align(Alignment::Baseline)
+                .child(label().text("Name").font_size(32.))
+                .child(label().text("first and last").font_size(14.))
+                .child(
+                    rect()
+                        .width(Size::px(16.))
+                        .height(Size::px(16.))
+                        .background((50, 50, 255)),
+                ),
+        )
 }
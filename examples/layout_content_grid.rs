@@ -0,0 +1,43 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+use freya::prelude::*;
+
+fn main() {
+    launch(LaunchConfig::new().with_window(WindowConfig::new(app)))
+}
+
+fn cell(color: (u8, u8, u8), column: usize, row: usize) -> Rect {
+    rect()
+        .expanded()
+        .background(color)
+        .corner_radius(8.)
+        .center()
+        .grid_column(column)
+        .grid_row(row)
+        .child(format!("{column}, {row}"))
+}
+
+fn app() -> impl IntoElement {
+    rect()
+        .expanded()
+        .padding(10.)
+        .spacing(10.)
+        .content(Content::grid(
+            vec![GridSize::px(150.), GridSize::flex(1.), GridSize::flex(2.)],
+            vec![GridSize::auto(), GridSize::flex(1.), GridSize::px(80.)],
+        ))
+        .child(
+            rect()
+                .width(Size::fill())
+                .padding(10.)
+                .background((230, 230, 230))
+                .grid_column_span(3)
+                .child("Header spanning all the columns"),
+        )
+        .child(cell((255, 120, 120), 0, 1).grid_row_span(2))
+        .child(cell((120, 255, 120), 1, 1))
+        .child(cell((120, 120, 255), 2, 1))
+        .child(cell((200, 120, 200), 1, 2).grid_column_span(2))
+}
@@ -0,0 +1,58 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+use freya::prelude::*;
+
+fn main() {
+    launch(LaunchConfig::new().with_window(WindowConfig::new(app)))
+}
+
+fn app() -> impl IntoElement {
+    let mut rtl = use_state(|| true);
+
+    let layout_direction = if rtl() {
+        LayoutDirection::Rtl
+    } else {
+        LayoutDirection::Ltr
+    };
+
+    rect()
+        .expanded()
+        .padding((10., 10., 10., 60.))
+        .spacing(10.)
+        .layout_direction(layout_direction)
+        .child(
+            Button::new()
+                .on_press(move |_| rtl.toggle())
+                .child("Toggle"),
+        )
+        .child(
+            rect()
+                .horizontal()
+                .spacing(10.)
+                .child(
+                    rect()
+                        .width(Size::px(100.))
+                        .height(Size::px(50.))
+                        .background((255, 120, 120)),
+                )
+                .child(
+                    rect()
+                        .width(Size::px(50.))
+                        .height(Size::px(50.))
+                        .background((120, 255, 120)),
+                )
+                .child(
+                    rect()
+                        .width(Size::px(75.))
+                        .height(Size::px(50.))
+                        .background((120, 120, 255)),
+                ),
+        )
+        .child(
+            paragraph()
+                .width(Size::fill())
+                .span(Span::new("مرحبا بالعالم, hello world")),
+        )
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use freya::{
+    prelude::*,
+    query::*,
+};
+
+const PAGE_SIZE: usize = 30;
+
+#[derive(Clone, PartialEq, Hash, Eq)]
+struct GetPosts;
+
+impl InfiniteQueryCapability for GetPosts {
+    type Page = Vec<String>;
+    type Err = ();
+    type Keys = ();
+    type Cursor = usize;
+
+    fn initial_cursor(&self, _keys: &Self::Keys) -> Self::Cursor {
+        0
+    }
+
+    async fn run(
+        &self,
+        _keys: &Self::Keys,
+        cursor: &Self::Cursor,
+    ) -> Result<Self::Page, Self::Err> {
+        async_io::Timer::after(Duration::from_millis(500)).await;
+        Ok((cursor * PAGE_SIZE..(cursor + 1) * PAGE_SIZE)
+            .map(|i| format!("Post {i}"))
+            .collect())
+    }
+
+    fn next_cursor(&self, page: &InfinitePage<Self>) -> Option<Self::Cursor> {
+        // Only 10 pages
+        (page.cursor < 9).then_some(page.cursor + 1)
+    }
+}
+
+fn app() -> impl IntoElement {
+    let posts = use_infinite_query(InfiniteQuery::new((), GetPosts));
+
+    let state = posts.read();
+    let state = state.state();
+    let items = state.pages_data().flatten().cloned().collect::<Vec<_>>();
+    let loading = state.is_loading();
+
+    rect()
+        .expanded()
+        .content(Content::flex())
+        .child(
+            VirtualScrollView::new_with_data(items.clone(), |i, items| {
+                label()
+                    .key(i)
+                    .height(Size::px(25.))
+                    .text(items[i].clone())
+                    .into()
+            })
+            .length(items.len())
+            .item_size(25.)
+            .height(Size::flex(1.))
+            .on_end_reached(move |_| posts.fetch_next_page()),
+        )
+        .maybe_child(loading.then(|| label().text("Loading...")))
+}
+
+fn main() {
+    launch(LaunchConfig::new().with_window(WindowConfig::new(app)))
+}